@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use crate::{ConstraintKey, Solver};
+
+pub(crate) type BaselineProvider = Arc<dyn Fn(f64, f64) -> Baseline + Send + Sync>;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Baseline {
+    pub first: f64,
+    pub last: f64,
+}
+
+impl Solver {
+    // Baseline methods:
+
+    pub fn set_baseline_provider<F>(&mut self, constraint_key: ConstraintKey, provider: F) -> bool
+    where
+        F: 'static + Fn(f64, f64) -> Baseline + Send + Sync,
+    {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key {
+            self.baseline_providers
+                .insert(constraint_key, Arc::new(provider));
+            self.mark_dirty(constraint_key, self.region(constraint_key));
+        };
+        contains_constraint_key
+    }
+
+    pub fn remove_baseline_provider(&mut self, constraint_key: ConstraintKey) -> bool {
+        let did_remove = self.baseline_providers.remove(&constraint_key).is_some();
+        if did_remove {
+            self.mark_dirty(constraint_key, self.region(constraint_key));
+        };
+        did_remove
+    }
+
+    pub fn get_baseline(&self, constraint_key: ConstraintKey) -> Option<Baseline> {
+        self.solve_outputs.get(&constraint_key)?.baseline
+    }
+}
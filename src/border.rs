@@ -0,0 +1,34 @@
+use crate::{ConstraintKey, Frame, Padding, Solver};
+
+impl Solver {
+    // Border methods:
+
+    pub fn get_border_box(&self, constraint_key: ConstraintKey) -> Option<Frame> {
+        self.get_frame(constraint_key)
+    }
+
+    pub fn get_padding_box(&self, constraint_key: ConstraintKey) -> Option<Frame> {
+        let frame = self.get_frame(constraint_key)?;
+        let border = self.resolved_constraint(constraint_key).content.border;
+        Some(inset_frame(frame, border))
+    }
+
+    pub fn get_content_box(&self, constraint_key: ConstraintKey) -> Option<Frame> {
+        let frame = self.get_frame(constraint_key)?;
+        Some(inset_frame(frame, self.resolved_insets(constraint_key)))
+    }
+}
+
+fn inset_frame(frame: Frame, insets: Padding) -> Frame {
+    let offset_x = frame.offset_x + insets.left.min(frame.length_x);
+    let offset_y = frame.offset_y + insets.top.min(frame.length_y);
+    let end_x = (frame.offset_x + frame.length_x - insets.right).max(offset_x);
+    let end_y = (frame.offset_y + frame.length_y - insets.bottom).max(offset_y);
+
+    Frame {
+        offset_x,
+        length_x: end_x - offset_x,
+        offset_y,
+        length_y: end_y - offset_y,
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{is_valid_root, Constraint, NodeId, Solver};
+
+#[derive(Default, Clone)]
+pub struct SolverBuilder {
+    solver: Solver,
+}
+
+impl SolverBuilder {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            solver: Solver::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(
+        &mut self,
+        parent_node_id: Option<NodeId>,
+        node_id: NodeId,
+        constraint: Constraint,
+    ) -> bool {
+        let solver = &mut self.solver;
+        if solver.constraint_keys_by_id.contains_key(&node_id) {
+            return false;
+        };
+
+        let constraint_key = match parent_node_id {
+            Some(parent_node_id) => {
+                let Some(&parent_constraint_key) =
+                    solver.constraint_keys_by_id.get(&parent_node_id)
+                else {
+                    return false;
+                };
+                let constraint_key = solver
+                    .constraint_tree
+                    .insert(constraint, parent_constraint_key)
+                    .unwrap();
+                solver
+                    .parent_keys
+                    .insert(constraint_key, parent_constraint_key);
+                constraint_key
+            }
+            None => {
+                if !solver.constraint_tree.is_empty() || !is_valid_root(constraint) {
+                    return false;
+                };
+                solver.constraint_tree.insert_root(constraint)
+            }
+        };
+
+        solver.node_ids.insert(constraint_key, node_id);
+        solver.constraint_keys_by_id.insert(node_id, constraint_key);
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.solver.node_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solver.is_empty()
+    }
+
+    pub fn build(self) -> Solver {
+        let mut solver = self.solver;
+        solver.is_dirty = !solver.constraint_tree.is_empty();
+        solver
+    }
+}
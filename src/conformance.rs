@@ -0,0 +1,103 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{Frame, ReproBundle, ReproNode, Solver};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConformanceCase {
+    pub path: PathBuf,
+    pub outcome: ConformanceOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConformanceOutcome {
+    Passed,
+    Failed { diffs: Vec<FrameDiff> },
+    Invalid,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameDiff {
+    pub path: Vec<usize>,
+    pub expected: Option<Frame>,
+    pub actual: Option<Frame>,
+}
+
+pub fn run_conformance<P, F>(directory: P, decode: F) -> io::Result<Vec<ConformanceCase>>
+where
+    P: AsRef<Path>,
+    F: Fn(&[u8]) -> Option<ReproBundle>,
+{
+    let mut paths = fs::read_dir(directory)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.retain(|path| path.is_file());
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let bytes = fs::read(&path)?;
+            let outcome = match decode(&bytes) {
+                Some(bundle) => run_conformance_case(&bundle),
+                None => ConformanceOutcome::Invalid,
+            };
+            Ok(ConformanceCase { path, outcome })
+        })
+        .collect()
+}
+
+pub fn run_conformance_case(bundle: &ReproBundle) -> ConformanceOutcome {
+    fn strip_frames(repro_node: &mut ReproNode) {
+        repro_node.frame = None;
+        repro_node.children.iter_mut().for_each(strip_frames);
+    }
+
+    fn diff_nodes(
+        expected: &ReproNode,
+        actual: &ReproNode,
+        path: &mut Vec<usize>,
+        diffs: &mut Vec<FrameDiff>,
+    ) {
+        if expected.frame != actual.frame {
+            diffs.push(FrameDiff {
+                path: path.clone(),
+                expected: expected.frame,
+                actual: actual.frame,
+            });
+        };
+
+        for (index, (expected, actual)) in
+            expected.children.iter().zip(&actual.children).enumerate()
+        {
+            path.push(index);
+            diff_nodes(expected, actual, path, diffs);
+            path.pop();
+        }
+    }
+
+    let Some((length_x, length_y)) = bundle.solve_lengths else {
+        return ConformanceOutcome::Invalid;
+    };
+
+    let mut input = bundle.clone();
+    if let Some(root) = &mut input.root {
+        strip_frames(root);
+    };
+    let Some(mut solver) = Solver::from_repro(input) else {
+        return ConformanceOutcome::Invalid;
+    };
+    solver.solve(length_x, length_y);
+
+    let mut diffs = vec![];
+    if let (Some(expected), Some(actual)) = (&bundle.root, &solver.dump_repro().root) {
+        diff_nodes(expected, actual, &mut vec![], &mut diffs);
+    };
+
+    match diffs.is_empty() {
+        true => ConformanceOutcome::Passed,
+        false => ConformanceOutcome::Failed { diffs },
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use crate::{Constraint, NodeId, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StructuralChange {
+    Inserted {
+        node_id: NodeId,
+        parent_node_id: Option<NodeId>,
+        index: usize,
+        constraint: Constraint,
+    },
+    Removed {
+        node_id: NodeId,
+    },
+    Moved {
+        node_id: NodeId,
+        parent_node_id: Option<NodeId>,
+        index: usize,
+    },
+    Modified {
+        node_id: NodeId,
+        constraint: Constraint,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Placement {
+    parent_node_id: Option<NodeId>,
+    index: usize,
+    shared_index: usize,
+    constraint: Constraint,
+}
+
+impl Solver {
+    // Diff methods:
+
+    pub fn diff_structure(&self, other: &Solver) -> Vec<StructuralChange> {
+        let placements = self.placements(other);
+        let other_placements = other.placements(self);
+        let placements_by_id = placements.iter().copied().collect::<BTreeMap<_, _>>();
+
+        let mut changes = placements
+            .iter()
+            .filter(|&&(node_id, _)| other.key_for_id(node_id).is_none())
+            .map(|&(node_id, _)| StructuralChange::Removed { node_id })
+            .collect::<Vec<_>>();
+
+        for (node_id, other_placement) in other_placements {
+            let Some(placement) = placements_by_id.get(&node_id) else {
+                changes.push(StructuralChange::Inserted {
+                    node_id,
+                    parent_node_id: other_placement.parent_node_id,
+                    index: other_placement.index,
+                    constraint: other_placement.constraint,
+                });
+                continue;
+            };
+
+            let is_moved = placement.parent_node_id != other_placement.parent_node_id
+                || placement.shared_index != other_placement.shared_index;
+            if is_moved {
+                changes.push(StructuralChange::Moved {
+                    node_id,
+                    parent_node_id: other_placement.parent_node_id,
+                    index: other_placement.index,
+                });
+            };
+
+            if placement.constraint != other_placement.constraint {
+                changes.push(StructuralChange::Modified {
+                    node_id,
+                    constraint: other_placement.constraint,
+                });
+            };
+        }
+
+        changes
+    }
+
+    fn placements(&self, other: &Solver) -> Vec<(NodeId, Placement)> {
+        let mut placements = vec![];
+        let mut to_visit = self
+            .constraint_tree
+            .root_key()
+            .map(|root_key| (root_key, None, 0, 0))
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        while let Some((constraint_key, parent_node_id, index, shared_index)) = to_visit.pop() {
+            let constraint_node = self.constraint_tree.get(constraint_key).unwrap();
+            let node_id = self.node_id(constraint_key);
+
+            if let Some(node_id) = node_id {
+                placements.push((
+                    node_id,
+                    Placement {
+                        parent_node_id,
+                        index,
+                        shared_index,
+                        constraint: *constraint_node.value,
+                    },
+                ));
+            };
+
+            let mut shared_index = 0;
+            let mut child_keys = Vec::with_capacity(constraint_node.child_keys.len());
+            for (index, &child_constraint_key) in constraint_node.child_keys.iter().enumerate() {
+                child_keys.push((
+                    child_constraint_key,
+                    node_id.or(parent_node_id),
+                    index,
+                    shared_index,
+                ));
+                let is_shared = self
+                    .node_id(child_constraint_key)
+                    .is_some_and(|child_node_id| other.key_for_id(child_node_id).is_some());
+                if is_shared {
+                    shared_index += 1;
+                };
+            }
+            to_visit.extend(child_keys.into_iter().rev());
+        }
+
+        placements
+    }
+}
@@ -0,0 +1,46 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{ConstraintKey, Frame, Solver};
+
+pub type SolverRef = Arc<Mutex<Solver>>;
+
+impl Solver {
+    // Embedding methods:
+
+    pub fn set_embedded(&mut self, constraint_key: ConstraintKey, solver: SolverRef) -> bool {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key {
+            self.embeddeds.insert(constraint_key, solver);
+            self.mark_dirty(constraint_key, self.region(constraint_key));
+        };
+        contains_constraint_key
+    }
+
+    pub fn remove_embedded(&mut self, constraint_key: ConstraintKey) -> bool {
+        let did_remove = self.embeddeds.remove(&constraint_key).is_some();
+        if did_remove {
+            self.mark_dirty(constraint_key, self.region(constraint_key));
+        };
+        did_remove
+    }
+
+    pub fn embedded(&self, constraint_key: ConstraintKey) -> Option<SolverRef> {
+        self.embeddeds.get(&constraint_key).cloned()
+    }
+
+    pub(crate) fn solve_embeddeds(&self) {
+        for (&constraint_key, embedded) in &self.embeddeds {
+            if let Some(frame) = self.get_frame(constraint_key) {
+                solve_embedded(embedded, frame);
+            };
+        }
+    }
+}
+
+fn solve_embedded(solver: &SolverRef, frame: Frame) {
+    let mut solver = solver.lock().unwrap();
+    if solver.last_solve_lengths != Some((frame.length_x, frame.length_y)) {
+        solver.is_dirty = true;
+    };
+    solver.solve(frame.length_x, frame.length_y);
+}
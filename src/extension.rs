@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+
+use crate::{ConstraintKey, Solver};
+
+pub type ExtensionMap = BTreeMap<String, ExtensionValue>;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum ExtensionValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Unsigned(u64),
+    Float(f64),
+    String(String),
+    Sequence(Vec<Self>),
+    Map(ExtensionMap),
+}
+
+impl Solver {
+    // Extension methods:
+
+    pub fn set_extension(
+        &mut self,
+        constraint_key: ConstraintKey,
+        name: impl Into<String>,
+        value: ExtensionValue,
+    ) -> bool {
+        let is_valid = self.constraint_tree.contains(constraint_key);
+        if is_valid {
+            self.extensions
+                .entry(constraint_key)
+                .or_default()
+                .insert(name.into(), value);
+        };
+        is_valid
+    }
+
+    pub fn remove_extension(
+        &mut self,
+        constraint_key: ConstraintKey,
+        name: &str,
+    ) -> Option<ExtensionValue> {
+        let extensions = self.extensions.get_mut(&constraint_key)?;
+        let value = extensions.remove(name);
+        if extensions.is_empty() {
+            self.extensions.remove(&constraint_key);
+        };
+        value
+    }
+
+    pub fn extension(&self, constraint_key: ConstraintKey, name: &str) -> Option<&ExtensionValue> {
+        self.extensions.get(&constraint_key)?.get(name)
+    }
+
+    pub fn extensions(&self, constraint_key: ConstraintKey) -> Option<&ExtensionMap> {
+        self.extensions.get(&constraint_key)
+    }
+}
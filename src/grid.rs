@@ -0,0 +1,120 @@
+use std::ops::Range;
+
+use crate::{ConstraintKey, FillType, Solver};
+
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Grid {
+    pub columns: Vec<FillType>,
+    pub rows: Vec<FillType>,
+    pub areas: Vec<Vec<String>>,
+}
+
+impl Grid {
+    pub(crate) fn area(&self, name: &str) -> Option<(Range<usize>, Range<usize>)> {
+        let cells = self
+            .areas
+            .iter()
+            .enumerate()
+            .flat_map(|(row, names)| {
+                names
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, area)| area == name)
+                    .map(move |(column, _)| (row, column))
+            })
+            .collect::<Vec<_>>();
+        let (rows, columns) = cells.iter().fold(
+            None,
+            |span: Option<(Range<usize>, Range<usize>)>, &(row, column)| {
+                Some(match span {
+                    Some((rows, columns)) => (
+                        rows.start.min(row)..rows.end.max(row + 1),
+                        columns.start.min(column)..columns.end.max(column + 1),
+                    ),
+                    None => (row..row + 1, column..column + 1),
+                })
+            },
+        )?;
+
+        let is_rectangular = cells.len() == rows.len() * columns.len();
+        is_rectangular.then_some((rows, columns))
+    }
+
+    pub(crate) fn is_well_formed(&self) -> bool {
+        self.areas
+            .iter()
+            .flatten()
+            .all(|name| self.area(name).is_some())
+    }
+}
+
+impl Solver {
+    // Grid methods:
+
+    pub fn set_grid(&mut self, constraint_key: ConstraintKey, grid: Option<Grid>) -> bool {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key && grid.as_ref() != self.grid(constraint_key) {
+            match grid {
+                Some(grid) => self.grids.insert(constraint_key, grid),
+                None => self.grids.remove(&constraint_key),
+            };
+            self.mark_dirty(constraint_key, self.region(constraint_key));
+        };
+        contains_constraint_key
+    }
+
+    pub fn grid(&self, constraint_key: ConstraintKey) -> Option<&Grid> {
+        self.grids.get(&constraint_key)
+    }
+
+    pub fn set_grid_area(&mut self, constraint_key: ConstraintKey, area: Option<String>) -> bool {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key && area.as_deref() != self.grid_area(constraint_key) {
+            match area {
+                Some(area) => self.grid_areas.insert(constraint_key, area),
+                None => self.grid_areas.remove(&constraint_key),
+            };
+            self.mark_grid_parent_dirty(constraint_key);
+        };
+        contains_constraint_key
+    }
+
+    pub fn grid_area(&self, constraint_key: ConstraintKey) -> Option<&str> {
+        self.grid_areas.get(&constraint_key).map(String::as_str)
+    }
+
+    pub fn set_grid_span(
+        &mut self,
+        constraint_key: ConstraintKey,
+        column_span: usize,
+        row_span: usize,
+    ) -> bool {
+        let is_valid =
+            self.constraint_tree.contains(constraint_key) && column_span > 0 && row_span > 0;
+        if is_valid && (column_span, row_span) != self.grid_span(constraint_key) {
+            match (column_span, row_span) {
+                (1, 1) => self.grid_spans.remove(&constraint_key),
+                spans => self.grid_spans.insert(constraint_key, spans),
+            };
+            self.mark_grid_parent_dirty(constraint_key);
+        };
+        is_valid
+    }
+
+    pub fn grid_span(&self, constraint_key: ConstraintKey) -> (usize, usize) {
+        self.grid_spans
+            .get(&constraint_key)
+            .copied()
+            .unwrap_or((1, 1))
+    }
+
+    fn mark_grid_parent_dirty(&mut self, constraint_key: ConstraintKey) {
+        let parent_constraint_key = self
+            .parent_keys
+            .get(&constraint_key)
+            .copied()
+            .unwrap_or(constraint_key);
+        self.mark_dirty(parent_constraint_key, self.region(parent_constraint_key));
+    }
+}
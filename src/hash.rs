@@ -0,0 +1,45 @@
+use crate::Solver;
+
+pub(crate) const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+impl Solver {
+    // Hash methods:
+
+    pub fn frames_hash(&self) -> u64 {
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut to_visit_constraint_keys = self
+            .constraint_tree
+            .root_key()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        while let Some(constraint_key) = to_visit_constraint_keys.pop() {
+            let constraint_node = self.constraint_tree.get(constraint_key).unwrap();
+            let frame = self.get_frame(constraint_key).unwrap_or_default();
+
+            for length in [
+                frame.offset_x,
+                frame.length_x,
+                frame.offset_y,
+                frame.length_y,
+            ] {
+                hash = fnv1a(hash, &length.to_bits().to_le_bytes());
+            }
+            hash = fnv1a(
+                hash,
+                &(constraint_node.child_keys.len() as u64).to_le_bytes(),
+            );
+
+            to_visit_constraint_keys.extend(constraint_node.child_keys.iter().rev().copied());
+        }
+
+        hash
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{ConstraintKey, Display, Padding, Solver, Visibility};
+
+impl Solver {
+    // Hit-test methods:
+
+    pub fn hit_test(&self, x: f64, y: f64) -> Option<ConstraintKey> {
+        self.hit_test_with(x, y, false)
+    }
+
+    pub fn hit_test_with(&self, x: f64, y: f64, include_clipped: bool) -> Option<ConstraintKey> {
+        if self.is_dirty() {
+            return None;
+        };
+
+        let mut hit = None::<(i32, ConstraintKey)>;
+        let mut to_visit = self
+            .constraint_tree
+            .root_key()
+            .map(|root_constraint_key| (root_constraint_key, 0))
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        while let Some((constraint_key, parent_layer)) = to_visit.pop() {
+            let constraint_node = self.constraint_tree.get(constraint_key).unwrap();
+            let constraint = constraint_node.value;
+            if constraint.display == Display::None || constraint.visibility == Visibility::Hidden {
+                continue;
+            };
+
+            let layer = constraint.layer.unwrap_or(parent_layer);
+            let is_on_top = hit.is_none_or(|(hit_layer, _)| layer >= hit_layer);
+            if is_on_top
+                && self.contains_point(constraint_key, x, y, constraint.hit_slop)
+                && self.contains_rounded_point(constraint_key, x, y)
+            {
+                hit = Some((layer, constraint_key));
+            };
+
+            let is_clipping = !include_clipped && self.is_clipping_hits(constraint_key);
+            if !is_clipping || self.contains_point(constraint_key, x, y, Padding::default()) {
+                to_visit.extend(
+                    constraint_node
+                        .child_keys
+                        .iter()
+                        .rev()
+                        .map(|&child_constraint_key| (child_constraint_key, layer)),
+                );
+            };
+        }
+
+        hit.map(|(_, constraint_key)| constraint_key)
+    }
+
+    pub fn is_clipping_hits(&self, constraint_key: ConstraintKey) -> bool {
+        self.hit_clips.contains(&constraint_key)
+    }
+
+    pub fn set_clipping_hits(&mut self, constraint_key: ConstraintKey, is_clipping: bool) -> bool {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key {
+            match is_clipping {
+                true => self.hit_clips.insert(constraint_key),
+                false => self.hit_clips.remove(&constraint_key),
+            };
+        };
+        contains_constraint_key
+    }
+
+    pub(crate) fn contains_point(
+        &self,
+        constraint_key: ConstraintKey,
+        x: f64,
+        y: f64,
+        hit_slop: Padding,
+    ) -> bool {
+        let Some(frame) = self.get_frame(constraint_key) else {
+            return false;
+        };
+        let Some((local_x, local_y)) = self.to_local(constraint_key, x, y) else {
+            return false;
+        };
+
+        let is_within_x = -hit_slop.left <= local_x && local_x < frame.length_x + hit_slop.right;
+        let is_within_y = -hit_slop.top <= local_y && local_y < frame.length_y + hit_slop.bottom;
+        is_within_x && is_within_y
+    }
+
+    fn contains_rounded_point(&self, constraint_key: ConstraintKey, x: f64, y: f64) -> bool {
+        let (Some(frame), Some(corner_radius)) = (
+            self.get_frame(constraint_key),
+            self.get_corner_radius(constraint_key),
+        ) else {
+            return false;
+        };
+        let Some((local_x, local_y)) = self.to_local(constraint_key, x, y) else {
+            return false;
+        };
+
+        let nearest_x = local_x.clamp(corner_radius, frame.length_x - corner_radius);
+        let nearest_y = local_y.clamp(corner_radius, frame.length_y - corner_radius);
+        let (delta_x, delta_y) = (local_x - nearest_x, local_y - nearest_y);
+        let is_in_corner = corner_radius > 0. && delta_x != 0. && delta_y != 0.;
+
+        !is_in_corner || delta_x * delta_x + delta_y * delta_y <= corner_radius * corner_radius
+    }
+}
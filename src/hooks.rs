@@ -0,0 +1,78 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{ConstraintKey, Frame, Solver};
+
+pub(crate) type Hook = Arc<Mutex<dyn FnMut(ConstraintKey, Option<Frame>) + Send>>;
+
+pub(crate) type OnLayout = Arc<Mutex<dyn FnMut(Frame) + Send>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Phase {
+    Measure,
+    Arrange,
+    Finalize,
+}
+
+impl Solver {
+    // Hook methods:
+
+    pub fn set_hook<F>(&mut self, constraint_key: ConstraintKey, phase: Phase, hook: F) -> bool
+    where
+        F: 'static + FnMut(ConstraintKey, Option<Frame>) + Send,
+    {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key {
+            self.hooks
+                .insert((constraint_key, phase), Arc::new(Mutex::new(hook)));
+        };
+        contains_constraint_key
+    }
+
+    pub fn remove_hook(&mut self, constraint_key: ConstraintKey, phase: Phase) -> bool {
+        self.hooks.remove(&(constraint_key, phase)).is_some()
+    }
+
+    // On-layout methods:
+
+    pub fn set_on_layout<F>(&mut self, constraint_key: ConstraintKey, on_layout: F) -> bool
+    where
+        F: 'static + FnMut(Frame) + Send,
+    {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key {
+            self.on_layouts
+                .insert(constraint_key, Arc::new(Mutex::new(on_layout)));
+            self.last_frames.remove(&constraint_key);
+        };
+        contains_constraint_key
+    }
+
+    pub fn remove_on_layout(&mut self, constraint_key: ConstraintKey) -> bool {
+        self.last_frames.remove(&constraint_key);
+        self.on_layouts.remove(&constraint_key).is_some()
+    }
+
+    pub(crate) fn run_on_layouts(&mut self) {
+        for (&constraint_key, on_layout) in &self.on_layouts {
+            let Some(frame) = self.get_frame(constraint_key) else {
+                continue;
+            };
+            if self.last_frames.insert(constraint_key, frame) != Some(frame) {
+                run_on_layout(on_layout, frame);
+            };
+        }
+    }
+}
+
+pub(crate) fn run_on_layout(on_layout: &OnLayout, frame: Frame) {
+    let mut on_layout = on_layout.lock().unwrap();
+    (*on_layout)(frame);
+}
+
+pub(crate) fn run_hook(hook: Option<&Hook>, constraint_key: ConstraintKey, frame: Option<Frame>) {
+    if let Some(hook) = hook {
+        let mut hook = hook.lock().unwrap();
+        (*hook)(constraint_key, frame);
+    };
+}
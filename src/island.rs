@@ -0,0 +1,137 @@
+use std::any::Any;
+
+use crate::{solver::solve_subtree, ConstraintKey, Fill, FillType, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Island {
+    pub(crate) length_x: f64,
+    pub(crate) length_y: f64,
+    pub(crate) is_dirty: bool,
+}
+
+impl Island {
+    pub(crate) fn fill(&self) -> Fill {
+        Fill::Absolute {
+            x: FillType::Exact(self.length_x),
+            y: FillType::Exact(self.length_y),
+        }
+    }
+}
+
+impl Solver {
+    // Island methods:
+
+    pub fn is_island(&self, constraint_key: ConstraintKey) -> bool {
+        self.islands.contains_key(&constraint_key)
+    }
+
+    pub fn is_island_dirty(&self, constraint_key: ConstraintKey) -> Option<bool> {
+        self.islands
+            .get(&constraint_key)
+            .map(|island| island.is_dirty)
+    }
+
+    pub fn set_island(
+        &mut self,
+        constraint_key: ConstraintKey,
+        length_x: f64,
+        length_y: f64,
+    ) -> bool {
+        let is_root = self.constraint_tree.root_key() == Some(constraint_key);
+        let is_valid = self.constraint_tree.contains(constraint_key) && !is_root;
+
+        if is_valid {
+            let island = Island {
+                length_x: length_x.max(0.),
+                length_y: length_y.max(0.),
+                is_dirty: true,
+            };
+            self.islands.insert(constraint_key, island);
+            self.mark_dirty(constraint_key, None);
+        };
+
+        is_valid
+    }
+
+    pub fn remove_island(&mut self, constraint_key: ConstraintKey) -> bool {
+        let did_remove = self.islands.remove(&constraint_key).is_some();
+        if did_remove {
+            self.mark_dirty(constraint_key, None);
+        };
+        did_remove
+    }
+
+    pub fn solve_island(&mut self, constraint_key: ConstraintKey) -> bool {
+        let is_solved = self.solve_island_measured(constraint_key, None);
+        if is_solved {
+            self.solve_embeddeds();
+        };
+        is_solved
+    }
+
+    pub(crate) fn solve_island_measured(
+        &mut self,
+        constraint_key: ConstraintKey,
+        measure_context: Option<&mut dyn Any>,
+    ) -> bool {
+        let is_solvable = !self.is_dirty
+            && self.constraint_tree.contains(constraint_key)
+            && self.islands.contains_key(&constraint_key)
+            && self.key_map.contains_key(&constraint_key);
+
+        if is_solvable {
+            let frame_key = self.key_map[&constraint_key];
+            let frame = *self.frame_tree.get(frame_key).unwrap().value;
+            let mut context = self.context();
+            context.measure_context = measure_context;
+            solve_subtree(&mut context, constraint_key, frame);
+            self.islands.get_mut(&constraint_key).unwrap().is_dirty = false;
+            self.dirty_keys
+                .retain(|_, region| *region != Some(constraint_key));
+            self.refresh_traversal_subtree(constraint_key);
+        };
+
+        is_solvable
+    }
+
+    pub(crate) fn region(&self, constraint_key: ConstraintKey) -> Option<ConstraintKey> {
+        let mut current_constraint_key = Some(constraint_key);
+
+        while let Some(constraint_key) = current_constraint_key {
+            if self.islands.contains_key(&constraint_key) {
+                return Some(constraint_key);
+            };
+            current_constraint_key = self.parent_keys.get(&constraint_key).copied();
+        }
+
+        None
+    }
+
+    pub(crate) fn mark_dirty(
+        &mut self,
+        constraint_key: ConstraintKey,
+        region: Option<ConstraintKey>,
+    ) {
+        self.dirty_keys.insert(constraint_key, region);
+        match region.and_then(|island_constraint_key| self.islands.get_mut(&island_constraint_key))
+        {
+            Some(island) => island.is_dirty = true,
+            None => self.is_dirty = true,
+        }
+    }
+
+    pub(crate) fn is_frame_dirty(&self, constraint_key: ConstraintKey) -> bool {
+        let region = self
+            .parent_keys
+            .get(&constraint_key)
+            .and_then(|&parent_constraint_key| self.region(parent_constraint_key));
+
+        let is_region_dirty = match region
+            .and_then(|island_constraint_key| self.islands.get(&island_constraint_key))
+        {
+            Some(island) => island.is_dirty,
+            None => self.is_dirty,
+        };
+        is_region_dirty || self.is_measure_stale(constraint_key)
+    }
+}
@@ -0,0 +1,102 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::{ConstraintKey, LintDiagnostic, Solver};
+
+impl Solver {
+    // Label methods:
+
+    pub fn set_label(&mut self, constraint_key: ConstraintKey, label: impl Into<String>) -> bool {
+        let is_valid = self.constraint_tree.contains(constraint_key);
+        if is_valid {
+            self.labels.insert(constraint_key, label.into());
+        };
+        is_valid
+    }
+
+    pub fn remove_label(&mut self, constraint_key: ConstraintKey) -> Option<String> {
+        self.labels.remove(&constraint_key)
+    }
+
+    pub fn label(&self, constraint_key: ConstraintKey) -> Option<&str> {
+        self.labels.get(&constraint_key).map(String::as_str)
+    }
+
+    pub fn describe(&self, constraint_key: ConstraintKey) -> String {
+        describe(&self.labels, &self.parent_keys, constraint_key)
+    }
+
+    pub fn describe_diagnostic(&self, diagnostic: LintDiagnostic) -> String {
+        let (name, constraint_key) = match diagnostic {
+            LintDiagnostic::ZeroScaleWeight { constraint_key } => {
+                ("zero scale weight", constraint_key)
+            }
+            LintDiagnostic::UnmeasuredMinimize { constraint_key } => {
+                ("unmeasured minimize", constraint_key)
+            }
+            LintDiagnostic::PaddingExceedsLength { constraint_key } => {
+                ("padding exceeds length", constraint_key)
+            }
+            LintDiagnostic::UnreachableLayoutIgnored { constraint_key } => {
+                ("unreachable layout-ignored child", constraint_key)
+            }
+            LintDiagnostic::MismatchedMeasureContext { constraint_key } => {
+                ("mismatched measure context", constraint_key)
+            }
+            LintDiagnostic::IgnoredMargin { constraint_key } => ("ignored margin", constraint_key),
+            LintDiagnostic::IgnoredMarginCollapse { constraint_key } => {
+                ("ignored margin collapse", constraint_key)
+            }
+            LintDiagnostic::IgnoredGap { constraint_key } => ("ignored gap", constraint_key),
+            LintDiagnostic::IgnoredGridPlacement { constraint_key } => {
+                ("ignored grid placement", constraint_key)
+            }
+            LintDiagnostic::MalformedGridArea { constraint_key } => {
+                ("malformed grid area", constraint_key)
+            }
+        };
+        format!("{}: {}", self.describe(constraint_key), name)
+    }
+}
+
+impl fmt::Debug for Solver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut nodes = f.debug_map();
+        let mut to_visit = self
+            .constraint_tree
+            .root_key()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        while let Some(constraint_key) = to_visit.pop() {
+            let constraint_node = self.constraint_tree.get(constraint_key).unwrap();
+            nodes.entry(
+                &self.describe(constraint_key),
+                &(constraint_node.value, self.get_frame(constraint_key)),
+            );
+            to_visit.extend(constraint_node.child_keys.iter().rev());
+        }
+
+        nodes.finish()
+    }
+}
+
+pub(crate) fn describe(
+    labels: &BTreeMap<ConstraintKey, String>,
+    parent_keys: &BTreeMap<ConstraintKey, ConstraintKey>,
+    constraint_key: ConstraintKey,
+) -> String {
+    let mut segments = vec![];
+    let mut current_constraint_key = Some(constraint_key);
+
+    while let Some(constraint_key) = current_constraint_key {
+        let segment = match labels.get(&constraint_key) {
+            Some(label) => label.clone(),
+            None => format!("{:?}", constraint_key),
+        };
+        segments.push(segment);
+        current_constraint_key = parent_keys.get(&constraint_key).copied();
+    }
+
+    segments.reverse();
+    segments.join("/")
+}
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use crate::{
+    hash::{fnv1a, FNV_OFFSET_BASIS},
+    Constraint, ConstraintKey, Frame, Solver,
+};
+
+pub trait CustomLayout: Send + Sync {
+    fn measure(&self, children: &[LayoutChild], available_x: f64, available_y: f64) -> (f64, f64);
+
+    fn arrange(&self, children: &[LayoutChild], content_frame: Frame) -> Vec<Frame>;
+
+    fn measure_y_for_x(&self, children: &[LayoutChild], length_x: f64) -> f64 {
+        self.measure(children, length_x, f64::INFINITY).1
+    }
+
+    fn measure_x_for_y(&self, children: &[LayoutChild], length_y: f64) -> f64 {
+        self.measure(children, f64::INFINITY, length_y).0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutId(u64);
+
+impl LayoutId {
+    pub fn new(name: &str) -> Self {
+        Self(fnv1a(FNV_OFFSET_BASIS, name.as_bytes()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutChild {
+    pub constraint_key: ConstraintKey,
+    pub constraint: Constraint,
+    pub length_x: f64,
+    pub length_y: f64,
+}
+
+impl Solver {
+    // Custom layout methods:
+
+    pub fn set_layout<L>(&mut self, constraint_key: ConstraintKey, layout: L) -> bool
+    where
+        L: 'static + CustomLayout,
+    {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key {
+            self.layouts.insert(constraint_key, Arc::new(layout));
+            self.mark_dirty(constraint_key, self.region(constraint_key));
+        };
+        contains_constraint_key
+    }
+
+    pub fn remove_layout(&mut self, constraint_key: ConstraintKey) -> bool {
+        let did_remove = self.layouts.remove(&constraint_key).is_some();
+        if did_remove {
+            self.mark_dirty(constraint_key, self.region(constraint_key));
+        };
+        did_remove
+    }
+
+    // Layout registry methods:
+
+    pub fn register_layout<L>(&mut self, name: impl Into<String>, layout: L) -> LayoutId
+    where
+        L: 'static + CustomLayout,
+    {
+        let name = name.into();
+        let layout_id = LayoutId::new(&name);
+        self.registry.insert(layout_id, (name, Arc::new(layout)));
+        self.is_dirty = true;
+        layout_id
+    }
+
+    pub fn unregister_layout(&mut self, layout_id: LayoutId) -> bool {
+        let did_unregister = self.registry.remove(&layout_id).is_some();
+        if did_unregister {
+            self.is_dirty = true;
+        };
+        did_unregister
+    }
+
+    pub fn is_layout_registered(&self, layout_id: LayoutId) -> bool {
+        self.registry.contains_key(&layout_id)
+    }
+
+    pub fn layout_name(&self, layout_id: LayoutId) -> Option<&str> {
+        self.registry.get(&layout_id).map(|(name, _)| name.as_str())
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{Align, AlignFraction, Bounds, Constraint, Content, Fill, FillType, Padding, Position};
+
+impl Constraint {
+    pub fn lerp(&self, other: &Self, t: f64) -> Self {
+        Self {
+            fill: lerp_fill(self.fill, other.fill, t),
+            basis: lerp_option(self.basis, other.basis, t),
+            shrink: lerp_option(self.shrink, other.shrink, t),
+            content: lerp_content(self.content, other.content, t),
+            bounds: Bounds {
+                min_x: lerp(self.bounds.min_x, other.bounds.min_x, t),
+                min_y: lerp(self.bounds.min_y, other.bounds.min_y, t),
+                max_x: lerp_option(self.bounds.max_x, other.bounds.max_x, t),
+                max_y: lerp_option(self.bounds.max_y, other.bounds.max_y, t),
+            },
+            margin: lerp_padding(self.margin, other.margin, t),
+            group: step(self.group, other.group, t),
+            layout_ignored: step(self.layout_ignored, other.layout_ignored, t),
+            display: step(self.display, other.display, t),
+            visibility: step(self.visibility, other.visibility, t),
+            position: lerp_position(self.position, other.position, t),
+            nudge_x: lerp(self.nudge_x, other.nudge_x, t),
+            nudge_y: lerp(self.nudge_y, other.nudge_y, t),
+            hit_slop: lerp_padding(self.hit_slop, other.hit_slop, t),
+            corner_radius: lerp(self.corner_radius, other.corner_radius, t),
+            intrinsic_size: match (self.intrinsic_size, other.intrinsic_size) {
+                (Some((x1, y1)), Some((x2, y2))) => Some((lerp(x1, x2, t), lerp(y1, y2, t))),
+                (intrinsic_size, other_intrinsic_size) => {
+                    step(intrinsic_size, other_intrinsic_size, t)
+                }
+            },
+            priority: step(self.priority, other.priority, t),
+            order: step(self.order, other.order, t),
+            layer: step(self.layer, other.layer, t),
+            align_self: step(self.align_self, other.align_self, t),
+            justify_self: step(self.justify_self, other.justify_self, t),
+        }
+    }
+}
+
+fn lerp_option(value: Option<f64>, other_value: Option<f64>, t: f64) -> Option<f64> {
+    match (value, other_value) {
+        (Some(value), Some(other_value)) => Some(lerp(value, other_value, t)),
+        (value, other_value) => step(value, other_value, t),
+    }
+}
+
+fn lerp_position(position: Position, other: Position, t: f64) -> Position {
+    match (position, other) {
+        (
+            Position::Absolute {
+                left,
+                right,
+                top,
+                bottom,
+            },
+            Position::Absolute {
+                left: other_left,
+                right: other_right,
+                top: other_top,
+                bottom: other_bottom,
+            },
+        ) => Position::Absolute {
+            left: lerp_option(left, other_left, t),
+            right: lerp_option(right, other_right, t),
+            top: lerp_option(top, other_top, t),
+            bottom: lerp_option(bottom, other_bottom, t),
+        },
+        (position, other) => step(position, other, t),
+    }
+}
+
+fn lerp_content(content: Content, other: Content, t: f64) -> Content {
+    Content {
+        direction: step(content.direction, other.direction, t),
+        padding: lerp_padding(content.padding, other.padding, t),
+        padding_fraction: lerp_padding(content.padding_fraction, other.padding_fraction, t),
+        border: lerp_padding(content.border, other.border, t),
+        align_main: lerp_align(content.align_main, other.align_main, t),
+        align_cross: lerp_align(content.align_cross, other.align_cross, t),
+        align_last_line: match (content.align_last_line, other.align_last_line) {
+            (Some(align), Some(other_align)) => Some(lerp_align(align, other_align, t)),
+            (align, other_align) => step(align, other_align, t),
+        },
+        layout: step(content.layout, other.layout, t),
+        default_child: step(content.default_child, other.default_child, t),
+        stretch_to_tallest: step(content.stretch_to_tallest, other.stretch_to_tallest, t),
+        mode: step(content.mode, other.mode, t),
+        reverse: step(content.reverse, other.reverse, t),
+        collapse_margins: step(content.collapse_margins, other.collapse_margins, t),
+        overflow: step(content.overflow, other.overflow, t),
+        overflow_anchor: step(content.overflow_anchor, other.overflow_anchor, t),
+        precedence: step(content.precedence, other.precedence, t),
+        gap_main: match (content.gap_main, other.gap_main) {
+            (Some(gap_main), Some(other_gap_main)) => {
+                Some(lerp_fill_type(gap_main, other_gap_main, t))
+            }
+            (gap_main, other_gap_main) => step(gap_main, other_gap_main, t),
+        },
+        gap_cross: match (content.gap_cross, other.gap_cross) {
+            (Some(gap_cross), Some(other_gap_cross)) => {
+                Some(lerp_fill_type(gap_cross, other_gap_cross, t))
+            }
+            (gap_cross, other_gap_cross) => step(gap_cross, other_gap_cross, t),
+        },
+    }
+}
+
+fn lerp_fill(fill: Fill, other: Fill, t: f64) -> Fill {
+    match (fill, other) {
+        (Fill::Absolute { x: x1, y: y1 }, Fill::Absolute { x: x2, y: y2 }) => Fill::Absolute {
+            x: lerp_fill_type(x1, x2, t),
+            y: lerp_fill_type(y1, y2, t),
+        },
+        (
+            Fill::Relative {
+                main: main1,
+                cross: cross1,
+            },
+            Fill::Relative {
+                main: main2,
+                cross: cross2,
+            },
+        ) => Fill::Relative {
+            main: lerp_fill_type(main1, main2, t),
+            cross: lerp_fill_type(cross1, cross2, t),
+        },
+        (fill, other) => step(fill, other, t),
+    }
+}
+
+fn lerp_fill_type(fill_type: FillType, other: FillType, t: f64) -> FillType {
+    match (fill_type, other) {
+        (FillType::Exact(length), FillType::Exact(other_length)) => {
+            FillType::Exact(lerp(length, other_length, t))
+        }
+        (FillType::Fraction(fraction), FillType::Fraction(other_fraction)) => {
+            FillType::Fraction(lerp(fraction, other_fraction, t))
+        }
+        (
+            FillType::Bounded {
+                min,
+                preferred,
+                max,
+            },
+            FillType::Bounded {
+                min: other_min,
+                preferred: other_preferred,
+                max: other_max,
+            },
+        ) => FillType::Bounded {
+            min: lerp(min, other_min, t),
+            preferred: lerp(preferred, other_preferred, t),
+            max: lerp(max, other_max, t),
+        },
+        (FillType::Scale(scale), FillType::Scale(other_scale)) => {
+            FillType::Scale(lerp(scale as f64, other_scale as f64, t).round().max(0.) as usize)
+        }
+        (fill_type, other) => step(fill_type, other, t),
+    }
+}
+
+fn lerp_align(align: Align, other: Align, t: f64) -> Align {
+    match (align, other) {
+        (
+            Align::Fraction(AlignFraction(fraction)),
+            Align::Fraction(AlignFraction(other_fraction)),
+        ) => Align::Fraction(AlignFraction(lerp(fraction, other_fraction, t))),
+        (align, other) => step(align, other, t),
+    }
+}
+
+fn lerp_padding(padding: Padding, other: Padding, t: f64) -> Padding {
+    Padding {
+        left: lerp(padding.left, other.left, t),
+        right: lerp(padding.right, other.right, t),
+        top: lerp(padding.top, other.top, t),
+        bottom: lerp(padding.bottom, other.bottom, t),
+        start: lerp(padding.start, other.start, t),
+        end: lerp(padding.end, other.end, t),
+    }
+}
+
+fn lerp(value: f64, other: f64, t: f64) -> f64 {
+    value + (other - value) * t
+}
+
+fn step<T>(value: T, other: T, t: f64) -> T {
+    match t < 0.5 {
+        true => value,
+        false => other,
+    }
+}
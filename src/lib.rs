@@ -1,12 +1,81 @@
+mod baseline;
+mod border;
+mod builder;
+pub mod conformance;
+mod diff;
+mod embed;
+mod extension;
+mod grid;
+mod hash;
+mod hit_test;
+mod hooks;
+mod island;
+mod label;
+mod layout;
+mod lerp;
+mod lines;
+mod lint;
+mod measure;
+mod memory;
+mod node_id;
+mod occupancy;
+mod paint;
+mod pipeline;
+pub mod presets;
+mod repro;
+mod scroll;
+mod sensitivity;
+mod snapshot;
 mod solver;
+mod split;
+mod stage;
+mod transform;
+mod traversal;
+mod virtualize;
 
-use std::collections::BTreeMap;
+use std::{
+    any::{Any, TypeId},
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    time::Duration,
+};
 
 use cherrytree::{Node, Tree};
 use indexmap::IndexSet;
 use slotmap::new_key_type;
 
-use crate::solver::solve;
+use crate::{
+    baseline::BaselineProvider,
+    hooks::{Hook, OnLayout},
+    island::Island,
+    measure::Measure,
+    scroll::Scroll,
+    solver::{
+        minimize_root, partition_layout_ignored, resolve_constraint, solve, solve_subtree, Context,
+        SolveOutput,
+    },
+    virtualize::Virtualization,
+};
+
+pub use crate::{
+    baseline::Baseline,
+    builder::SolverBuilder,
+    diff::StructuralChange,
+    embed::SolverRef,
+    extension::{ExtensionMap, ExtensionValue},
+    grid::Grid,
+    hooks::Phase,
+    layout::{CustomLayout, LayoutChild, LayoutId},
+    lines::LineInfo,
+    lint::LintDiagnostic,
+    memory::MemoryReport,
+    node_id::NodeId,
+    pipeline::PipelinedSolver,
+    repro::{ReproBundle, ReproConstraint, ReproNode},
+    sensitivity::Sensitivity,
+    snapshot::{FrameGradient, FrameSnapshot},
+    transform::Transform,
+};
 
 new_key_type! { pub struct ConstraintKey; }
 
@@ -18,6 +87,46 @@ pub struct Solver {
     frame_tree: Tree<FrameKey, Frame>,
     key_map: BTreeMap<ConstraintKey, FrameKey>,
     is_dirty: bool,
+    last_solve_lengths: Option<(f64, f64)>,
+    profile: Option<BTreeMap<ConstraintKey, Duration>>,
+    hooks: BTreeMap<(ConstraintKey, Phase), Hook>,
+    layouts: BTreeMap<ConstraintKey, Arc<dyn CustomLayout>>,
+    registry: BTreeMap<LayoutId, (String, Arc<dyn CustomLayout>)>,
+    scale_factor: Option<f64>,
+    rounding_policy: RoundingPolicy,
+    layout_direction: LayoutDirection,
+    baseline_grid: Option<f64>,
+    is_deterministic: bool,
+    physical_frames: BTreeMap<ConstraintKey, Frame>,
+    frames_i32: BTreeMap<ConstraintKey, FrameI32>,
+    transforms: BTreeMap<ConstraintKey, Transform>,
+    parent_keys: BTreeMap<ConstraintKey, ConstraintKey>,
+    islands: BTreeMap<ConstraintKey, Island>,
+    solve_outputs: BTreeMap<ConstraintKey, SolveOutput>,
+    staged: BTreeMap<ConstraintKey, Constraint>,
+    traversal_indices: Option<BTreeMap<ConstraintKey, usize>>,
+    sensitivities: BTreeMap<ConstraintKey, (Sensitivity, Sensitivity)>,
+    virtualizations: BTreeMap<ConstraintKey, Virtualization>,
+    splits: BTreeMap<ConstraintKey, Vec<f64>>,
+    grids: BTreeMap<ConstraintKey, Grid>,
+    grid_areas: BTreeMap<ConstraintKey, String>,
+    grid_spans: BTreeMap<ConstraintKey, (usize, usize)>,
+    on_layouts: BTreeMap<ConstraintKey, OnLayout>,
+    last_frames: BTreeMap<ConstraintKey, Frame>,
+    node_ids: BTreeMap<ConstraintKey, NodeId>,
+    constraint_keys_by_id: BTreeMap<NodeId, ConstraintKey>,
+    labels: BTreeMap<ConstraintKey, String>,
+    extensions: BTreeMap<ConstraintKey, ExtensionMap>,
+    constraint_extensions: BTreeMap<ConstraintKey, ExtensionMap>,
+    generations: BTreeMap<ConstraintKey, (u64, Frame)>,
+    dirty_keys: BTreeMap<ConstraintKey, Option<ConstraintKey>>,
+    embeddeds: BTreeMap<ConstraintKey, SolverRef>,
+    baseline_providers: BTreeMap<ConstraintKey, BaselineProvider>,
+    measures: BTreeMap<ConstraintKey, (TypeId, Measure)>,
+    stale_measures: BTreeMap<ConstraintKey, ConstraintKey>,
+    measure_context_type: Option<TypeId>,
+    scrolls: BTreeMap<ConstraintKey, Scroll>,
+    hit_clips: BTreeSet<ConstraintKey>,
 }
 
 impl Solver {
@@ -27,8 +136,7 @@ impl Solver {
         Self {
             constraint_tree: Tree::with_capacity(capacity),
             frame_tree: Tree::with_capacity(capacity),
-            key_map: BTreeMap::default(),
-            is_dirty: false,
+            ..Default::default()
         }
     }
 
@@ -40,12 +148,22 @@ impl Solver {
 
     pub fn is_dirty(&self) -> bool {
         self.is_dirty
+            || self.islands.values().any(|island| island.is_dirty)
+            || !self.stale_measures.is_empty()
+    }
+
+    pub fn dirty_keys(&self) -> impl Iterator<Item = ConstraintKey> + '_ {
+        self.dirty_keys.keys().copied()
     }
 
     pub fn contains(&self, constraint_key: ConstraintKey) -> bool {
         self.constraint_tree.contains(constraint_key)
     }
 
+    pub fn is_profiling(&self) -> bool {
+        self.profile.is_some()
+    }
+
     // Insertion/removal methods:
 
     pub fn insert_root(&mut self, constraint: Constraint) -> Option<ConstraintKey> {
@@ -57,16 +175,20 @@ impl Solver {
         constraint: Constraint,
         capacity: usize,
     ) -> Option<ConstraintKey> {
-        let both_fills_are_absolute_scales = matches! { constraint.fill, Fill::Absolute { x: FillType::Scale(..), y: FillType::Scale(..) }};
-        let both_fills_are_relative_scales = matches! { constraint.fill, Fill::Relative { main: FillType::Scale(..), cross: FillType::Scale(..) }};
-
-        let both_fills_are_scales = both_fills_are_absolute_scales | both_fills_are_relative_scales;
-
-        both_fills_are_scales.then(|| {
+        is_valid_root(constraint).then(|| {
             let root_key = self
                 .constraint_tree
                 .insert_root_with_capacity(constraint, capacity);
-            self.is_dirty = true;
+            self.parent_keys.clear();
+            self.node_ids.clear();
+            self.constraint_keys_by_id.clear();
+            self.labels.clear();
+            self.extensions.clear();
+            self.constraint_extensions.clear();
+            self.grid_areas.clear();
+            self.grid_spans.clear();
+            self.dirty_keys.clear();
+            self.mark_dirty(root_key, None);
             root_key
         })
     }
@@ -88,8 +210,9 @@ impl Solver {
         let root_key =
             self.constraint_tree
                 .insert_with_capacity(constraint, parent_constraint_key, capacity);
-        if root_key.is_some() {
-            self.is_dirty = true;
+        if let Some(root_key) = root_key {
+            self.parent_keys.insert(root_key, parent_constraint_key);
+            self.mark_dirty(root_key, self.region(parent_constraint_key));
         };
         root_key
     }
@@ -106,7 +229,7 @@ impl Solver {
             .constraint_tree
             .reorder_children(constraint_key, get_reordered_constraint_keys);
         if did_reorder {
-            self.is_dirty = true;
+            self.mark_dirty(constraint_key, self.region(constraint_key));
         };
         did_reorder
     }
@@ -116,9 +239,29 @@ impl Solver {
         constraint_key: ConstraintKey,
         size_hint: Option<usize>,
     ) -> Option<Constraint> {
+        let parent_constraint_key = self.parent_keys.get(&constraint_key).copied();
+        let region = parent_constraint_key
+            .and_then(|parent_constraint_key| self.region(parent_constraint_key));
+        let removed_constraint_keys = self.descendant_keys(constraint_key);
+
         let old_value = self.constraint_tree.remove(constraint_key, size_hint);
         if old_value.is_some() {
-            self.is_dirty = true;
+            self.remove_traversal_indices(&removed_constraint_keys);
+            for removed_constraint_key in removed_constraint_keys {
+                self.parent_keys.remove(&removed_constraint_key);
+                self.remove_node_id(removed_constraint_key);
+                self.labels.remove(&removed_constraint_key);
+                self.extensions.remove(&removed_constraint_key);
+                self.constraint_extensions.remove(&removed_constraint_key);
+                self.grid_areas.remove(&removed_constraint_key);
+                self.grid_spans.remove(&removed_constraint_key);
+                self.dirty_keys.remove(&removed_constraint_key);
+                self.stale_measures.remove(&removed_constraint_key);
+            }
+            match parent_constraint_key {
+                Some(parent_constraint_key) => self.mark_dirty(parent_constraint_key, region),
+                None => self.is_dirty = true,
+            };
         };
         old_value
     }
@@ -128,11 +271,20 @@ impl Solver {
         consraint_key: ConstraintKey,
         new_parent_consraint_key: ConstraintKey,
     ) -> bool {
+        let old_parent_constraint_key = self.parent_keys.get(&consraint_key).copied();
+        let old_region = old_parent_constraint_key
+            .and_then(|old_parent_constraint_key| self.region(old_parent_constraint_key));
+
         let did_rebase = self
             .constraint_tree
             .rebase(consraint_key, new_parent_consraint_key);
         if did_rebase {
-            self.is_dirty = true;
+            self.parent_keys
+                .insert(consraint_key, new_parent_consraint_key);
+            if let Some(old_parent_constraint_key) = old_parent_constraint_key {
+                self.mark_dirty(old_parent_constraint_key, old_region);
+            };
+            self.mark_dirty(consraint_key, self.region(new_parent_consraint_key));
         };
         did_rebase
     }
@@ -142,6 +294,43 @@ impl Solver {
         self.frame_tree.clear();
         self.key_map.clear();
         self.is_dirty = false;
+        self.last_solve_lengths = None;
+        self.hooks.clear();
+        self.on_layouts.clear();
+        self.last_frames.clear();
+        self.layouts.clear();
+        self.physical_frames.clear();
+        self.frames_i32.clear();
+        self.transforms.clear();
+        self.parent_keys.clear();
+        self.node_ids.clear();
+        self.constraint_keys_by_id.clear();
+        self.labels.clear();
+        self.extensions.clear();
+        self.constraint_extensions.clear();
+        self.islands.clear();
+        self.solve_outputs.clear();
+        self.staged.clear();
+        self.virtualizations.clear();
+        self.splits.clear();
+        self.grids.clear();
+        self.grid_areas.clear();
+        self.grid_spans.clear();
+        if let Some(traversal_indices) = &mut self.traversal_indices {
+            traversal_indices.clear();
+        };
+        self.sensitivities.clear();
+        self.generations.clear();
+        self.dirty_keys.clear();
+        self.embeddeds.clear();
+        self.baseline_providers.clear();
+        self.measures.clear();
+        self.stale_measures.clear();
+        self.scrolls.clear();
+        self.hit_clips.clear();
+        if let Some(profile) = &mut self.profile {
+            profile.clear();
+        };
     }
 
     // Getter/setter methods:
@@ -163,9 +352,13 @@ impl Solver {
         self.constraint_tree.get(constraint_key)
     }
 
+    pub fn parent_constraint_key(&self, constraint_key: ConstraintKey) -> Option<ConstraintKey> {
+        self.parent_keys.get(&constraint_key).copied()
+    }
+
     pub fn get_frame(&self, constraint_key: ConstraintKey) -> Option<Frame> {
         let contains_constraint_key = self.constraint_tree.contains(constraint_key);
-        let is_dirty = self.is_dirty;
+        let is_dirty = self.is_frame_dirty(constraint_key);
 
         match (contains_constraint_key, is_dirty) {
             (false, _) => None,
@@ -173,67 +366,590 @@ impl Solver {
             (true, true) => None,
 
             (true, false) => {
-                let frame_key = *self.key_map.get(&constraint_key).unwrap();
+                let frame_key = *self.key_map.get(&constraint_key)?;
                 let frame = *self.frame_tree.get(frame_key).unwrap().value;
                 Some(frame)
             }
         }
     }
 
+    pub fn get_last_frame(&self, constraint_key: ConstraintKey) -> Option<Frame> {
+        if !self.constraint_tree.contains(constraint_key) {
+            return None;
+        };
+
+        let frame_key = *self.key_map.get(&constraint_key)?;
+        let frame = *self.frame_tree.get(frame_key)?.value;
+        Some(frame)
+    }
+
+    pub fn get_physical_frame(&self, constraint_key: ConstraintKey) -> Option<Frame> {
+        match self.is_dirty || self.is_frame_dirty(constraint_key) {
+            true => None,
+            false => self.physical_frames.get(&constraint_key).copied(),
+        }
+    }
+
+    pub fn get_frame_i32(&self, constraint_key: ConstraintKey) -> Option<FrameI32> {
+        match self.is_dirty || self.is_frame_dirty(constraint_key) {
+            true => None,
+            false => self.frames_i32.get(&constraint_key).copied(),
+        }
+    }
+
+    pub fn get_corner_radius(&self, constraint_key: ConstraintKey) -> Option<f64> {
+        let frame = self.get_frame(constraint_key)?;
+        let corner_radius = self
+            .constraint_tree
+            .get(constraint_key)?
+            .value
+            .corner_radius;
+        let max_corner_radius = frame.length_x.min(frame.length_y) / 2.;
+        Some(corner_radius.clamp(0., max_corner_radius))
+    }
+
+    pub fn get_visibility(&self, constraint_key: ConstraintKey) -> Option<Visibility> {
+        self.get_frame(constraint_key)?;
+        let mut current_constraint_key = Some(constraint_key);
+        while let Some(constraint_key) = current_constraint_key {
+            let visibility = self.constraint_tree.get(constraint_key)?.value.visibility;
+            if visibility == Visibility::Hidden {
+                return Some(Visibility::Hidden);
+            };
+            current_constraint_key = self.parent_keys.get(&constraint_key).copied();
+        }
+        Some(Visibility::Visible)
+    }
+
+    pub fn frame_generation(&self, constraint_key: ConstraintKey) -> Option<u64> {
+        self.generations
+            .get(&constraint_key)
+            .map(|&(generation, _)| generation)
+    }
+
+    pub fn get_overflow(&self, constraint_key: ConstraintKey) -> Option<(f64, f64)> {
+        let is_solved = self.key_map.contains_key(&constraint_key)
+            && !self.is_dirty
+            && !self.is_frame_dirty(constraint_key);
+
+        is_solved.then(|| {
+            self.solve_outputs
+                .get(&constraint_key)
+                .and_then(|solve_output| solve_output.overflow)
+                .unwrap_or_default()
+        })
+    }
+
+    pub fn free_space(&self, constraint_key: ConstraintKey) -> Option<f64> {
+        let is_solved = !self.is_dirty && !self.is_frame_dirty(constraint_key);
+
+        is_solved
+            .then(|| self.solve_outputs.get(&constraint_key)?.free_space)
+            .flatten()
+    }
+
+    pub fn collapsed_children(&self, constraint_key: ConstraintKey) -> Option<&[ConstraintKey]> {
+        let is_solved = !self.is_dirty && !self.is_frame_dirty(constraint_key);
+
+        is_solved.then(|| {
+            self.solve_outputs
+                .get(&constraint_key)
+                .map_or(&[][..], |solve_output| solve_output.collapsed.as_slice())
+        })
+    }
+
     pub fn set(
         &mut self,
         constraint_key: ConstraintKey,
         new_constraint: Constraint,
     ) -> Option<Constraint> {
-        let old_constraint = self.constraint_tree.set(constraint_key, new_constraint);
-        if old_constraint.is_some() {
-            self.is_dirty = true;
+        let old_constraint = self
+            .constraint_tree
+            .get(constraint_key)
+            .map(|constraint_node| *constraint_node.value);
+
+        if let Some(old_constraint) =
+            old_constraint.filter(|&old_constraint| old_constraint != new_constraint)
+        {
+            self.constraint_tree.set(constraint_key, new_constraint);
+
+            // Visibility and layers only affect painting and hit testing, never geometry.
+            let is_geometry_unchanged = Constraint {
+                visibility: new_constraint.visibility,
+                layer: new_constraint.layer,
+                ..old_constraint
+            } == new_constraint;
+            if !is_geometry_unchanged {
+                self.mark_dirty(constraint_key, self.region(constraint_key));
+            };
         };
+
         old_constraint
     }
 
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor.unwrap_or(1.)
+    }
+
+    pub fn set_scale_factor(&mut self, scale_factor: f64) -> bool {
+        let is_valid = scale_factor.is_finite() && scale_factor > 0.;
+        if is_valid && scale_factor != self.scale_factor() {
+            self.scale_factor = Some(scale_factor);
+            self.is_dirty = true;
+        };
+        is_valid
+    }
+
+    pub fn rounding_policy(&self) -> RoundingPolicy {
+        self.rounding_policy
+    }
+
+    pub fn set_rounding_policy(&mut self, rounding_policy: RoundingPolicy) {
+        if rounding_policy != self.rounding_policy {
+            self.rounding_policy = rounding_policy;
+            self.is_dirty = true;
+        };
+    }
+
+    pub fn layout_direction(&self) -> LayoutDirection {
+        self.layout_direction
+    }
+
+    pub fn set_layout_direction(&mut self, layout_direction: LayoutDirection) {
+        if layout_direction != self.layout_direction {
+            self.layout_direction = layout_direction;
+            self.is_dirty = true;
+        };
+    }
+
+    pub fn baseline_grid(&self) -> Option<f64> {
+        self.baseline_grid
+    }
+
+    pub fn set_baseline_grid(&mut self, baseline_grid: Option<f64>) -> bool {
+        let is_valid = match baseline_grid {
+            Some(baseline_grid) => baseline_grid.is_finite() && baseline_grid > 0.,
+            None => true,
+        };
+        if is_valid && baseline_grid != self.baseline_grid {
+            self.baseline_grid = baseline_grid;
+            self.is_dirty = true;
+        };
+        is_valid
+    }
+
+    pub fn is_deterministic(&self) -> bool {
+        self.is_deterministic
+    }
+
+    pub fn set_deterministic(&mut self, is_deterministic: bool) {
+        if is_deterministic != self.is_deterministic {
+            self.is_deterministic = is_deterministic;
+            self.is_dirty = true;
+        };
+    }
+
+    pub fn set_profiling(&mut self, profiling: bool) {
+        match (profiling, self.profile.is_some()) {
+            (true, false) => self.profile = Some(BTreeMap::default()),
+            (false, true) => self.profile = None,
+            _ => (),
+        }
+    }
+
+    pub fn profile(&self) -> Vec<(ConstraintKey, Duration)> {
+        let mut profile = self
+            .profile
+            .iter()
+            .flatten()
+            .map(|(&constraint_key, &duration)| (constraint_key, duration))
+            .collect::<Vec<_>>();
+        profile.sort_by(|(_, a), (_, b)| b.cmp(a));
+        profile
+    }
+
     // Solve method:
 
+    pub fn solve_auto(&mut self, max_x: Option<f64>, max_y: Option<f64>) -> (f64, f64) {
+        let lengths = match self.constraint_tree.is_empty() {
+            true => (0., 0.),
+            false => {
+                let max_length_x = max_x.unwrap_or(f64::INFINITY).max(0.);
+                let max_length_y = max_y.unwrap_or(f64::INFINITY).max(0.);
+                let mut context = self.context();
+                minimize_root(&mut context, max_length_x, max_length_y)
+            }
+        };
+
+        if self.last_solve_lengths != Some(lengths) {
+            self.is_dirty = true;
+        };
+
+        let (length_x, length_y) = lengths;
+        self.solve(length_x, length_y);
+
+        lengths
+    }
+
+    pub fn solve_subtree(&mut self, constraint_key: ConstraintKey, frame: Frame) -> bool {
+        let is_solvable = !self.is_dirty
+            && self.constraint_tree.contains(constraint_key)
+            && self.key_map.contains_key(&constraint_key);
+
+        if is_solvable {
+            let mut context = self.context();
+            solve_subtree(&mut context, constraint_key, frame);
+            self.solve_embeddeds();
+            let refreshed_constraint_key = self
+                .parent_keys
+                .get(&constraint_key)
+                .copied()
+                .unwrap_or(constraint_key);
+            self.refresh_traversal_subtree(refreshed_constraint_key);
+        };
+
+        is_solvable
+    }
+
     pub fn solve(&mut self, length_x: f64, length_y: f64) {
+        self.solve_measured(None, length_x, length_y);
+    }
+
+    pub fn solve_with_context<C>(&mut self, measure_context: &mut C, length_x: f64, length_y: f64)
+    where
+        C: 'static,
+    {
+        self.solve_measured(Some(measure_context), length_x, length_y);
+    }
+
+    fn solve_measured(
+        &mut self,
+        mut measure_context: Option<&mut dyn Any>,
+        length_x: f64,
+        length_y: f64,
+    ) {
+        let measure_context_type = measure_context.as_deref().map(<dyn Any>::type_id);
+        if measure_context_type != self.measure_context_type {
+            self.measure_context_type = measure_context_type;
+            let measured_constraint_keys = self.measures.keys().copied().collect::<Vec<_>>();
+            for constraint_key in measured_constraint_keys {
+                self.mark_dirty(constraint_key, self.region(constraint_key));
+            }
+        };
+
         let is_dirty = self.is_dirty;
         let is_empty = self.constraint_tree.is_empty();
 
         match (is_dirty, is_empty) {
-            (true, true) => self.is_dirty = false,
+            (true, true) => {
+                self.last_solve_lengths = Some((length_x, length_y));
+                self.is_dirty = false;
+                self.islands.clear();
+                self.sensitivities.clear();
+                self.dirty_keys.clear();
+                self.stale_measures.clear();
+                self.refresh_traversal_indices();
+            }
 
             (true, false) => {
+                self.last_solve_lengths = Some((length_x, length_y));
                 let length_x = length_x.max(0.);
                 let length_y = length_y.max(0.);
 
-                solve(
-                    &self.constraint_tree,
-                    &mut self.frame_tree,
-                    &mut self.key_map,
-                    length_x,
-                    length_y,
-                );
+                if let Some(profile) = &mut self.profile {
+                    profile.clear();
+                };
+
+                let mut context = self.context();
+                context.measure_context = measure_context;
+                solve(&mut context, length_x, length_y);
+
+                self.prune();
+                self.refresh_sensitivities();
 
                 self.is_dirty = false;
+                self.dirty_keys.clear();
+                self.stale_measures.clear();
+                for island in self.islands.values_mut() {
+                    island.is_dirty = false;
+                }
+                self.refresh_traversal_indices();
+            }
+
+            (false, _) => {
+                let dirty_island_constraint_keys = self
+                    .islands
+                    .iter()
+                    .filter(|(_, island)| island.is_dirty)
+                    .map(|(&constraint_key, _)| constraint_key)
+                    .collect::<Vec<_>>();
+
+                let stale_measures = std::mem::take(&mut self.stale_measures);
+                for constraint_key in dirty_island_constraint_keys {
+                    self.solve_island_measured(constraint_key, measure_context.as_deref_mut());
+                }
+                self.solve_stale_measures(stale_measures, measure_context);
             }
+        }
+
+        self.solve_embeddeds();
+    }
+
+    fn context(&mut self) -> Context<'_> {
+        let scale_factor = self.scale_factor();
+
+        Context {
+            constraint_tree: &self.constraint_tree,
+            frame_tree: &mut self.frame_tree,
+            key_map: &mut self.key_map,
+            profile: self.profile.as_mut(),
+            hooks: &self.hooks,
+            on_layouts: &self.on_layouts,
+            last_frames: &mut self.last_frames,
+            layouts: &self.layouts,
+            registry: &self.registry,
+            scale_factor,
+            rounding_policy: self.rounding_policy,
+            layout_direction: self.layout_direction,
+            baseline_grid: self.baseline_grid,
+            is_deterministic: self.is_deterministic,
+            physical_frames: &mut self.physical_frames,
+            frames_i32: &mut self.frames_i32,
+            transforms: &mut self.transforms,
+            islands: &self.islands,
+            parent_keys: &self.parent_keys,
+            labels: &self.labels,
+            solve_outputs: &mut self.solve_outputs,
+            splits: &self.splits,
+            grids: &self.grids,
+            grid_areas: &self.grid_areas,
+            grid_spans: &self.grid_spans,
+            generations: &mut self.generations,
+            baseline_providers: &self.baseline_providers,
+            measures: &self.measures,
+            measure_context: None,
+            scrolls: &mut self.scrolls,
+            content_frame: Frame::default(),
+        }
+    }
+
+    fn prune(&mut self) {
+        self.key_map
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.hooks
+            .retain(|&(constraint_key, _), _| self.constraint_tree.contains(constraint_key));
+        self.layouts
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.islands
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.solve_outputs
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.virtualizations
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.splits
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.grids
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.on_layouts
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.last_frames
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.generations
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.embeddeds
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.baseline_providers
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.measures
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.scrolls
+            .retain(|&constraint_key, _| self.constraint_tree.contains(constraint_key));
+        self.hit_clips
+            .retain(|&constraint_key| self.constraint_tree.contains(constraint_key));
+    }
+
+    fn resolved_constraint(&self, constraint_key: ConstraintKey) -> Constraint {
+        resolve_constraint(
+            &self.constraint_tree,
+            &self.parent_keys,
+            &self.islands,
+            constraint_key,
+        )
+        .resolve_logical(self.layout_direction)
+    }
+
+    fn laid_out_child_keys(
+        &self,
+        constraint_key: ConstraintKey,
+    ) -> Option<IndexSet<ConstraintKey>> {
+        let constraint_node = self.constraint_tree.get(constraint_key)?;
+        let (constraint_keys, _) =
+            partition_layout_ignored(&self.constraint_tree, constraint_node.child_keys);
+        Some(constraint_keys)
+    }
+
+    fn resolved_insets(&self, constraint_key: ConstraintKey) -> Padding {
+        let containing_frame = self
+            .parent_keys
+            .get(&constraint_key)
+            .and_then(|&parent_constraint_key| self.get_content_box(parent_constraint_key))
+            .or_else(|| self.get_frame(constraint_key))
+            .unwrap_or_default();
+
+        self.resolved_constraint(constraint_key)
+            .content
+            .resolve_insets(containing_frame.length_x, containing_frame.length_y)
+    }
+
+    fn descendant_keys(&self, constraint_key: ConstraintKey) -> Vec<ConstraintKey> {
+        let mut descendant_keys = vec![];
+        let mut to_visit_constraint_keys = vec![constraint_key];
 
-            (false, _) => (),
+        while let Some(constraint_key) = to_visit_constraint_keys.pop() {
+            if let Some(constraint_node) = self.constraint_tree.get(constraint_key) {
+                descendant_keys.push(constraint_key);
+                to_visit_constraint_keys.extend(constraint_node.child_keys.iter().copied());
+            };
         }
+
+        descendant_keys
+    }
+}
+
+fn is_valid_root(constraint: Constraint) -> bool {
+    let both_fills_are_absolute_scales = matches! { constraint.fill, Fill::Absolute { x: FillType::Scale(..), y: FillType::Scale(..) }};
+    let both_fills_are_relative_scales = matches! { constraint.fill, Fill::Relative { main: FillType::Scale(..), cross: FillType::Scale(..) }};
+
+    both_fills_are_absolute_scales | both_fills_are_relative_scales
+}
+
+// Fractional gaps have nothing to resolve against while measuring unbounded content.
+fn exact_gap(gap: Option<FillType>, length: f64) -> f64 {
+    match gap {
+        Some(FillType::Fraction(..)) if !length.is_finite() => 0.,
+        Some(gap) => match gap.resolve(length) {
+            ResolvedFillType::Exact(exact_gap) => exact_gap,
+            ResolvedFillType::Scale(..) | ResolvedFillType::Minimize => 0.,
+        },
+        None => 0.,
     }
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Constraint {
     pub fill: Fill,
+    /// Main-axis length a `Scale` fill starts from before free space is shared out by weight.
+    pub basis: Option<f64>,
+    pub shrink: Option<f64>,
     pub content: Content,
+    pub bounds: Bounds,
+    /// Only honored for in-flow children of relative-mode containers without a custom layout;
+    /// `lint` reports margins set anywhere else.
+    pub margin: Padding,
+    pub group: Option<u32>,
+    pub layout_ignored: bool,
+    pub display: Display,
+    pub visibility: Visibility,
+    pub position: Position,
+    pub nudge_x: f64,
+    pub nudge_y: f64,
+    pub hit_slop: Padding,
+    pub corner_radius: f64,
+    pub intrinsic_size: Option<(f64, f64)>,
+    pub priority: Option<u32>,
+    pub order: i32,
+    pub layer: Option<i32>,
+    /// Overrides the parent's `align_cross` for this child. Any value other than `Stretch` also
+    /// sizes a scale cross fill to its content instead of stretching it.
+    pub align_self: Option<Align>,
+    /// Overrides the parent's `align_main` for this child in stack and grid modes.
+    pub justify_self: Option<Align>,
+}
+
+impl Constraint {
+    pub fn image(width: f64, height: f64) -> Self {
+        Self {
+            fill: Fill::Absolute {
+                x: FillType::Minimize,
+                y: FillType::Minimize,
+            },
+            intrinsic_size: Some((width.max(0.), height.max(0.))),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_out_of_flow(&self) -> bool {
+        self.layout_ignored || self.position != Position::Flow
+    }
+
+    fn resolve_logical(mut self, layout_direction: LayoutDirection) -> Self {
+        self.content.padding = self.content.padding.resolve_logical(layout_direction);
+        self.content.padding_fraction = self
+            .content
+            .padding_fraction
+            .resolve_logical(layout_direction);
+        self.content.border = self.content.border.resolve_logical(layout_direction);
+        self.margin = self.margin.resolve_logical(layout_direction);
+        self
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Position {
+    #[default]
+    Flow,
+    Absolute {
+        left: Option<f64>,
+        right: Option<f64>,
+        top: Option<f64>,
+        bottom: Option<f64>,
+    },
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Display {
+    #[default]
+    Flow,
+    None,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Visibility {
+    #[default]
+    Visible,
+    Hidden,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Fill {
     Absolute { x: FillType, y: FillType },
     Relative { main: FillType, cross: FillType },
 }
 
 impl Fill {
+    fn unstretch_cross(self, direction: Direction) -> Self {
+        let unstretch = |fill_type| match fill_type {
+            FillType::Scale(1..) => FillType::Minimize,
+            fill_type => fill_type,
+        };
+        match (self, direction) {
+            (Self::Absolute { x, y }, Direction::Horizontal) => {
+                Self::Absolute { x, y: unstretch(y) }
+            }
+            (Self::Absolute { x, y }, Direction::Vertical) => Self::Absolute { x: unstretch(x), y },
+            (Self::Relative { main, cross }, _) => Self::Relative {
+                main,
+                cross: unstretch(cross),
+            },
+        }
+    }
+
     fn to_relative_fill(self, direction: Direction) -> RelativeFill {
         match self {
             Self::Absolute { x, y } => match direction {
@@ -260,11 +976,45 @@ struct RelativeFill {
     cross: FillType,
 }
 
+impl RelativeFill {
+    fn resolve(self, length_main: f64, length_cross: f64) -> ResolvedRelativeFill {
+        ResolvedRelativeFill {
+            main: self.main.resolve(length_main),
+            cross: self.cross.resolve(length_cross),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
+struct ResolvedRelativeFill {
+    main: ResolvedFillType,
+    cross: ResolvedFillType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FillType {
     Exact(f64),
     Scale(usize),
     Minimize,
+    Fraction(f64),
+    Bounded { min: f64, preferred: f64, max: f64 },
+}
+
+impl FillType {
+    fn resolve(self, length: f64) -> ResolvedFillType {
+        match self {
+            Self::Exact(exact) => ResolvedFillType::Exact(exact),
+            Self::Scale(scale) => ResolvedFillType::Scale(scale),
+            Self::Minimize => ResolvedFillType::Minimize,
+            Self::Fraction(fraction) => ResolvedFillType::Exact(fraction * length),
+            Self::Bounded {
+                min,
+                preferred,
+                max,
+            } => ResolvedFillType::Exact(preferred.min(max).max(min)),
+        }
+    }
 }
 
 impl Default for FillType {
@@ -273,30 +1023,208 @@ impl Default for FillType {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ResolvedFillType {
+    Exact(f64),
+    Scale(usize),
+    Minimize,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Bounds {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: Option<f64>,
+    pub max_y: Option<f64>,
+}
+
+impl Bounds {
+    fn to_relative_bounds(self, direction: Direction) -> RelativeBounds {
+        let Self {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        } = self;
+
+        match direction {
+            Direction::Horizontal => RelativeBounds {
+                min_main: min_x,
+                min_cross: min_y,
+                max_main: max_x,
+                max_cross: max_y,
+            },
+            Direction::Vertical => RelativeBounds {
+                min_main: min_y,
+                min_cross: min_x,
+                max_main: max_y,
+                max_cross: max_x,
+            },
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+struct RelativeBounds {
+    min_main: f64,
+    min_cross: f64,
+    max_main: Option<f64>,
+    max_cross: Option<f64>,
+}
+
+impl RelativeBounds {
+    fn clamp_main(self, length_main: f64) -> f64 {
+        self.max_main
+            .map_or(length_main, |max_main| length_main.min(max_main))
+            .max(self.min_main)
+    }
+
+    fn clamp_cross(self, length_cross: f64) -> f64 {
+        self.max_cross
+            .map_or(length_cross, |max_cross| length_cross.min(max_cross))
+            .max(self.min_cross)
+    }
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Content {
     pub direction: Direction,
     pub padding: Padding,
+    pub padding_fraction: Padding,
+    pub border: Padding,
     pub align_main: Align,
     pub align_cross: Align,
+    /// Overrides `align_main` for the last line of a flow or wrap container with several lines.
+    pub align_last_line: Option<Align>,
+    pub layout: Option<LayoutId>,
+    pub default_child: Option<ChildDefaults>,
+    pub stretch_to_tallest: bool,
+    pub mode: LayoutMode,
+    pub reverse: bool,
+    /// Combines the largest positive and the most negative of two adjacent margins, like CSS.
+    /// Margins are only honored in relative mode, so `lint` reports this set anywhere else.
+    pub collapse_margins: bool,
+    pub overflow: Overflow,
+    pub overflow_anchor: Option<Align>,
+    pub precedence: Precedence,
+    pub gap_main: Option<FillType>,
+    /// Cross-axis spacing: between lines in flow and wrap modes, and between cross-axis tracks
+    /// in columns, grid and table modes.
+    pub gap_cross: Option<FillType>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayoutMode {
+    #[default]
+    Relative,
+    Flow,
+    Wrap,
+    Columns(usize),
+    Stack,
+    Table,
+    Grid,
+    Split,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ChildDefaults {
+    pub fill: Fill,
+    pub padding: Padding,
+    pub bounds: Bounds,
+}
+
+impl ChildDefaults {
+    fn apply(self, mut constraint: Constraint) -> Constraint {
+        if constraint.fill == Fill::default() {
+            constraint.fill = self.fill;
+        };
+        if constraint.content.padding == Padding::default() {
+            constraint.content.padding = self.padding;
+        };
+        if constraint.bounds == Bounds::default() {
+            constraint.bounds = self.bounds;
+        };
+        constraint
+    }
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Padding {
     pub left: f64,
     pub right: f64,
 
     pub top: f64,
     pub bottom: f64,
+
+    pub start: f64,
+    pub end: f64,
+}
+
+impl Content {
+    fn resolve_padding(self, length_x: f64, length_y: f64) -> Padding {
+        let Self {
+            padding,
+            padding_fraction,
+            ..
+        } = self;
+
+        Padding {
+            left: padding.left + padding_fraction.left * length_x,
+            right: padding.right + padding_fraction.right * length_x,
+            top: padding.top + padding_fraction.top * length_y,
+            bottom: padding.bottom + padding_fraction.bottom * length_y,
+            start: padding.start + padding_fraction.start * length_x,
+            end: padding.end + padding_fraction.end * length_x,
+        }
+    }
+
+    fn resolve_insets(self, length_x: f64, length_y: f64) -> Padding {
+        let Self { border, .. } = self;
+        let padding = self.resolve_padding(length_x, length_y);
+
+        Padding {
+            left: border.left + padding.left,
+            right: border.right + padding.right,
+            top: border.top + padding.top,
+            bottom: border.bottom + padding.bottom,
+            start: border.start + padding.start,
+            end: border.end + padding.end,
+        }
+    }
 }
 
 impl Padding {
+    fn resolve_logical(self, layout_direction: LayoutDirection) -> Self {
+        let (start, end) = match layout_direction {
+            LayoutDirection::LeftToRight => (self.start, self.end),
+            LayoutDirection::RightToLeft => (self.end, self.start),
+        };
+
+        Self {
+            left: self.left + start,
+            right: self.right + end,
+            start: 0.,
+            end: 0.,
+            ..self
+        }
+    }
+
     fn to_relative_padding(self, direction: Direction) -> RelativePadding {
         let Self {
             left,
             right,
             top,
             bottom,
+            ..
         } = self;
 
         match direction {
@@ -325,7 +1253,18 @@ struct RelativePadding {
     pub cross_end: f64,
 }
 
+impl RelativePadding {
+    fn length_main(self) -> f64 {
+        self.main_start + self.main_end
+    }
+
+    fn length_cross(self) -> f64 {
+        self.cross_start + self.cross_end
+    }
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Horizontal,
 
@@ -334,14 +1273,109 @@ pub enum Direction {
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Align {
     #[default]
     Start,
     Middle,
     End,
+    Fraction(AlignFraction),
+    FirstBaseline,
+    LastBaseline,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+    Stretch,
+}
+
+impl Align {
+    fn offset(self, remaining_length: f64) -> f64 {
+        match self {
+            Self::Start | Self::SpaceBetween | Self::Stretch => 0.,
+            Self::Middle | Self::SpaceAround | Self::SpaceEvenly => remaining_length / 2.,
+            Self::End => remaining_length,
+            Self::Fraction(AlignFraction(fraction)) => remaining_length * fraction,
+            Self::FirstBaseline | Self::LastBaseline => 0.,
+        }
+    }
+
+    fn distribute(self, remaining_length: f64, number_of_children: usize) -> (f64, f64) {
+        if remaining_length <= 0. || number_of_children == 0 {
+            return (self.offset(remaining_length), 0.);
+        };
+
+        let number_of_children = number_of_children as f64;
+        match self {
+            Self::SpaceBetween if number_of_children > 1. => {
+                (0., remaining_length / (number_of_children - 1.))
+            }
+            Self::SpaceAround => {
+                let spacing = remaining_length / number_of_children;
+                (spacing / 2., spacing)
+            }
+            Self::SpaceEvenly => {
+                let spacing = remaining_length / (number_of_children + 1.);
+                (spacing, spacing)
+            }
+            _ => (self.offset(remaining_length), 0.),
+        }
+    }
+}
+
+/// Compares by bit pattern, so that `Align` can stay `Eq`.
+#[derive(Default, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct AlignFraction(pub f64);
+
+impl PartialEq for AlignFraction {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.total_cmp(&other.0).is_eq()
+    }
+}
+
+impl Eq for AlignFraction {}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Overflow {
+    #[default]
+    Clip,
+    ShrinkProportional,
+    ShrinkWeighted,
+    Relax,
+    Collapse,
+    Overflow,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayoutDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RoundingPolicy {
+    #[default]
+    Round,
+    RoundHalfEven,
+    FloorOffsetsCeilExtents,
+    Truncate,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Precedence {
+    #[default]
+    ExactFirst,
+    Proportional,
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Frame {
     pub offset_x: f64,
     pub length_x: f64,
@@ -350,6 +1384,55 @@ pub struct Frame {
     pub length_y: f64,
 }
 
+impl Frame {
+    fn to_relative_frame(self, direction: Direction) -> RelativeFrame {
+        let Self {
+            offset_x,
+            length_x,
+            offset_y,
+            length_y,
+        } = self;
+
+        match direction {
+            Direction::Horizontal => RelativeFrame {
+                offset_main: offset_x,
+                length_main: length_x,
+                offset_cross: offset_y,
+                length_cross: length_y,
+            },
+            Direction::Vertical => RelativeFrame {
+                offset_main: offset_y,
+                length_main: length_y,
+                offset_cross: offset_x,
+                length_cross: length_x,
+            },
+        }
+    }
+
+    fn union(self, other: Self) -> Self {
+        let offset_x = self.offset_x.min(other.offset_x);
+        let offset_y = self.offset_y.min(other.offset_y);
+        let end_x = (self.offset_x + self.length_x).max(other.offset_x + other.length_x);
+        let end_y = (self.offset_y + self.length_y).max(other.offset_y + other.length_y);
+
+        Self {
+            offset_x,
+            length_x: end_x - offset_x,
+            offset_y,
+            length_y: end_y - offset_y,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameI32 {
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 struct RelativeFrame {
     pub offset_main: f64,
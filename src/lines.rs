@@ -0,0 +1,47 @@
+use std::ops::Range;
+
+use crate::{ConstraintKey, Solver};
+
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineInfo {
+    pub child_range: Range<usize>,
+    pub offset_cross: f64,
+    pub length_cross: f64,
+}
+
+impl Solver {
+    // Line methods:
+
+    pub fn lines(&self, constraint_key: ConstraintKey) -> Vec<LineInfo> {
+        match self.is_dirty || self.is_frame_dirty(constraint_key) {
+            true => vec![],
+            false => self
+                .solve_outputs
+                .get(&constraint_key)
+                .map(|solve_output| solve_output.lines.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn line_position(&self, constraint_key: ConstraintKey) -> Option<(usize, usize)> {
+        let parent_constraint_key = *self.parent_keys.get(&constraint_key)?;
+        let collapsed_constraint_keys = self
+            .solve_outputs
+            .get(&parent_constraint_key)
+            .map_or(&[][..], |solve_output| solve_output.collapsed.as_slice());
+        let index = self
+            .laid_out_child_keys(parent_constraint_key)?
+            .iter()
+            .filter(|child_constraint_key| {
+                !collapsed_constraint_keys.contains(child_constraint_key)
+            })
+            .position(|&child_constraint_key| child_constraint_key == constraint_key)?;
+
+        self.lines(parent_constraint_key)
+            .iter()
+            .enumerate()
+            .find(|(_, line)| line.child_range.contains(&index))
+            .map(|(line_index, line)| (line_index, index - line.child_range.start))
+    }
+}
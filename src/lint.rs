@@ -0,0 +1,191 @@
+use crate::{ConstraintKey, Direction, FillType, Frame, LayoutMode, Padding, Phase, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintDiagnostic {
+    ZeroScaleWeight { constraint_key: ConstraintKey },
+    UnmeasuredMinimize { constraint_key: ConstraintKey },
+    PaddingExceedsLength { constraint_key: ConstraintKey },
+    UnreachableLayoutIgnored { constraint_key: ConstraintKey },
+    MismatchedMeasureContext { constraint_key: ConstraintKey },
+    IgnoredMargin { constraint_key: ConstraintKey },
+    IgnoredMarginCollapse { constraint_key: ConstraintKey },
+    IgnoredGap { constraint_key: ConstraintKey },
+    IgnoredGridPlacement { constraint_key: ConstraintKey },
+    MalformedGridArea { constraint_key: ConstraintKey },
+}
+
+impl Solver {
+    // Lint methods:
+
+    pub fn lint(&self) -> Vec<LintDiagnostic> {
+        let mut diagnostics = vec![];
+        let mut to_visit = self
+            .constraint_tree
+            .root_key()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        while let Some(constraint_key) = to_visit.pop() {
+            let constraint_node = self.constraint_tree.get(constraint_key).unwrap();
+            let constraint = self.resolved_constraint(constraint_key);
+            let frame = self.get_frame(constraint_key);
+
+            let mut total_scale = None;
+            for &child_constraint_key in constraint_node.child_keys {
+                let child_constraint = self.resolved_constraint(child_constraint_key);
+                if child_constraint.is_out_of_flow() {
+                    if let (Some(frame), Some(child_frame)) =
+                        (frame, self.get_frame(child_constraint_key))
+                    {
+                        if !intersects(frame, child_frame) {
+                            diagnostics.push(LintDiagnostic::UnreachableLayoutIgnored {
+                                constraint_key: child_constraint_key,
+                            });
+                        };
+                    };
+                    continue;
+                };
+
+                let relative_fill = child_constraint
+                    .fill
+                    .to_relative_fill(constraint.content.direction);
+                if let FillType::Scale(scale) = relative_fill.main {
+                    *total_scale.get_or_insert(0) += scale;
+                };
+            }
+            if total_scale == Some(0) {
+                diagnostics.push(LintDiagnostic::ZeroScaleWeight { constraint_key });
+            };
+
+            let parent_direction = self
+                .parent_keys
+                .get(&constraint_key)
+                .map(|&parent_constraint_key| {
+                    self.resolved_constraint(parent_constraint_key)
+                        .content
+                        .direction
+                })
+                .unwrap_or_default();
+            let relative_fill = constraint.fill.to_relative_fill(parent_direction);
+            let is_minimize = relative_fill.main == FillType::Minimize
+                || relative_fill.cross == FillType::Minimize;
+            let is_measured = self.hooks.contains_key(&(constraint_key, Phase::Measure))
+                || self.measures.contains_key(&constraint_key)
+                || constraint.intrinsic_size.is_some();
+            if constraint_node.child_keys.is_empty() && is_minimize && !is_measured {
+                diagnostics.push(LintDiagnostic::UnmeasuredMinimize { constraint_key });
+            };
+            if let (Some((measure_type, _)), Some(..)) = (self.measures.get(&constraint_key), frame)
+            {
+                if Some(*measure_type) != self.measure_context_type {
+                    diagnostics.push(LintDiagnostic::MismatchedMeasureContext { constraint_key });
+                };
+            };
+
+            let insets = match frame {
+                Some(..) => self.resolved_insets(constraint_key),
+                None => constraint.content.resolve_insets(0., 0.),
+            };
+            let lengths = match frame {
+                Some(frame) => (Some(frame.length_x), Some(frame.length_y)),
+                None => match parent_direction {
+                    Direction::Horizontal => (
+                        exact_length(relative_fill.main),
+                        exact_length(relative_fill.cross),
+                    ),
+                    Direction::Vertical => (
+                        exact_length(relative_fill.cross),
+                        exact_length(relative_fill.main),
+                    ),
+                },
+            };
+            if padding_exceeds(insets, lengths) {
+                diagnostics.push(LintDiagnostic::PaddingExceedsLength { constraint_key });
+            };
+
+            let is_margin_honored =
+                self.parent_keys
+                    .get(&constraint_key)
+                    .is_some_and(|&parent_constraint_key| {
+                        let parent_content =
+                            self.resolved_constraint(parent_constraint_key).content;
+                        parent_content.mode == LayoutMode::Relative
+                            && parent_content.layout.is_none()
+                            && !self.layouts.contains_key(&parent_constraint_key)
+                    });
+            if constraint.margin != Padding::default()
+                && (constraint.is_out_of_flow() || !is_margin_honored)
+            {
+                diagnostics.push(LintDiagnostic::IgnoredMargin { constraint_key });
+            };
+
+            let is_relative_layout = constraint.content.mode == LayoutMode::Relative
+                && constraint.content.layout.is_none()
+                && !self.layouts.contains_key(&constraint_key);
+            if constraint.content.collapse_margins && !is_relative_layout {
+                diagnostics.push(LintDiagnostic::IgnoredMarginCollapse { constraint_key });
+            };
+
+            let is_gap_ignored = |gap: Option<FillType>, is_scalable: bool| match gap {
+                Some(FillType::Minimize) => true,
+                Some(FillType::Scale(..)) => !is_scalable,
+                _ => false,
+            };
+            let is_flowing = matches!(constraint.content.mode, LayoutMode::Flow | LayoutMode::Wrap);
+            if is_gap_ignored(constraint.content.gap_main, is_relative_layout)
+                || is_gap_ignored(constraint.content.gap_cross, is_flowing)
+            {
+                diagnostics.push(LintDiagnostic::IgnoredGap { constraint_key });
+            };
+
+            let has_grid_placement = self.grid_areas.contains_key(&constraint_key)
+                || self.grid_spans.contains_key(&constraint_key);
+            let is_grid_placement_honored =
+                self.parent_keys
+                    .get(&constraint_key)
+                    .is_some_and(|&parent_constraint_key| {
+                        let parent_content =
+                            self.resolved_constraint(parent_constraint_key).content;
+                        let is_area_declared = self.grid_area(constraint_key).is_none_or(|area| {
+                            self.grid(parent_constraint_key)
+                                .is_some_and(|grid| grid.area(area).is_some())
+                        });
+                        parent_content.mode == LayoutMode::Grid && is_area_declared
+                    });
+            if has_grid_placement && (constraint.is_out_of_flow() || !is_grid_placement_honored) {
+                diagnostics.push(LintDiagnostic::IgnoredGridPlacement { constraint_key });
+            };
+
+            if self
+                .grid(constraint_key)
+                .is_some_and(|grid| !grid.is_well_formed())
+            {
+                diagnostics.push(LintDiagnostic::MalformedGridArea { constraint_key });
+            };
+
+            to_visit.extend(constraint_node.child_keys.iter().rev());
+        }
+
+        diagnostics
+    }
+}
+
+fn exact_length(fill_type: FillType) -> Option<f64> {
+    match fill_type {
+        FillType::Exact(length) => Some(length),
+        _ => None,
+    }
+}
+
+fn padding_exceeds(padding: Padding, (length_x, length_y): (Option<f64>, Option<f64>)) -> bool {
+    let exceeds_x = length_x.is_some_and(|length_x| padding.left + padding.right > length_x);
+    let exceeds_y = length_y.is_some_and(|length_y| padding.top + padding.bottom > length_y);
+    exceeds_x || exceeds_y
+}
+
+fn intersects(frame: Frame, child_frame: Frame) -> bool {
+    child_frame.offset_x < frame.length_x
+        && child_frame.offset_x + child_frame.length_x > 0.
+        && child_frame.offset_y < frame.length_y
+        && child_frame.offset_y + child_frame.length_y > 0.
+}
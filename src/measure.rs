@@ -0,0 +1,113 @@
+use std::{
+    any::{Any, TypeId},
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use crate::{solver::solve_subtree, ConstraintKey, Sensitivity, Solver};
+
+pub(crate) type Measure = Arc<dyn Fn(&mut dyn Any, f64, f64) -> Option<(f64, f64)> + Send + Sync>;
+
+impl Solver {
+    // Measure methods:
+
+    pub fn set_measure<C, F>(&mut self, constraint_key: ConstraintKey, measure: F) -> bool
+    where
+        C: 'static,
+        F: 'static + Fn(&mut C, f64, f64) -> (f64, f64) + Send + Sync,
+    {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key {
+            let measure: Measure = Arc::new(move |measure_context, max_length_x, max_length_y| {
+                let measure_context = measure_context.downcast_mut::<C>()?;
+                Some(measure(measure_context, max_length_x, max_length_y))
+            });
+            self.measures
+                .insert(constraint_key, (TypeId::of::<C>(), measure));
+            self.mark_dirty(constraint_key, self.region(constraint_key));
+        };
+        contains_constraint_key
+    }
+
+    pub fn remove_measure(&mut self, constraint_key: ConstraintKey) -> bool {
+        let did_remove = self.measures.remove(&constraint_key).is_some();
+        if did_remove {
+            self.mark_dirty(constraint_key, self.region(constraint_key));
+        };
+        did_remove
+    }
+
+    pub fn invalidate_measure(&mut self, constraint_key: ConstraintKey) -> bool {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key {
+            let region = self.region(constraint_key);
+            match self.relayout_root(constraint_key) {
+                Some(relayout_constraint_key) => {
+                    self.dirty_keys.insert(constraint_key, region);
+                    self.stale_measures
+                        .insert(constraint_key, relayout_constraint_key);
+                }
+                None => self.mark_dirty(constraint_key, region),
+            };
+        };
+        contains_constraint_key
+    }
+
+    pub(crate) fn is_measure_stale(&self, constraint_key: ConstraintKey) -> bool {
+        let mut current_constraint_key = self.parent_keys.get(&constraint_key).copied();
+        while let Some(constraint_key) = current_constraint_key {
+            if self
+                .stale_measures
+                .values()
+                .any(|&relayout_constraint_key| relayout_constraint_key == constraint_key)
+            {
+                return true;
+            };
+            current_constraint_key = self.parent_keys.get(&constraint_key).copied();
+        }
+        false
+    }
+
+    pub(crate) fn solve_stale_measures(
+        &mut self,
+        stale_measures: BTreeMap<ConstraintKey, ConstraintKey>,
+        mut measure_context: Option<&mut dyn Any>,
+    ) {
+        let relayout_constraint_keys = stale_measures.values().copied().collect::<BTreeSet<_>>();
+        for relayout_constraint_key in relayout_constraint_keys {
+            let Some(&frame_key) = self.key_map.get(&relayout_constraint_key) else {
+                continue;
+            };
+            let frame = *self.frame_tree.get(frame_key).unwrap().value;
+            let mut context = self.context();
+            context.measure_context = measure_context.as_deref_mut();
+            solve_subtree(&mut context, relayout_constraint_key, frame);
+            self.refresh_traversal_subtree(relayout_constraint_key);
+        }
+        for constraint_key in stale_measures.keys() {
+            self.dirty_keys.remove(constraint_key);
+        }
+    }
+
+    // A changed measure can only move frames up to the nearest ancestor whose size does not depend
+    // on its content. The root is always re-solved in full because its size comes from `solve`.
+    fn relayout_root(&self, constraint_key: ConstraintKey) -> Option<ConstraintKey> {
+        let is_region_dirty = self
+            .region(constraint_key)
+            .is_some_and(|island_constraint_key| self.islands[&island_constraint_key].is_dirty);
+        if self.is_dirty || is_region_dirty || !self.key_map.contains_key(&constraint_key) {
+            return None;
+        };
+
+        let mut current_constraint_key = constraint_key;
+        loop {
+            let (sensitivity_x, sensitivity_y) =
+                *self.sensitivities.get(&current_constraint_key)?;
+            let parent_constraint_key = *self.parent_keys.get(&current_constraint_key)?;
+            if sensitivity_x != Sensitivity::Content && sensitivity_y != Sensitivity::Content {
+                return Some(current_constraint_key);
+            };
+            current_constraint_key = parent_constraint_key;
+        }
+    }
+}
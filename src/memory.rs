@@ -0,0 +1,145 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    mem::size_of,
+};
+
+use cherrytree::Tree;
+use slotmap::Key;
+
+use crate::{ExtensionMap, ExtensionValue, Grid, Solver};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryReport {
+    pub constraint_tree: usize,
+    pub frame_tree: usize,
+    pub key_map: usize,
+    pub caches: usize,
+    pub attributes: usize,
+}
+
+impl MemoryReport {
+    pub fn total(&self) -> usize {
+        self.constraint_tree + self.frame_tree + self.key_map + self.caches + self.attributes
+    }
+}
+
+impl Solver {
+    // Memory methods:
+
+    pub fn memory_usage(&self) -> MemoryReport {
+        let caches = [
+            self.profile.as_ref().map_or(0, map_bytes),
+            self.traversal_indices.as_ref().map_or(0, map_bytes),
+            map_bytes(&self.physical_frames),
+            map_bytes(&self.frames_i32),
+            map_bytes(&self.parent_keys),
+            map_bytes(&self.solve_outputs)
+                + heap_bytes(&self.solve_outputs, |solve_output| {
+                    vec_bytes(&solve_output.collapsed) + vec_bytes(&solve_output.lines)
+                }),
+            map_bytes(&self.sensitivities),
+            map_bytes(&self.last_frames),
+            map_bytes(&self.constraint_keys_by_id),
+            map_bytes(&self.generations),
+            map_bytes(&self.dirty_keys),
+            map_bytes(&self.stale_measures),
+        ];
+        let attributes = [
+            map_bytes(&self.hooks),
+            map_bytes(&self.layouts),
+            map_bytes(&self.registry) + heap_bytes(&self.registry, |(name, _)| name.capacity()),
+            map_bytes(&self.transforms),
+            map_bytes(&self.islands),
+            map_bytes(&self.staged),
+            map_bytes(&self.virtualizations),
+            map_bytes(&self.splits) + heap_bytes(&self.splits, vec_bytes),
+            map_bytes(&self.grids) + heap_bytes(&self.grids, grid_bytes),
+            map_bytes(&self.grid_areas) + heap_bytes(&self.grid_areas, String::capacity),
+            map_bytes(&self.grid_spans),
+            map_bytes(&self.on_layouts),
+            map_bytes(&self.node_ids),
+            map_bytes(&self.labels) + heap_bytes(&self.labels, String::capacity),
+            extension_map_bytes(&self.extensions),
+            extension_map_bytes(&self.constraint_extensions),
+            map_bytes(&self.embeddeds),
+            map_bytes(&self.baseline_providers),
+            map_bytes(&self.measures),
+            map_bytes(&self.scrolls),
+            set_bytes(&self.hit_clips),
+        ];
+
+        MemoryReport {
+            constraint_tree: tree_bytes(&self.constraint_tree),
+            frame_tree: tree_bytes(&self.frame_tree),
+            key_map: map_bytes(&self.key_map),
+            caches: caches.into_iter().sum(),
+            attributes: attributes.into_iter().sum(),
+        }
+    }
+}
+
+fn tree_bytes<K: Key, V>(tree: &Tree<K, V>) -> usize {
+    let mut bytes = 0;
+    let mut to_visit_keys = tree.root_key().into_iter().collect::<Vec<_>>();
+
+    while let Some(key) = to_visit_keys.pop() {
+        let node = tree.get(key).unwrap();
+        bytes += size_of::<K>() + size_of::<V>();
+        bytes += node.child_keys.capacity() * (size_of::<K>() + size_of::<usize>());
+        to_visit_keys.extend(node.child_keys.iter().copied());
+    }
+
+    bytes
+}
+
+fn map_bytes<K, V>(map: &BTreeMap<K, V>) -> usize {
+    map.len() * size_of::<(K, V)>()
+}
+
+fn set_bytes<K>(set: &BTreeSet<K>) -> usize {
+    set.len() * size_of::<K>()
+}
+
+fn heap_bytes<K, V>(map: &BTreeMap<K, V>, value_bytes: impl Fn(&V) -> usize) -> usize {
+    map.values().map(value_bytes).sum()
+}
+
+fn vec_bytes<T>(values: &Vec<T>) -> usize {
+    values.capacity() * size_of::<T>()
+}
+
+fn grid_bytes(grid: &Grid) -> usize {
+    let area_bytes = grid
+        .areas
+        .iter()
+        .map(|row| vec_bytes(row) + row.iter().map(String::capacity).sum::<usize>())
+        .sum::<usize>();
+    vec_bytes(&grid.columns) + vec_bytes(&grid.rows) + vec_bytes(&grid.areas) + area_bytes
+}
+
+fn extension_map_bytes<K>(map: &BTreeMap<K, ExtensionMap>) -> usize {
+    map_bytes(map) + heap_bytes(map, extension_bytes)
+}
+
+fn extension_bytes(extension_map: &ExtensionMap) -> usize {
+    map_bytes(extension_map)
+        + extension_map
+            .iter()
+            .map(|(name, value)| name.capacity() + extension_value_bytes(value))
+            .sum::<usize>()
+}
+
+fn extension_value_bytes(value: &ExtensionValue) -> usize {
+    match value {
+        ExtensionValue::String(string) => string.capacity(),
+        ExtensionValue::Sequence(values) => {
+            vec_bytes(values) + values.iter().map(extension_value_bytes).sum::<usize>()
+        }
+        ExtensionValue::Map(extension_map) => extension_bytes(extension_map),
+        ExtensionValue::Null
+        | ExtensionValue::Bool(..)
+        | ExtensionValue::Integer(..)
+        | ExtensionValue::Unsigned(..)
+        | ExtensionValue::Float(..) => 0,
+    }
+}
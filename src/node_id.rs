@@ -0,0 +1,104 @@
+use indexmap::IndexSet;
+
+use crate::{Constraint, ConstraintKey, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeId(pub u64);
+
+impl Solver {
+    // Node id methods:
+
+    pub fn set_node_id(&mut self, constraint_key: ConstraintKey, node_id: NodeId) -> bool {
+        let is_available = match self.constraint_keys_by_id.get(&node_id) {
+            Some(&other_constraint_key) => other_constraint_key == constraint_key,
+            None => true,
+        };
+        let is_valid = self.constraint_tree.contains(constraint_key) && is_available;
+
+        if is_valid {
+            self.remove_node_id(constraint_key);
+            self.node_ids.insert(constraint_key, node_id);
+            self.constraint_keys_by_id.insert(node_id, constraint_key);
+        };
+
+        is_valid
+    }
+
+    pub fn remove_node_id(&mut self, constraint_key: ConstraintKey) -> Option<NodeId> {
+        let node_id = self.node_ids.remove(&constraint_key)?;
+        self.constraint_keys_by_id.remove(&node_id);
+        Some(node_id)
+    }
+
+    pub fn node_id(&self, constraint_key: ConstraintKey) -> Option<NodeId> {
+        self.node_ids.get(&constraint_key).copied()
+    }
+
+    pub fn key_for_id(&self, node_id: NodeId) -> Option<ConstraintKey> {
+        self.constraint_keys_by_id.get(&node_id).copied()
+    }
+
+    // Reconciliation methods:
+
+    pub fn sync_children<I>(&mut self, parent_constraint_key: ConstraintKey, items: I) -> bool
+    where
+        I: IntoIterator<Item = (NodeId, Constraint)>,
+    {
+        let Some(parent_constraint_node) = self.constraint_tree.get(parent_constraint_key) else {
+            return false;
+        };
+        let items = items.into_iter().collect::<Vec<_>>();
+
+        let stale_constraint_keys = parent_constraint_node
+            .child_keys
+            .iter()
+            .copied()
+            .filter(|&child_constraint_key| {
+                !self.node_id(child_constraint_key).is_some_and(|node_id| {
+                    items
+                        .iter()
+                        .any(|&(item_node_id, _)| item_node_id == node_id)
+                })
+            })
+            .collect::<Vec<_>>();
+        for stale_constraint_key in stale_constraint_keys {
+            self.remove(stale_constraint_key, None);
+        }
+
+        let mut constraint_keys = IndexSet::with_capacity(items.len());
+        for (node_id, constraint) in items {
+            let constraint_key = match self.key_for_id(node_id) {
+                Some(constraint_key) => {
+                    let is_child =
+                        self.parent_keys.get(&constraint_key) == Some(&parent_constraint_key);
+                    if !is_child && !self.rebase(constraint_key, parent_constraint_key) {
+                        continue;
+                    };
+                    self.set(constraint_key, constraint);
+                    constraint_key
+                }
+                None => {
+                    let Some(constraint_key) = self.insert(constraint, parent_constraint_key)
+                    else {
+                        continue;
+                    };
+                    self.set_node_id(constraint_key, node_id);
+                    constraint_key
+                }
+            };
+            constraint_keys.insert(constraint_key);
+        }
+
+        let child_keys = self
+            .constraint_tree
+            .get(parent_constraint_key)
+            .unwrap()
+            .child_keys;
+        if !child_keys.iter().eq(constraint_keys.iter()) {
+            self.reorder_children(parent_constraint_key, |_| constraint_keys);
+        };
+
+        true
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{Padding, Solver};
+
+impl Solver {
+    // Occupancy methods:
+
+    pub fn occupancy(&self, grid_length_x: usize, grid_length_y: usize) -> Vec<u32> {
+        let Some(root_constraint_key) = self.constraint_tree.root_key() else {
+            return vec![];
+        };
+        if self.is_dirty() {
+            return vec![];
+        };
+        let (Some(root_frame), Some((origin_x, origin_y))) = (
+            self.get_frame(root_constraint_key),
+            self.to_world(root_constraint_key, 0., 0.),
+        ) else {
+            return vec![];
+        };
+
+        let cell_length_x = root_frame.length_x / grid_length_x as f64;
+        let cell_length_y = root_frame.length_y / grid_length_y as f64;
+        let mut occupancy = vec![0; grid_length_x * grid_length_y];
+        let mut to_visit = vec![root_constraint_key];
+
+        while let Some(constraint_key) = to_visit.pop() {
+            let constraint_node = self.constraint_tree.get(constraint_key).unwrap();
+            if !constraint_node.child_keys.is_empty() {
+                to_visit.extend(constraint_node.child_keys.iter().copied());
+                continue;
+            };
+
+            for (index, count) in occupancy.iter_mut().enumerate() {
+                let (cell_x, cell_y) = (index % grid_length_x, index / grid_length_x);
+                let x = origin_x + (cell_x as f64 + 0.5) * cell_length_x;
+                let y = origin_y + (cell_y as f64 + 0.5) * cell_length_y;
+
+                if self.contains_point(constraint_key, x, y, Padding::default()) {
+                    *count += 1;
+                };
+            }
+        }
+
+        occupancy
+    }
+}
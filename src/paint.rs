@@ -0,0 +1,45 @@
+use crate::{ConstraintKey, Display, Solver, Visibility};
+
+impl Solver {
+    // Paint methods:
+
+    pub fn paint_order(&self) -> Option<Vec<ConstraintKey>> {
+        if self.is_dirty() {
+            return None;
+        };
+
+        let mut paint_order = vec![];
+        let mut to_visit = self
+            .constraint_tree
+            .root_key()
+            .map(|root_constraint_key| (root_constraint_key, 0))
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        while let Some((constraint_key, parent_layer)) = to_visit.pop() {
+            let constraint_node = self.constraint_tree.get(constraint_key).unwrap();
+            let constraint = constraint_node.value;
+            if constraint.display == Display::None || constraint.visibility == Visibility::Hidden {
+                continue;
+            };
+
+            let layer = constraint.layer.unwrap_or(parent_layer);
+            paint_order.push((layer, constraint_key));
+            to_visit.extend(
+                constraint_node
+                    .child_keys
+                    .iter()
+                    .rev()
+                    .map(|&child_constraint_key| (child_constraint_key, layer)),
+            );
+        }
+
+        paint_order.sort_by_key(|&(layer, _)| layer);
+        Some(
+            paint_order
+                .into_iter()
+                .map(|(_, constraint_key)| constraint_key)
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,60 @@
+use std::mem;
+
+use crate::Solver;
+
+#[derive(Default, Clone)]
+pub struct PipelinedSolver {
+    front: Solver,
+    back: Solver,
+}
+
+impl PipelinedSolver {
+    pub fn new(solver: Solver) -> Self {
+        Self {
+            front: solver.clone(),
+            back: solver,
+        }
+    }
+
+    pub fn front(&self) -> &Solver {
+        &self.front
+    }
+
+    pub fn back(&self) -> &Solver {
+        &self.back
+    }
+
+    pub fn mutate<F, T>(&mut self, f: F) -> T
+    where
+        F: FnOnce(&mut Solver) -> T,
+    {
+        f(&mut self.back)
+    }
+
+    pub fn solve_back(&mut self, length_x: f64, length_y: f64) {
+        let hooks = mem::take(&mut self.back.hooks);
+        let on_layouts = mem::take(&mut self.back.on_layouts);
+        let embeddeds = mem::take(&mut self.back.embeddeds);
+
+        self.back.solve(length_x, length_y);
+
+        self.back.hooks = hooks;
+        self.back.on_layouts = on_layouts;
+        self.back.embeddeds = embeddeds;
+    }
+
+    pub fn swap(&mut self) -> bool {
+        let is_solved = !self.back.is_dirty();
+        if is_solved {
+            mem::swap(&mut self.front, &mut self.back);
+            self.front.run_on_layouts();
+            self.front.solve_embeddeds();
+            self.back.clone_from(&self.front);
+        };
+        is_solved
+    }
+
+    pub fn into_front(self) -> Solver {
+        self.front
+    }
+}
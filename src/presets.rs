@@ -0,0 +1,128 @@
+use crate::{Align, Bounds, Constraint, ConstraintKey, Content, Direction, Fill, FillType, Solver};
+
+const SPLIT_PANE_RESOLUTION: usize = 1000;
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DeclarativeNode<V> {
+    pub value: V,
+    pub children: Vec<Self>,
+}
+
+impl<V> DeclarativeNode<V> {
+    pub fn new(value: V, children: Vec<Self>) -> Self {
+        Self { value, children }
+    }
+}
+
+pub fn sidebar(fixed_width: f64) -> DeclarativeNode<Constraint> {
+    let sidebar = Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Exact(fixed_width),
+            y: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    DeclarativeNode::new(
+        horizontal(),
+        vec![
+            DeclarativeNode::new(sidebar, vec![]),
+            DeclarativeNode::new(Constraint::default(), vec![]),
+        ],
+    )
+}
+
+pub fn toolbar() -> DeclarativeNode<Constraint> {
+    let toolbar = Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Scale(1),
+            y: FillType::Minimize,
+        },
+        content: Content {
+            direction: Direction::Horizontal,
+            align_cross: Align::Middle,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    DeclarativeNode::new(toolbar, vec![])
+}
+
+pub fn centered_modal(max_width: f64, max_height: f64) -> DeclarativeNode<Constraint> {
+    let backdrop = Constraint {
+        content: Content {
+            align_main: Align::Middle,
+            align_cross: Align::Middle,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let modal = Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Scale(1),
+            y: FillType::Scale(1),
+        },
+        bounds: Bounds {
+            max_x: Some(max_width),
+            max_y: Some(max_height),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    DeclarativeNode::new(backdrop, vec![DeclarativeNode::new(modal, vec![])])
+}
+
+pub fn split_pane(ratio: f64) -> DeclarativeNode<Constraint> {
+    let leading_scale = (ratio.clamp(0., 1.) * SPLIT_PANE_RESOLUTION as f64).round() as usize;
+    let pane = |scale| Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Scale(scale),
+            y: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    DeclarativeNode::new(
+        horizontal(),
+        vec![
+            DeclarativeNode::new(pane(leading_scale), vec![]),
+            DeclarativeNode::new(pane(SPLIT_PANE_RESOLUTION - leading_scale), vec![]),
+        ],
+    )
+}
+
+fn horizontal() -> Constraint {
+    Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+impl Solver {
+    // Declarative methods:
+
+    pub fn insert_declarative(
+        &mut self,
+        declarative_node: &DeclarativeNode<Constraint>,
+        parent_constraint_key: Option<ConstraintKey>,
+    ) -> Option<ConstraintKey> {
+        let capacity = declarative_node.children.len();
+        let constraint_key = match parent_constraint_key {
+            Some(parent_constraint_key) => {
+                self.insert_with_capacity(declarative_node.value, parent_constraint_key, capacity)?
+            }
+            None => self.insert_root_with_capacity(declarative_node.value, capacity)?,
+        };
+
+        for child in &declarative_node.children {
+            self.insert_declarative(child, Some(constraint_key))?;
+        }
+
+        Some(constraint_key)
+    }
+}
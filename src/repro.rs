@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    scroll::Scroll,
+    solver::{restore, SolveOutput},
+    virtualize::Virtualization,
+    Baseline, Constraint, ConstraintKey, ExtensionMap, Frame, Grid, LayoutDirection, LineInfo,
+    NodeId, RoundingPolicy, Solver,
+};
+
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReproBundle {
+    pub version: String,
+    pub root: Option<ReproNode>,
+    pub solve_lengths: Option<(f64, f64)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scale_factor: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rounding_policy: RoundingPolicy,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub layout_direction: LayoutDirection,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub baseline_grid: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_deterministic: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReproNode {
+    pub constraint: ReproConstraint,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub node_id: Option<NodeId>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub label: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub grid: Option<Grid>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub grid_area: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub grid_span: Option<(usize, usize)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub split: Vec<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scroll_offset: Option<(f64, f64)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_scroll_anchoring: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub estimated_child_extent: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub virtual_child_count: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_clipping_hits: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub frame: Option<Frame>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub overflow: Option<(f64, f64)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub free_space: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lines: Vec<LineInfo>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub collapsed: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub baseline: Option<Baseline>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub traversal_index: Option<usize>,
+    pub children: Vec<Self>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub extensions: ExtensionMap,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReproConstraint {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub constraint: Constraint,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub extensions: ExtensionMap,
+}
+
+impl Solver {
+    // Repro methods:
+
+    pub fn dump_repro(&self) -> ReproBundle {
+        fn dump_node(solver: &Solver, constraint_key: ConstraintKey) -> ReproNode {
+            let constraint_node = solver.constraint_tree.get(constraint_key).unwrap();
+            let children = constraint_node
+                .child_keys
+                .iter()
+                .map(|&child_constraint_key| dump_node(solver, child_constraint_key))
+                .collect();
+
+            let virtualization = solver.virtualizations.get(&constraint_key);
+            let frame = solver.get_frame(constraint_key);
+            let is_solved = frame.is_some();
+            let collapsed = solver
+                .collapsed_children(constraint_key)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|child_constraint_key| {
+                    constraint_node
+                        .child_keys
+                        .get_index_of(child_constraint_key)
+                })
+                .collect();
+
+            ReproNode {
+                constraint: ReproConstraint {
+                    constraint: *constraint_node.value,
+                    extensions: solver
+                        .constraint_extensions
+                        .get(&constraint_key)
+                        .cloned()
+                        .unwrap_or_default(),
+                },
+                node_id: solver.node_id(constraint_key),
+                label: solver.label(constraint_key).map(str::to_string),
+                grid: solver.grid(constraint_key).cloned(),
+                grid_area: solver.grid_area(constraint_key).map(str::to_string),
+                grid_span: Some(solver.grid_span(constraint_key)).filter(|&span| span != (1, 1)),
+                split: solver
+                    .splits
+                    .get(&constraint_key)
+                    .cloned()
+                    .unwrap_or_default(),
+                scroll_offset: solver.scroll_offset(constraint_key),
+                is_scroll_anchoring: solver.is_scroll_anchoring(constraint_key),
+                estimated_child_extent: virtualization
+                    .map(|virtualization| virtualization.estimated_child_extent),
+                virtual_child_count: virtualization
+                    .and_then(|virtualization| virtualization.child_count),
+                is_clipping_hits: solver.is_clipping_hits(constraint_key),
+                frame,
+                overflow: is_solved
+                    .then(|| solver.solve_outputs.get(&constraint_key)?.overflow)
+                    .flatten(),
+                free_space: solver.free_space(constraint_key),
+                lines: solver.lines(constraint_key),
+                collapsed,
+                baseline: is_solved
+                    .then(|| solver.solve_outputs.get(&constraint_key)?.baseline)
+                    .flatten(),
+                traversal_index: solver.get_traversal_index(constraint_key),
+                children,
+                extensions: solver
+                    .extensions(constraint_key)
+                    .cloned()
+                    .unwrap_or_default(),
+            }
+        }
+
+        let root = self
+            .constraint_tree
+            .root_key()
+            .map(|root_constraint_key| dump_node(self, root_constraint_key));
+
+        ReproBundle {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            root,
+            solve_lengths: self.last_solve_lengths,
+            scale_factor: self.scale_factor,
+            rounding_policy: self.rounding_policy,
+            layout_direction: self.layout_direction,
+            baseline_grid: self.baseline_grid,
+            is_deterministic: self.is_deterministic,
+        }
+    }
+
+    pub fn from_repro(bundle: ReproBundle) -> Option<Self> {
+        fn insert_node<'a>(
+            solver: &mut Solver,
+            repro_node: &'a ReproNode,
+            parent_constraint_key: Option<ConstraintKey>,
+            repro_nodes: &mut Vec<(ConstraintKey, &'a ReproNode)>,
+        ) -> Option<()> {
+            let number_of_children = repro_node.children.len();
+            let constraint_key = match parent_constraint_key {
+                Some(parent_constraint_key) => solver.insert_with_capacity(
+                    repro_node.constraint.constraint,
+                    parent_constraint_key,
+                    number_of_children,
+                )?,
+                None => solver.insert_root_with_capacity(
+                    repro_node.constraint.constraint,
+                    number_of_children,
+                )?,
+            };
+            if let Some(node_id) = repro_node.node_id {
+                solver.set_node_id(constraint_key, node_id).then_some(())?;
+            };
+            if let Some(label) = &repro_node.label {
+                solver.set_label(constraint_key, label.as_str());
+            };
+            solver.set_grid(constraint_key, repro_node.grid.clone());
+            solver.set_grid_area(constraint_key, repro_node.grid_area.clone());
+            if let Some((column_span, row_span)) = repro_node.grid_span {
+                solver
+                    .set_grid_span(constraint_key, column_span, row_span)
+                    .then_some(())?;
+            };
+            if !repro_node.split.is_empty() {
+                solver
+                    .splits
+                    .insert(constraint_key, repro_node.split.clone());
+            };
+            if let Some((offset_x, offset_y)) = repro_node.scroll_offset {
+                solver.scrolls.insert(
+                    constraint_key,
+                    Scroll {
+                        offset_x,
+                        offset_y,
+                        is_anchoring: repro_node.is_scroll_anchoring,
+                    },
+                );
+            };
+            if repro_node.estimated_child_extent.is_some()
+                || repro_node.virtual_child_count.is_some()
+            {
+                solver.virtualizations.insert(
+                    constraint_key,
+                    Virtualization {
+                        estimated_child_extent: repro_node
+                            .estimated_child_extent
+                            .unwrap_or_default(),
+                        child_count: repro_node.virtual_child_count,
+                    },
+                );
+            };
+            solver.set_clipping_hits(constraint_key, repro_node.is_clipping_hits);
+            if !repro_node.constraint.extensions.is_empty() {
+                solver
+                    .constraint_extensions
+                    .insert(constraint_key, repro_node.constraint.extensions.clone());
+            };
+            for (name, value) in &repro_node.extensions {
+                solver.set_extension(constraint_key, name.as_str(), value.clone());
+            }
+            repro_nodes.push((constraint_key, repro_node));
+
+            for child in &repro_node.children {
+                insert_node(solver, child, Some(constraint_key), repro_nodes)?;
+            }
+
+            Some(())
+        }
+
+        let mut solver = Self {
+            scale_factor: bundle.scale_factor,
+            rounding_policy: bundle.rounding_policy,
+            layout_direction: bundle.layout_direction,
+            baseline_grid: bundle.baseline_grid,
+            is_deterministic: bundle.is_deterministic,
+            ..Self::default()
+        };
+        let mut repro_nodes = vec![];
+
+        if let Some(root) = &bundle.root {
+            insert_node(&mut solver, root, None, &mut repro_nodes)?;
+        };
+
+        solver.last_solve_lengths = bundle.solve_lengths;
+
+        let frames = repro_nodes
+            .iter()
+            .filter_map(|&(constraint_key, repro_node)| {
+                repro_node.frame.map(|frame| (constraint_key, frame))
+            })
+            .collect::<BTreeMap<_, _>>();
+        let is_restorable = !repro_nodes.is_empty()
+            && bundle.solve_lengths.is_some()
+            && frames.len() == repro_nodes.len();
+        if is_restorable {
+            let mut context = solver.context();
+            restore(&mut context, &frames);
+
+            for &(constraint_key, repro_node) in &repro_nodes {
+                let child_keys = &solver
+                    .constraint_tree
+                    .get(constraint_key)
+                    .unwrap()
+                    .child_keys;
+                let collapsed_constraint_keys = repro_node
+                    .collapsed
+                    .iter()
+                    .map(|&index| child_keys.get_index(index).copied())
+                    .collect::<Option<Vec<_>>>()?;
+
+                solver.solve_outputs.insert(
+                    constraint_key,
+                    SolveOutput {
+                        overflow: repro_node.overflow,
+                        free_space: repro_node.free_space,
+                        collapsed: collapsed_constraint_keys,
+                        lines: repro_node.lines.clone(),
+                        baseline: repro_node.baseline,
+                    },
+                );
+            }
+
+            solver.refresh_sensitivities();
+            solver.is_dirty = false;
+            solver.dirty_keys.clear();
+
+            let is_traversed = repro_nodes
+                .iter()
+                .any(|(_, repro_node)| repro_node.traversal_index.is_some());
+            if is_traversed {
+                solver.assign_traversal_indices();
+            };
+        };
+
+        Some(solver)
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{solver::recompose, ConstraintKey, Solver};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Scroll {
+    pub(crate) offset_x: f64,
+    pub(crate) offset_y: f64,
+    pub(crate) is_anchoring: bool,
+}
+
+impl Solver {
+    // Scroll methods:
+
+    pub fn scroll_offset(&self, constraint_key: ConstraintKey) -> Option<(f64, f64)> {
+        self.scrolls
+            .get(&constraint_key)
+            .map(|scroll| (scroll.offset_x, scroll.offset_y))
+    }
+
+    pub fn set_scroll_offset(
+        &mut self,
+        constraint_key: ConstraintKey,
+        offset_x: f64,
+        offset_y: f64,
+    ) -> bool {
+        let is_valid = self.constraint_tree.contains(constraint_key)
+            && offset_x.is_finite()
+            && offset_y.is_finite();
+
+        if is_valid {
+            let scroll = self.scrolls.entry(constraint_key).or_default();
+            scroll.offset_x = offset_x;
+            scroll.offset_y = offset_y;
+
+            if !self.is_dirty() && self.key_map.contains_key(&constraint_key) {
+                let mut context = self.context();
+                recompose(&mut context, constraint_key);
+            };
+        };
+
+        is_valid
+    }
+
+    pub fn is_scroll_anchoring(&self, constraint_key: ConstraintKey) -> bool {
+        self.scrolls
+            .get(&constraint_key)
+            .is_some_and(|scroll| scroll.is_anchoring)
+    }
+
+    pub fn set_scroll_anchoring(
+        &mut self,
+        constraint_key: ConstraintKey,
+        is_anchoring: bool,
+    ) -> bool {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key {
+            self.scrolls.entry(constraint_key).or_default().is_anchoring = is_anchoring;
+        };
+        contains_constraint_key
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+use crate::{ConstraintKey, Direction, FillType, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Sensitivity {
+    Exact,
+    Content,
+    Root,
+}
+
+impl Sensitivity {
+    fn from_fill_type(fill_type: FillType, parent_sensitivity: Self) -> Self {
+        match fill_type {
+            FillType::Exact(..) => Self::Exact,
+            FillType::Scale(..) | FillType::Fraction(..) | FillType::Bounded { .. } => {
+                parent_sensitivity
+            }
+            FillType::Minimize => Self::Content,
+        }
+    }
+}
+
+impl Solver {
+    // Sensitivity methods:
+
+    pub fn resize_sensitivity(
+        &self,
+        constraint_key: ConstraintKey,
+    ) -> Option<(Sensitivity, Sensitivity)> {
+        if self.is_dirty() {
+            return None;
+        };
+
+        self.sensitivities.get(&constraint_key).copied()
+    }
+
+    pub(crate) fn refresh_sensitivities(&mut self) {
+        let mut sensitivities = BTreeMap::default();
+        let mut to_visit = self
+            .constraint_tree
+            .root_key()
+            .map(|root_key| {
+                (
+                    root_key,
+                    Direction::default(),
+                    (Sensitivity::Root, Sensitivity::Root),
+                )
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        while let Some((constraint_key, parent_direction, (parent_x, parent_y))) = to_visit.pop() {
+            let constraint = self.resolved_constraint(constraint_key);
+            let relative_fill = constraint.fill.to_relative_fill(parent_direction);
+            let (fill_x, fill_y) = match parent_direction {
+                Direction::Horizontal => (relative_fill.main, relative_fill.cross),
+                Direction::Vertical => (relative_fill.cross, relative_fill.main),
+            };
+            let sensitivity = (
+                Sensitivity::from_fill_type(fill_x, parent_x),
+                Sensitivity::from_fill_type(fill_y, parent_y),
+            );
+            sensitivities.insert(constraint_key, sensitivity);
+
+            let constraint_node = self.constraint_tree.get(constraint_key).unwrap();
+            to_visit.extend(
+                constraint_node
+                    .child_keys
+                    .iter()
+                    .map(|&child_constraint_key| {
+                        (
+                            child_constraint_key,
+                            constraint.content.direction,
+                            sensitivity,
+                        )
+                    }),
+            );
+        }
+
+        self.sensitivities = sensitivities;
+    }
+}
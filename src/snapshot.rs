@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use crate::{ConstraintKey, Frame, Solver};
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct FrameSnapshot {
+    pub length_x: f64,
+    pub length_y: f64,
+    pub frames: BTreeMap<ConstraintKey, Frame>,
+}
+
+impl FrameSnapshot {
+    pub fn get(&self, constraint_key: ConstraintKey) -> Option<Frame> {
+        self.frames.get(&constraint_key).copied()
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct FrameGradient {
+    pub per_x: Frame,
+    pub per_y: Frame,
+}
+
+impl FrameGradient {
+    pub fn extrapolate(&self, frame: Frame, delta_x: f64, delta_y: f64) -> Frame {
+        Frame {
+            offset_x: frame.offset_x
+                + self.per_x.offset_x * delta_x
+                + self.per_y.offset_x * delta_y,
+            length_x: frame.length_x
+                + self.per_x.length_x * delta_x
+                + self.per_y.length_x * delta_y,
+            offset_y: frame.offset_y
+                + self.per_x.offset_y * delta_x
+                + self.per_y.offset_y * delta_y,
+            length_y: frame.length_y
+                + self.per_x.length_y * delta_x
+                + self.per_y.length_y * delta_y,
+        }
+    }
+}
+
+impl Solver {
+    // Snapshot methods:
+
+    pub fn solve_many(&self, lengths: &[(f64, f64)]) -> Vec<FrameSnapshot> {
+        let mut solver = Self {
+            hooks: BTreeMap::default(),
+            on_layouts: BTreeMap::default(),
+            embeddeds: BTreeMap::default(),
+            profile: None,
+            ..self.clone()
+        };
+
+        lengths
+            .iter()
+            .map(|&(length_x, length_y)| {
+                solver.is_dirty = true;
+                solver.solve(length_x, length_y);
+
+                let frames = solver
+                    .key_map
+                    .iter()
+                    .map(|(&constraint_key, &frame_key)| {
+                        (
+                            constraint_key,
+                            *solver.frame_tree.get(frame_key).unwrap().value,
+                        )
+                    })
+                    .collect();
+
+                FrameSnapshot {
+                    length_x,
+                    length_y,
+                    frames,
+                }
+            })
+            .collect()
+    }
+
+    pub fn solve_gradient(
+        &self,
+        length_x: f64,
+        length_y: f64,
+        delta_x: f64,
+        delta_y: f64,
+    ) -> BTreeMap<ConstraintKey, FrameGradient> {
+        let snapshots = self.solve_many(&[
+            (length_x, length_y),
+            (length_x + delta_x, length_y),
+            (length_x, length_y + delta_y),
+        ]);
+        let [base, step_x, step_y] = &snapshots[..] else {
+            unreachable!()
+        };
+
+        base.frames
+            .iter()
+            .map(|(&constraint_key, &frame)| {
+                let per_x = step_x
+                    .get(constraint_key)
+                    .map_or_else(Frame::default, |step_frame| {
+                        rate(frame, step_frame, delta_x)
+                    });
+                let per_y = step_y
+                    .get(constraint_key)
+                    .map_or_else(Frame::default, |step_frame| {
+                        rate(frame, step_frame, delta_y)
+                    });
+                (constraint_key, FrameGradient { per_x, per_y })
+            })
+            .collect()
+    }
+}
+
+fn rate(frame: Frame, step_frame: Frame, delta: f64) -> Frame {
+    if delta == 0. {
+        return Frame::default();
+    };
+
+    Frame {
+        offset_x: (step_frame.offset_x - frame.offset_x) / delta,
+        length_x: (step_frame.length_x - frame.length_x) / delta,
+        offset_y: (step_frame.offset_y - frame.offset_y) / delta,
+        length_y: (step_frame.length_y - frame.length_y) / delta,
+    }
+}
@@ -0,0 +1,114 @@
+use std::cmp::Reverse;
+
+use indexmap::IndexSet;
+
+use crate::{
+    exact_gap,
+    solver::{iter, minimize, solve_children, Bound, Context},
+    ConstraintKey, Content, FrameKey, Overflow, RelativeFrame, ResolvedFillType,
+};
+
+pub(super) fn collapse_child_keys(
+    context: &mut Context,
+    parent_constraint_key: ConstraintKey,
+    constraint_keys: IndexSet<ConstraintKey>,
+    relative_content_frame: RelativeFrame,
+    parent_content: Content,
+) -> (IndexSet<ConstraintKey>, Vec<ConstraintKey>) {
+    let constraint_tree = context.constraint_tree;
+    if parent_content.overflow != Overflow::Collapse {
+        return (constraint_keys, vec![]);
+    };
+
+    let direction = parent_content.direction;
+    let gap_length_main = exact_gap(parent_content.gap_main, relative_content_frame.length_main);
+
+    let mut candidates = iter(constraint_tree, &constraint_keys)
+        .map(|(constraint_key, constraint_node)| {
+            let constraint = context.get_constraint(constraint_key);
+            let relative_fill = constraint.fill.to_relative_fill(direction).resolve(
+                relative_content_frame.length_main,
+                relative_content_frame.length_cross,
+            );
+            let length_main = match relative_fill.main {
+                ResolvedFillType::Exact(exact_main) => exact_main,
+                ResolvedFillType::Scale(..) => 0.,
+                ResolvedFillType::Minimize => {
+                    let (minimizing_length_main, _) = minimize(
+                        context,
+                        constraint_key,
+                        &constraint_node,
+                        direction,
+                        Bound::AtMost(relative_content_frame.length_main),
+                        Bound::AtMost(relative_content_frame.length_cross),
+                    );
+                    minimizing_length_main
+                }
+            };
+            (constraint_key, constraint.priority, length_main)
+        })
+        .collect::<Vec<_>>();
+
+    let used_length_main = |candidates: &[(ConstraintKey, Option<u32>, f64)]| {
+        let number_of_gaps = candidates.len().saturating_sub(1);
+        candidates
+            .iter()
+            .map(|&(_, _, length_main)| length_main)
+            .sum::<f64>()
+            + gap_length_main * number_of_gaps as f64
+    };
+
+    let mut collapsed_constraint_keys = vec![];
+    while used_length_main(&candidates) > relative_content_frame.length_main {
+        let lowest = candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &(_, priority, _))| priority.map(|priority| (priority, index)))
+            .min_by_key(|&(priority, index)| (priority, Reverse(index)));
+        let Some((_, index)) = lowest else {
+            break;
+        };
+        let (constraint_key, _, _) = candidates.remove(index);
+        collapsed_constraint_keys.push(constraint_key);
+    }
+
+    context.solve_output(parent_constraint_key).collapsed = collapsed_constraint_keys.clone();
+
+    let constraint_keys = candidates
+        .into_iter()
+        .map(|(constraint_key, _, _)| constraint_key)
+        .collect();
+    (constraint_keys, collapsed_constraint_keys)
+}
+
+pub(super) fn solve_collapsed_child_keys(
+    context: &mut Context,
+    constraint_keys: &[ConstraintKey],
+    parent_frame_key: FrameKey,
+    relative_content_frame: RelativeFrame,
+    parent_content: Content,
+) {
+    let constraint_tree = context.constraint_tree;
+
+    for &constraint_key in constraint_keys {
+        let start = context.start_timer();
+        let constraint_node = constraint_tree.get(constraint_key).unwrap();
+
+        let relative_frame = RelativeFrame {
+            length_main: 0.,
+            length_cross: 0.,
+            ..relative_content_frame
+        };
+        let frame = relative_frame.to_frame(parent_content.direction);
+        let frame_key = context.insert_frame(
+            constraint_key,
+            frame,
+            parent_frame_key,
+            constraint_node.child_keys.len(),
+        );
+
+        solve_children(context, constraint_key, &constraint_node, frame_key, frame);
+
+        context.stop_timer(constraint_key, start);
+    }
+}
@@ -0,0 +1,213 @@
+use std::ops::Range;
+
+use indexmap::IndexSet;
+
+use crate::{
+    exact_gap,
+    solver::{
+        bound_length,
+        flow::{measure_items, FlowItem},
+        iter, solve_children, Bound, Context,
+    },
+    Align, ConstraintKey, Content, Direction, FrameKey, LineInfo, RelativeFrame,
+};
+
+pub(super) fn solve_child_keys_columns(
+    context: &mut Context,
+    parent_constraint_key: ConstraintKey,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    parent_frame_key: FrameKey,
+    relative_content_frame: RelativeFrame,
+    parent_content: Content,
+    number_of_columns: usize,
+) {
+    let constraint_tree = context.constraint_tree;
+    let direction = parent_content.direction;
+    let number_of_columns = number_of_columns.max(1);
+    let gap_length_main = exact_gap(parent_content.gap_main, relative_content_frame.length_main);
+    let gap_length_cross = exact_gap(
+        parent_content.gap_cross,
+        relative_content_frame.length_cross,
+    );
+    let column_length_cross = column_length_cross(
+        relative_content_frame.length_cross,
+        number_of_columns,
+        gap_length_cross,
+    );
+
+    let items = measure_items(
+        context,
+        constraint_keys,
+        direction,
+        parent_content.align_cross,
+        relative_content_frame.length_main,
+        column_length_cross,
+    );
+    let ranges = balance_columns(&items, number_of_columns, gap_length_main);
+
+    let mut lines = Vec::with_capacity(ranges.len());
+    let mut offset_cross = relative_content_frame.offset_cross;
+    let mut children = iter(constraint_tree, constraint_keys);
+
+    for child_range in ranges {
+        let mut offset_main = relative_content_frame.offset_main;
+
+        for item in &items[child_range.clone()] {
+            let (constraint_key, constraint_node) = children.next().unwrap();
+            let start = context.start_timer();
+
+            let bounded_length_cross = bound_length(
+                item.relative_fill.cross,
+                column_length_cross,
+                item.align_cross == Align::Stretch,
+            );
+            let item_length_cross = match (bounded_length_cross, item.is_stretching) {
+                (Some(bounded_length_cross), _) => bounded_length_cross,
+                (None, true) => column_length_cross,
+                (None, false) => item.length_cross.min(column_length_cross),
+            };
+            let relative_frame = RelativeFrame {
+                offset_main,
+                length_main: item.length_main,
+                offset_cross: offset_cross
+                    + item
+                        .align_cross
+                        .offset(column_length_cross - item_length_cross),
+                length_cross: item_length_cross,
+            };
+            offset_main += item.length_main + gap_length_main;
+
+            let number_of_child_keys = constraint_node.child_keys.len();
+            let frame = relative_frame.to_frame(direction);
+            let frame_key = context.insert_frame(
+                constraint_key,
+                frame,
+                parent_frame_key,
+                number_of_child_keys,
+            );
+
+            solve_children(context, constraint_key, &constraint_node, frame_key, frame);
+
+            context.stop_timer(constraint_key, start);
+        }
+
+        lines.push(LineInfo {
+            child_range,
+            offset_cross,
+            length_cross: column_length_cross,
+        });
+        offset_cross += column_length_cross + gap_length_cross;
+    }
+
+    context.solve_output(parent_constraint_key).lines = lines;
+}
+
+pub(super) fn find_minimizing_length_columns(
+    context: &mut Context,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    parent_content: Content,
+    number_of_columns: usize,
+    direction: Direction,
+    bound_main: Bound,
+    bound_cross: Bound,
+) -> (f64, f64) {
+    let max_length_main = bound_main.max_length();
+    let max_length_cross = bound_cross.max_length();
+    let number_of_columns = number_of_columns.max(1);
+    let gap_length_main = exact_gap(parent_content.gap_main, max_length_main);
+    let gap_length_cross = exact_gap(parent_content.gap_cross, max_length_cross);
+    let column_length_cross =
+        column_length_cross(max_length_cross, number_of_columns, gap_length_cross);
+
+    let items = measure_items(
+        context,
+        constraint_keys,
+        direction,
+        parent_content.align_cross,
+        max_length_main,
+        column_length_cross,
+    );
+    let length_main = balance_columns(&items, number_of_columns, gap_length_main)
+        .into_iter()
+        .map(|child_range| column_length_main(&items[child_range], gap_length_main))
+        .fold(0., f64::max);
+    let length_cross = match max_length_cross.is_finite() {
+        true => max_length_cross,
+        false => {
+            let widest_length_cross = items
+                .iter()
+                .map(|item| item.length_cross)
+                .fold(0., f64::max);
+            widest_length_cross * number_of_columns as f64
+                + gap_length_cross * number_of_columns.saturating_sub(1) as f64
+        }
+    };
+
+    (
+        length_main.min(max_length_main),
+        length_cross.min(max_length_cross),
+    )
+}
+
+fn column_length_cross(length_cross: f64, number_of_columns: usize, gap_length_cross: f64) -> f64 {
+    let total_gap_length_cross = gap_length_cross * number_of_columns.saturating_sub(1) as f64;
+    ((length_cross - total_gap_length_cross) / number_of_columns as f64).max(0.)
+}
+
+fn column_length_main(column_items: &[FlowItem], gap_length_main: f64) -> f64 {
+    let number_of_gaps = column_items.len().saturating_sub(1);
+    column_items
+        .iter()
+        .map(|item| item.length_main)
+        .sum::<f64>()
+        + gap_length_main * number_of_gaps as f64
+}
+
+fn balance_columns(
+    items: &[FlowItem],
+    number_of_columns: usize,
+    gap_length_main: f64,
+) -> Vec<Range<usize>> {
+    let number_of_items = items.len();
+    let number_of_columns = number_of_columns.min(number_of_items);
+    if number_of_columns == 0 {
+        return vec![];
+    };
+
+    let prefix_lengths = std::iter::once(0.)
+        .chain(items.iter().scan(0., |prefix_length, item| {
+            *prefix_length += item.length_main;
+            Some(*prefix_length)
+        }))
+        .collect::<Vec<_>>();
+    let cost = |start: usize, end: usize| {
+        let number_of_gaps = (end - start).saturating_sub(1);
+        prefix_lengths[end] - prefix_lengths[start] + gap_length_main * number_of_gaps as f64
+    };
+
+    let mut tallest = vec![vec![f64::INFINITY; number_of_items + 1]; number_of_columns + 1];
+    let mut splits = vec![vec![0; number_of_items + 1]; number_of_columns + 1];
+    tallest[0][0] = 0.;
+
+    for k in 1..=number_of_columns {
+        for i in k..=number_of_items {
+            for j in (k - 1)..i {
+                let candidate = tallest[k - 1][j].max(cost(j, i));
+                if candidate < tallest[k][i] {
+                    tallest[k][i] = candidate;
+                    splits[k][i] = j;
+                };
+            }
+        }
+    }
+
+    let mut ranges = Vec::with_capacity(number_of_columns);
+    let mut end = number_of_items;
+    for k in (1..=number_of_columns).rev() {
+        let start = splits[k][end];
+        ranges.push(start..end);
+        end = start;
+    }
+    ranges.reverse();
+    ranges
+}
@@ -0,0 +1,381 @@
+use std::ops::Range;
+
+use indexmap::IndexSet;
+
+use crate::{
+    exact_gap,
+    solver::{bound_length, flex, iter, minimize, solve_children, Bound, Context, FlexItem},
+    Align, ConstraintKey, Content, Direction, FillType, FrameKey, LayoutMode, LineInfo,
+    RelativeBounds, RelativeFill, RelativeFrame, ResolvedFillType,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct FlowItem {
+    pub(super) length_main: f64,
+    pub(super) length_cross: f64,
+    pub(super) scale_main: usize,
+    pub(super) relative_fill: RelativeFill,
+    pub(super) relative_bounds: RelativeBounds,
+    pub(super) align_cross: Align,
+    pub(super) is_stretching: bool,
+}
+
+pub(super) fn solve_child_keys_flow(
+    context: &mut Context,
+    parent_constraint_key: ConstraintKey,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    parent_frame_key: FrameKey,
+    relative_content_frame: RelativeFrame,
+    parent_content: Content,
+) {
+    let constraint_tree = context.constraint_tree;
+    let direction = parent_content.direction;
+    let gap_length_main = exact_gap(parent_content.gap_main, relative_content_frame.length_main);
+
+    let items = measure_items(
+        context,
+        constraint_keys,
+        direction,
+        parent_content.align_cross,
+        relative_content_frame.length_main,
+        relative_content_frame.length_cross,
+    );
+    let ranges = break_lines(&items, relative_content_frame.length_main, gap_length_main);
+    let number_of_lines = ranges.len();
+
+    let total_length_cross = ranges
+        .iter()
+        .map(|child_range| line_length_cross(&items[child_range.clone()]))
+        .sum::<f64>();
+    let number_of_line_gaps = number_of_lines.saturating_sub(1);
+    let gap_length_cross = match parent_content.gap_cross {
+        Some(FillType::Scale(1..)) if number_of_line_gaps > 0 => {
+            ((relative_content_frame.length_cross - total_length_cross)
+                / number_of_line_gaps as f64)
+                .max(0.)
+        }
+        gap_cross => exact_gap(gap_cross, relative_content_frame.length_cross),
+    };
+
+    let mut lines = Vec::with_capacity(number_of_lines);
+    let mut free_length_main = None::<f64>;
+    let mut offset_cross = relative_content_frame.offset_cross;
+    let mut children = iter(constraint_tree, constraint_keys);
+
+    for (line_index, child_range) in ranges.into_iter().enumerate() {
+        let mut line_items = items[child_range.clone()].to_vec();
+        if parent_content.mode == LayoutMode::Wrap {
+            grow_line(
+                &mut line_items,
+                relative_content_frame.length_main,
+                gap_length_main,
+                context.is_deterministic,
+            );
+        };
+        let length_cross = line_length_cross(&line_items);
+        let used_length_main = line_length_main(&line_items, gap_length_main);
+        let line_free_length_main = relative_content_frame.length_main - used_length_main;
+        free_length_main = Some(
+            free_length_main.map_or(line_free_length_main, |free_length_main| {
+                free_length_main.min(line_free_length_main)
+            }),
+        );
+
+        let align_main = match number_of_lines > 1 && line_index + 1 == number_of_lines {
+            true => parent_content
+                .align_last_line
+                .unwrap_or(parent_content.align_main),
+            false => parent_content.align_main,
+        };
+        let (leading_length_main, spacing_length_main) = align_main.distribute(
+            relative_content_frame.length_main - used_length_main,
+            line_items.len(),
+        );
+        let mut offset_main = relative_content_frame.offset_main + leading_length_main;
+
+        for item in &line_items {
+            let (constraint_key, constraint_node) = children.next().unwrap();
+            let start = context.start_timer();
+
+            let bounded_length_cross = bound_length(
+                item.relative_fill.cross,
+                length_cross,
+                item.align_cross == Align::Stretch,
+            );
+            let item_length_cross = match (bounded_length_cross, item.is_stretching) {
+                (Some(bounded_length_cross), _) => bounded_length_cross,
+                (None, true) => length_cross,
+                (None, false) => item.length_cross,
+            };
+            let item_offset_cross = match item.align_cross {
+                Align::FirstBaseline | Align::LastBaseline => 0.,
+                align_cross => align_cross.offset(length_cross - item_length_cross),
+            };
+
+            let relative_frame = RelativeFrame {
+                offset_main,
+                length_main: item.length_main,
+                offset_cross: offset_cross + item_offset_cross,
+                length_cross: item_length_cross,
+            };
+            offset_main += item.length_main + gap_length_main + spacing_length_main;
+
+            let number_of_child_keys = constraint_node.child_keys.len();
+            let frame = relative_frame.to_frame(direction);
+            let frame_key = context.insert_frame(
+                constraint_key,
+                frame,
+                parent_frame_key,
+                number_of_child_keys,
+            );
+
+            solve_children(context, constraint_key, &constraint_node, frame_key, frame);
+
+            context.stop_timer(constraint_key, start);
+        }
+
+        lines.push(LineInfo {
+            child_range,
+            offset_cross,
+            length_cross,
+        });
+        offset_cross += length_cross + gap_length_cross;
+    }
+
+    let solve_output = context.solve_output(parent_constraint_key);
+    if let Some(free_length_main) = free_length_main {
+        solve_output.free_space = Some(free_length_main);
+    };
+    solve_output.lines = lines;
+}
+
+pub(super) fn find_minimizing_length_flow(
+    context: &mut Context,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    parent_content: Content,
+    direction: Direction,
+    bound_main: Bound,
+    bound_cross: Bound,
+) -> (f64, f64) {
+    let max_length_main = bound_main.max_length();
+    let max_length_cross = bound_cross.max_length();
+    let gap_length_main = exact_gap(parent_content.gap_main, max_length_main);
+
+    let items = measure_items(
+        context,
+        constraint_keys,
+        direction,
+        parent_content.align_cross,
+        max_length_main,
+        max_length_cross,
+    );
+
+    let ranges = break_lines(&items, max_length_main, gap_length_main);
+    let gap_length_cross = exact_gap(parent_content.gap_cross, max_length_cross)
+        * ranges.len().saturating_sub(1) as f64;
+
+    let (length_main, length_cross) = ranges
+        .into_iter()
+        .map(|child_range| {
+            let line_items = &items[child_range];
+            (
+                line_length_main(line_items, gap_length_main),
+                line_length_cross(line_items),
+            )
+        })
+        .fold(
+            (0., 0.),
+            |(length_main, length_cross), (line_main, line_cross)| {
+                (f64::max(length_main, line_main), length_cross + line_cross)
+            },
+        );
+
+    (
+        length_main.min(max_length_main),
+        (length_cross + gap_length_cross).min(max_length_cross),
+    )
+}
+
+pub(super) fn measure_items(
+    context: &mut Context,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    direction: Direction,
+    align_cross: Align,
+    max_length_main: f64,
+    max_length_cross: f64,
+) -> Vec<FlowItem> {
+    let constraint_tree = context.constraint_tree;
+
+    iter(constraint_tree, constraint_keys)
+        .map(|(constraint_key, constraint_node)| {
+            let constraint = context.get_constraint(constraint_key);
+            let unresolved_fill = constraint.fill.to_relative_fill(direction);
+            let relative_fill = unresolved_fill.resolve(max_length_main, max_length_cross);
+            let relative_padding = constraint
+                .content
+                .resolve_insets(
+                    context.content_frame.length_x,
+                    context.content_frame.length_y,
+                )
+                .to_relative_padding(direction);
+            let relative_bounds = constraint.bounds.to_relative_bounds(direction);
+            let align_cross = constraint.align_self.unwrap_or(align_cross);
+
+            let bound_main = match relative_fill.main {
+                ResolvedFillType::Exact(exact_main) => {
+                    Bound::Exactly(exact_main.min(max_length_main))
+                }
+                _ => Bound::AtMost(max_length_main),
+            };
+            let bound_cross = match relative_fill.cross {
+                ResolvedFillType::Exact(exact_cross) => {
+                    Bound::Exactly(exact_cross.min(max_length_cross))
+                }
+                _ => Bound::AtMost(max_length_cross),
+            };
+            let is_minimizing = relative_fill.main == ResolvedFillType::Minimize
+                || relative_fill.cross == ResolvedFillType::Minimize;
+            let (minimizing_length_main, minimizing_length_cross) = match is_minimizing {
+                true => minimize(
+                    context,
+                    constraint_key,
+                    &constraint_node,
+                    direction,
+                    bound_main,
+                    bound_cross,
+                ),
+                false => (0., 0.),
+            };
+
+            let length_main = match relative_fill.main {
+                ResolvedFillType::Exact(..) => bound_main.max_length(),
+                ResolvedFillType::Scale(..) => (relative_padding.main_start
+                    + relative_padding.main_end)
+                    .max(relative_bounds.min_main)
+                    .max(constraint.basis.unwrap_or_default()),
+                ResolvedFillType::Minimize => minimizing_length_main,
+            };
+            let length_cross = match relative_fill.cross {
+                ResolvedFillType::Exact(..) => bound_cross.max_length(),
+                ResolvedFillType::Scale(..) => (relative_padding.cross_start
+                    + relative_padding.cross_end)
+                    .max(relative_bounds.min_cross),
+                ResolvedFillType::Minimize => minimizing_length_cross,
+            };
+
+            let scale_main = match relative_fill.main {
+                ResolvedFillType::Scale(scale_main) => scale_main,
+                _ => 0,
+            };
+
+            FlowItem {
+                length_main,
+                length_cross,
+                scale_main,
+                relative_fill: unresolved_fill,
+                relative_bounds,
+                align_cross,
+                is_stretching: match relative_fill.cross {
+                    ResolvedFillType::Scale(1..) => true,
+                    ResolvedFillType::Minimize => align_cross == Align::Stretch,
+                    _ => false,
+                },
+            }
+        })
+        .collect()
+}
+
+fn break_lines(
+    items: &[FlowItem],
+    max_length_main: f64,
+    gap_length_main: f64,
+) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut line_start = 0;
+    let mut used_length_main = 0.;
+
+    for (index, item) in items.iter().enumerate() {
+        let is_line_empty = index == line_start;
+        let next_length_main = match is_line_empty {
+            true => item.length_main,
+            false => used_length_main + gap_length_main + item.length_main,
+        };
+
+        if !is_line_empty && next_length_main > max_length_main {
+            ranges.push(line_start..index);
+            line_start = index;
+            used_length_main = item.length_main;
+        } else {
+            used_length_main = next_length_main;
+        };
+    }
+
+    if line_start < items.len() {
+        ranges.push(line_start..items.len());
+    };
+
+    ranges
+}
+
+fn grow_line(
+    line_items: &mut [FlowItem],
+    length_main: f64,
+    gap_length_main: f64,
+    is_deterministic: bool,
+) {
+    let free_length_main = length_main - line_length_main(line_items, gap_length_main);
+    let is_scaling = free_length_main > 0. && line_items.iter().any(|item| item.scale_main > 0);
+
+    let flex_items = line_items
+        .iter()
+        .map(|item| match (item.relative_fill.main, is_scaling) {
+            (FillType::Scale(1..), true) => Some(FlexItem {
+                weight: item.scale_main as f64,
+                basis: item.length_main,
+                min: item.relative_bounds.min_main,
+                max: item.relative_bounds.max_main,
+            }),
+            (FillType::Bounded { min, max, .. }, false) => Some(FlexItem {
+                weight: 1.,
+                basis: item.length_main,
+                min: min.max(item.relative_bounds.min_main),
+                max: Some(item.relative_bounds.clamp_main(max).max(min)),
+            }),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let bases_length_main = flex_items
+        .iter()
+        .flatten()
+        .map(|flex_item| flex_item.basis)
+        .sum::<f64>();
+    let mut lengths_main = line_items
+        .iter()
+        .map(|item| Some(item.length_main))
+        .collect::<Vec<_>>();
+
+    flex(
+        &flex_items,
+        &mut lengths_main,
+        free_length_main + bases_length_main,
+        0.,
+        !is_scaling,
+        is_deterministic,
+    );
+    for (item, length_main) in line_items.iter_mut().zip(lengths_main) {
+        item.length_main = length_main.unwrap_or_default();
+    }
+}
+
+fn line_length_main(line_items: &[FlowItem], gap_length_main: f64) -> f64 {
+    let number_of_gaps = line_items.len().saturating_sub(1);
+    line_items.iter().map(|item| item.length_main).sum::<f64>()
+        + gap_length_main * number_of_gaps as f64
+}
+
+fn line_length_cross(line_items: &[FlowItem]) -> f64 {
+    line_items
+        .iter()
+        .map(|item| item.length_cross)
+        .fold(0., f64::max)
+}
@@ -0,0 +1,389 @@
+use std::ops::Range;
+
+use indexmap::IndexSet;
+
+use crate::{
+    exact_gap,
+    solver::{
+        bound_length, flex, iter, layout_children, relative_lengths, solve_children, Bound,
+        Context, FlexItem,
+    },
+    Align, ConstraintKey, Content, Direction, FillType, Frame, FrameKey, Grid, RelativeFrame,
+    ResolvedFillType,
+};
+
+struct GridCell {
+    columns: Range<usize>,
+    rows: Range<usize>,
+}
+
+pub(super) fn solve_child_keys_grid(
+    context: &mut Context,
+    parent_constraint_key: ConstraintKey,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    parent_frame_key: FrameKey,
+    relative_content_frame: RelativeFrame,
+    parent_content: Content,
+) {
+    let constraint_tree = context.constraint_tree;
+    let direction = parent_content.direction;
+    let content_frame = relative_content_frame.to_frame(direction);
+    let (gap_length_x, gap_length_y) = exact_gaps(
+        parent_content,
+        relative_content_frame.length_main,
+        relative_content_frame.length_cross,
+    );
+
+    let children = layout_children(
+        context,
+        constraint_keys,
+        direction,
+        content_frame.length_x,
+        content_frame.length_y,
+    );
+    let (columns, rows, cells) = place_children(context, parent_constraint_key, constraint_keys);
+    let column_lengths = track_lengths(
+        &columns,
+        cells
+            .iter()
+            .zip(&children)
+            .map(|(cell, child)| (cell.columns.clone(), child.length_x)),
+        content_frame.length_x,
+        gap_length_x,
+        false,
+        context.is_deterministic,
+    );
+    let row_lengths = track_lengths(
+        &rows,
+        cells
+            .iter()
+            .zip(&children)
+            .map(|(cell, child)| (cell.rows.clone(), child.length_y)),
+        content_frame.length_y,
+        gap_length_y,
+        false,
+        context.is_deterministic,
+    );
+    let column_offsets = track_offsets(&column_lengths, content_frame.offset_x, gap_length_x);
+    let row_offsets = track_offsets(&row_lengths, content_frame.offset_y, gap_length_y);
+
+    for (((constraint_key, constraint_node), child), cell) in iter(constraint_tree, constraint_keys)
+        .zip(&children)
+        .zip(&cells)
+    {
+        let start = context.start_timer();
+        let relative_fill = child.constraint.fill.to_relative_fill(direction);
+        let (fill_x, fill_y) = relative_lengths(direction, relative_fill.main, relative_fill.cross);
+        let (align_x, align_y) = relative_lengths(
+            direction,
+            child
+                .constraint
+                .justify_self
+                .unwrap_or(parent_content.align_main),
+            child
+                .constraint
+                .align_self
+                .unwrap_or(parent_content.align_cross),
+        );
+
+        let cell_length_x = span_length(&column_lengths, cell.columns.clone(), gap_length_x);
+        let cell_length_y = span_length(&row_lengths, cell.rows.clone(), gap_length_y);
+        let length_x = cell_length(fill_x, child.length_x, cell_length_x, align_x);
+        let length_y = cell_length(fill_y, child.length_y, cell_length_y, align_y);
+        let frame = Frame {
+            offset_x: column_offsets[cell.columns.start] + align_x.offset(cell_length_x - length_x),
+            length_x,
+            offset_y: row_offsets[cell.rows.start] + align_y.offset(cell_length_y - length_y),
+            length_y,
+        };
+
+        let number_of_child_keys = constraint_node.child_keys.len();
+        let frame_key = context.insert_frame(
+            constraint_key,
+            frame,
+            parent_frame_key,
+            number_of_child_keys,
+        );
+
+        solve_children(context, constraint_key, &constraint_node, frame_key, frame);
+
+        context.stop_timer(constraint_key, start);
+    }
+}
+
+pub(super) fn find_minimizing_length_grid(
+    context: &mut Context,
+    parent_constraint_key: ConstraintKey,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    parent_content: Content,
+    direction: Direction,
+    bound_main: Bound,
+    bound_cross: Bound,
+) -> (f64, f64) {
+    let max_length_main = bound_main.max_length();
+    let max_length_cross = bound_cross.max_length();
+    let (max_length_x, max_length_y) =
+        relative_lengths(direction, max_length_main, max_length_cross);
+    let (gap_length_x, gap_length_y) =
+        exact_gaps(parent_content, max_length_main, max_length_cross);
+
+    let children = layout_children(
+        context,
+        constraint_keys,
+        direction,
+        max_length_x,
+        max_length_y,
+    );
+    let (columns, rows, cells) = place_children(context, parent_constraint_key, constraint_keys);
+    let column_lengths = track_lengths(
+        &columns,
+        cells
+            .iter()
+            .zip(&children)
+            .map(|(cell, child)| (cell.columns.clone(), child.length_x)),
+        max_length_x,
+        gap_length_x,
+        true,
+        context.is_deterministic,
+    );
+    let row_lengths = track_lengths(
+        &rows,
+        cells
+            .iter()
+            .zip(&children)
+            .map(|(cell, child)| (cell.rows.clone(), child.length_y)),
+        max_length_y,
+        gap_length_y,
+        true,
+        context.is_deterministic,
+    );
+
+    let length_x = total_length(&column_lengths, gap_length_x);
+    let length_y = total_length(&row_lengths, gap_length_y);
+    let (length_main, length_cross) = relative_lengths(direction, length_x, length_y);
+
+    (
+        length_main.min(max_length_main),
+        length_cross.min(max_length_cross),
+    )
+}
+
+fn exact_gaps(parent_content: Content, length_main: f64, length_cross: f64) -> (f64, f64) {
+    relative_lengths(
+        parent_content.direction,
+        exact_gap(parent_content.gap_main, length_main),
+        exact_gap(parent_content.gap_cross, length_cross),
+    )
+}
+
+fn place_children(
+    context: &Context,
+    parent_constraint_key: ConstraintKey,
+    constraint_keys: &IndexSet<ConstraintKey>,
+) -> (Vec<FillType>, Vec<FillType>, Vec<GridCell>) {
+    let default_grid = Grid::default();
+    let grid = context
+        .grids
+        .get(&parent_constraint_key)
+        .unwrap_or(&default_grid);
+    let number_of_template_columns = grid.areas.iter().map(Vec::len).max().unwrap_or_default();
+    let columns = match (grid.columns.is_empty(), number_of_template_columns) {
+        (true, 0) => vec![FillType::Scale(1)],
+        _ => {
+            let mut columns = grid.columns.clone();
+            columns.resize(
+                columns.len().max(number_of_template_columns),
+                FillType::Minimize,
+            );
+            columns
+        }
+    };
+
+    let mut occupied = vec![vec![false; columns.len()]; grid.areas.len()];
+    let mut cells = constraint_keys
+        .iter()
+        .map(|constraint_key| {
+            let area = context.grid_areas.get(constraint_key)?;
+            let (rows, columns) = grid.area(area)?;
+            let cell = GridCell { columns, rows };
+            for row in cell.rows.clone() {
+                occupied[row][cell.columns.clone()].fill(true);
+            }
+            Some(cell)
+        })
+        .collect::<Vec<_>>();
+
+    let mut cursor = 0;
+    for (constraint_key, cell) in constraint_keys.iter().zip(&mut cells) {
+        if cell.is_some() {
+            continue;
+        };
+        let (column_span, row_span) = context
+            .grid_spans
+            .get(constraint_key)
+            .map_or((1, 1), |&(column_span, row_span)| {
+                (column_span.min(columns.len()), row_span)
+            });
+        let (row, column) = loop {
+            let (row, column) = (cursor / columns.len(), cursor % columns.len());
+            cursor += 1;
+            if column + column_span > columns.len() {
+                continue;
+            };
+            while occupied.len() < row + row_span {
+                occupied.push(vec![false; columns.len()]);
+            }
+            let is_free = occupied[row..row + row_span]
+                .iter()
+                .all(|occupied| !occupied[column..column + column_span].contains(&true));
+            if is_free {
+                break (row, column);
+            };
+        };
+        for occupied in &mut occupied[row..row + row_span] {
+            occupied[column..column + column_span].fill(true);
+        }
+        *cell = Some(GridCell {
+            columns: column..column + column_span,
+            rows: row..row + row_span,
+        });
+    }
+
+    let mut rows = grid.rows.clone();
+    rows.resize(rows.len().max(occupied.len()), FillType::Minimize);
+    (
+        columns,
+        rows,
+        cells.into_iter().map(Option::unwrap).collect(),
+    )
+}
+
+fn track_lengths(
+    tracks: &[FillType],
+    items: impl Iterator<Item = (Range<usize>, f64)>,
+    available_length: f64,
+    gap_length: f64,
+    is_minimizing: bool,
+    is_deterministic: bool,
+) -> Vec<f64> {
+    let unresolved_tracks = tracks;
+    let tracks = tracks
+        .iter()
+        .map(|track| track.resolve(available_length))
+        .collect::<Vec<_>>();
+    let mut lengths = tracks
+        .iter()
+        .map(|&track| match track {
+            ResolvedFillType::Exact(exact_length) => exact_length,
+            ResolvedFillType::Scale(..) | ResolvedFillType::Minimize => 0.,
+        })
+        .collect::<Vec<_>>();
+
+    let mut items = items.collect::<Vec<_>>();
+    items.sort_by_key(|(span, _)| span.len());
+
+    for (span, length) in items {
+        let is_growable = |track: ResolvedFillType| match track {
+            ResolvedFillType::Minimize => true,
+            ResolvedFillType::Scale(1..) => is_minimizing,
+            ResolvedFillType::Exact(..) | ResolvedFillType::Scale(0) => false,
+        };
+        let growable_indices = span
+            .clone()
+            .filter(|&index| is_growable(tracks[index]))
+            .collect::<Vec<_>>();
+        let deficit = length - span_length(&lengths, span, gap_length);
+        if deficit > 0. && !growable_indices.is_empty() {
+            let growth = deficit / growable_indices.len() as f64;
+            for index in growable_indices {
+                lengths[index] += growth;
+            }
+        };
+    }
+
+    let total_scale = tracks
+        .iter()
+        .map(|&track| match track {
+            ResolvedFillType::Scale(scale) => scale,
+            _ => 0,
+        })
+        .sum::<usize>();
+    let remaining_length = available_length - total_length(&lengths, gap_length);
+    if !is_minimizing && (total_scale == 0 || remaining_length < 0.) {
+        let flex_items = unresolved_tracks
+            .iter()
+            .zip(&lengths)
+            .map(|(&track, &length)| match track {
+                FillType::Bounded { min, max, .. } => Some(FlexItem {
+                    weight: 1.,
+                    basis: length,
+                    min,
+                    max: Some(max.max(min)),
+                }),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let bases_length = flex_items
+            .iter()
+            .flatten()
+            .map(|flex_item| flex_item.basis)
+            .sum::<f64>();
+        let mut flexed_lengths = lengths.iter().copied().map(Some).collect::<Vec<_>>();
+
+        flex(
+            &flex_items,
+            &mut flexed_lengths,
+            remaining_length + bases_length,
+            0.,
+            true,
+            is_deterministic,
+        );
+        for (length, flexed_length) in lengths.iter_mut().zip(flexed_lengths) {
+            *length = flexed_length.unwrap_or_default();
+        }
+    };
+
+    if !is_minimizing && total_scale > 0 {
+        let remaining_length = (available_length - total_length(&lengths, gap_length)).max(0.);
+        for (&track, length) in tracks.iter().zip(&mut lengths) {
+            if let ResolvedFillType::Scale(scale) = track {
+                *length = remaining_length * scale as f64 / total_scale as f64;
+            };
+        }
+    };
+
+    lengths
+}
+
+fn track_offsets(lengths: &[f64], offset: f64, gap_length: f64) -> Vec<f64> {
+    lengths
+        .iter()
+        .scan(offset, |offset, &length| {
+            let track_offset = *offset;
+            *offset += length + gap_length;
+            Some(track_offset)
+        })
+        .collect()
+}
+
+fn span_length(lengths: &[f64], span: Range<usize>, gap_length: f64) -> f64 {
+    total_length(&lengths[span], gap_length)
+}
+
+fn total_length(lengths: &[f64], gap_length: f64) -> f64 {
+    lengths.iter().sum::<f64>() + gap_length * lengths.len().saturating_sub(1) as f64
+}
+
+fn cell_length(fill: FillType, length: f64, cell_length: f64, align: Align) -> f64 {
+    if let Some(bounded_length) = bound_length(fill, cell_length, align == Align::Stretch) {
+        return bounded_length;
+    };
+
+    match fill.resolve(cell_length) {
+        ResolvedFillType::Exact(exact_length) => exact_length.min(cell_length),
+        ResolvedFillType::Scale(0) => 0.,
+        ResolvedFillType::Scale(_) => cell_length,
+        ResolvedFillType::Minimize if align == Align::Stretch => cell_length,
+        ResolvedFillType::Minimize => length.min(cell_length),
+    }
+}
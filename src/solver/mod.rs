@@ -1,21 +1,541 @@
-use std::collections::BTreeMap;
+mod collapse;
+mod columns;
+mod flow;
+mod grid;
+mod scroll;
+mod snap;
+mod split;
+mod table;
+mod transform;
+
+use std::{
+    any::{Any, TypeId},
+    cmp::Reverse,
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use cherrytree::{Node, Tree};
 use indexmap::IndexSet;
 
 use crate::{
-    Align, Constraint, ConstraintKey, Content, Direction, FillType, Frame, FrameKey, RelativeFrame,
-    RelativePadding,
+    baseline::{Baseline, BaselineProvider},
+    exact_gap,
+    hooks::{run_hook, run_on_layout, Hook, OnLayout},
+    island::Island,
+    label::describe,
+    measure::Measure,
+    scroll::Scroll,
+    Align, Constraint, ConstraintKey, Content, CustomLayout, Direction, Display, FillType, Frame,
+    FrameI32, FrameKey, Grid, LayoutChild, LayoutDirection, LayoutId, LayoutMode, LineInfo,
+    Overflow, Phase, Position, Precedence, RelativeFrame, RelativePadding, ResolvedFillType,
+    RoundingPolicy, Transform,
+};
+
+use self::{
+    collapse::{collapse_child_keys, solve_collapsed_child_keys},
+    columns::{find_minimizing_length_columns, solve_child_keys_columns},
+    flow::{find_minimizing_length_flow, solve_child_keys_flow},
+    grid::{find_minimizing_length_grid, solve_child_keys_grid},
+    scroll::{apply_scroll_anchors, capture_scroll_anchors},
+    snap::{snap, snap_subtree},
+    split::solve_child_keys_split,
+    table::{find_minimizing_length_table, solve_child_keys_table},
+    transform::{compose, compose_subtree},
 };
 
-pub(super) fn solve(
+pub(crate) use self::split::divider_positions;
+
+pub(crate) fn resolve_constraint(
     constraint_tree: &Tree<ConstraintKey, Constraint>,
-    frame_tree: &mut Tree<FrameKey, Frame>,
-    key_map: &mut BTreeMap<ConstraintKey, FrameKey>,
-    length_x: f64,
-    length_y: f64,
-) {
+    parent_keys: &BTreeMap<ConstraintKey, ConstraintKey>,
+    islands: &BTreeMap<ConstraintKey, Island>,
+    constraint_key: ConstraintKey,
+) -> Constraint {
+    let mut constraint = *constraint_tree.get(constraint_key).unwrap().value;
+
+    let parent_content = parent_keys
+        .get(&constraint_key)
+        .and_then(|&parent_constraint_key| constraint_tree.get(parent_constraint_key))
+        .map(|parent_constraint_node| parent_constraint_node.value.content);
+    if let Some(child_defaults) = parent_content.and_then(|content| content.default_child) {
+        constraint = child_defaults.apply(constraint);
+    };
+    if let (Some(parent_content), Some(align_self)) = (parent_content, constraint.align_self) {
+        if align_self != Align::Stretch {
+            constraint.fill = constraint.fill.unstretch_cross(parent_content.direction);
+        };
+    };
+
+    if let Some(island) = islands.get(&constraint_key) {
+        constraint.fill = island.fill();
+    };
+
+    constraint
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Bound {
+    AtMost(f64),
+    Exactly(f64),
+}
+
+impl Bound {
+    fn max_length(self) -> f64 {
+        match self {
+            Self::AtMost(length) | Self::Exactly(length) => length,
+        }
+    }
+
+    fn shrink(self, length: f64) -> Self {
+        match self {
+            Self::AtMost(max_length) => Self::AtMost((max_length - length).max(0.)),
+            Self::Exactly(exact_length) => Self::Exactly((exact_length - length).max(0.)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SolveOutput {
+    pub(crate) overflow: Option<(f64, f64)>,
+    pub(crate) free_space: Option<f64>,
+    pub(crate) collapsed: Vec<ConstraintKey>,
+    pub(crate) lines: Vec<LineInfo>,
+    pub(crate) baseline: Option<Baseline>,
+}
+
+pub(super) struct Context<'a> {
+    pub(super) constraint_tree: &'a Tree<ConstraintKey, Constraint>,
+    pub(super) frame_tree: &'a mut Tree<FrameKey, Frame>,
+    pub(super) key_map: &'a mut BTreeMap<ConstraintKey, FrameKey>,
+    pub(super) profile: Option<&'a mut BTreeMap<ConstraintKey, Duration>>,
+    pub(super) hooks: &'a BTreeMap<(ConstraintKey, Phase), Hook>,
+    pub(super) on_layouts: &'a BTreeMap<ConstraintKey, OnLayout>,
+    pub(super) last_frames: &'a mut BTreeMap<ConstraintKey, Frame>,
+    pub(super) layouts: &'a BTreeMap<ConstraintKey, Arc<dyn CustomLayout>>,
+    pub(super) registry: &'a BTreeMap<LayoutId, (String, Arc<dyn CustomLayout>)>,
+    pub(super) scale_factor: f64,
+    pub(super) rounding_policy: RoundingPolicy,
+    pub(super) layout_direction: LayoutDirection,
+    pub(super) baseline_grid: Option<f64>,
+    pub(super) is_deterministic: bool,
+    pub(super) physical_frames: &'a mut BTreeMap<ConstraintKey, Frame>,
+    pub(super) frames_i32: &'a mut BTreeMap<ConstraintKey, FrameI32>,
+    pub(super) transforms: &'a mut BTreeMap<ConstraintKey, Transform>,
+    pub(super) islands: &'a BTreeMap<ConstraintKey, Island>,
+    pub(super) parent_keys: &'a BTreeMap<ConstraintKey, ConstraintKey>,
+    pub(super) labels: &'a BTreeMap<ConstraintKey, String>,
+    pub(super) solve_outputs: &'a mut BTreeMap<ConstraintKey, SolveOutput>,
+    pub(super) splits: &'a BTreeMap<ConstraintKey, Vec<f64>>,
+    pub(super) grids: &'a BTreeMap<ConstraintKey, Grid>,
+    pub(super) grid_areas: &'a BTreeMap<ConstraintKey, String>,
+    pub(super) grid_spans: &'a BTreeMap<ConstraintKey, (usize, usize)>,
+    pub(super) generations: &'a mut BTreeMap<ConstraintKey, (u64, Frame)>,
+    pub(super) baseline_providers: &'a BTreeMap<ConstraintKey, BaselineProvider>,
+    pub(super) measures: &'a BTreeMap<ConstraintKey, (TypeId, Measure)>,
+    pub(super) measure_context: Option<&'a mut dyn Any>,
+    pub(super) scrolls: &'a mut BTreeMap<ConstraintKey, Scroll>,
+    pub(super) content_frame: Frame,
+}
+
+impl Context<'_> {
+    fn start_timer(&self) -> Option<Instant> {
+        self.profile.as_ref().map(|_| Instant::now())
+    }
+
+    fn stop_timer(&mut self, constraint_key: ConstraintKey, start: Option<Instant>) {
+        if let (Some(profile), Some(start)) = (self.profile.as_mut(), start) {
+            *profile.entry(constraint_key).or_default() += start.elapsed();
+        };
+    }
+
+    fn run_hook(&self, constraint_key: ConstraintKey, phase: Phase, frame: Option<Frame>) {
+        run_hook(
+            self.hooks.get(&(constraint_key, phase)),
+            constraint_key,
+            frame,
+        );
+    }
+
+    fn run_on_layout(&mut self, constraint_key: ConstraintKey, frame: Frame) {
+        if let Some(on_layout) = self.on_layouts.get(&constraint_key) {
+            if self.last_frames.insert(constraint_key, frame) != Some(frame) {
+                run_on_layout(on_layout, frame);
+            };
+        };
+    }
+
+    fn insert_frame(
+        &mut self,
+        constraint_key: ConstraintKey,
+        frame: Frame,
+        parent_frame_key: FrameKey,
+        number_of_child_keys: usize,
+    ) -> FrameKey {
+        let constraint = self.get_constraint(constraint_key);
+        let frame = match constraint.is_out_of_flow() {
+            true => frame,
+            false => self.mirror_frame(constraint_key, frame),
+        };
+        let frame = Frame {
+            offset_x: frame.offset_x + constraint.nudge_x,
+            offset_y: frame.offset_y + constraint.nudge_y,
+            ..frame
+        };
+        let frame_key = self
+            .frame_tree
+            .insert_with_capacity(frame, parent_frame_key, number_of_child_keys)
+            .unwrap();
+        self.key_map.insert(constraint_key, frame_key);
+        self.bump_generation(constraint_key, frame);
+        self.run_hook(constraint_key, Phase::Arrange, Some(frame));
+        frame_key
+    }
+
+    fn mirror_frame(&self, constraint_key: ConstraintKey, frame: Frame) -> Frame {
+        let Some(&parent_constraint_key) = self.parent_keys.get(&constraint_key) else {
+            return frame;
+        };
+        let parent_content = self.get_constraint(parent_constraint_key).content;
+        if self
+            .get_layout(parent_constraint_key, parent_content)
+            .is_some()
+        {
+            return frame;
+        };
+
+        let is_right_to_left = self.layout_direction == LayoutDirection::RightToLeft;
+        let is_mirrored_x = match parent_content.direction {
+            Direction::Horizontal => parent_content.reverse != is_right_to_left,
+            Direction::Vertical => is_right_to_left,
+        };
+        let is_mirrored_y =
+            parent_content.direction == Direction::Vertical && parent_content.reverse;
+        if !is_mirrored_x && !is_mirrored_y {
+            return frame;
+        };
+
+        let content_frame = self.content_frame;
+        let mirror = |offset: f64, length: f64, content_offset: f64, content_length: f64| {
+            2. * content_offset + content_length - offset - length
+        };
+
+        Frame {
+            offset_x: match is_mirrored_x {
+                true => mirror(
+                    frame.offset_x,
+                    frame.length_x,
+                    content_frame.offset_x,
+                    content_frame.length_x,
+                ),
+                false => frame.offset_x,
+            },
+            offset_y: match is_mirrored_y {
+                true => mirror(
+                    frame.offset_y,
+                    frame.length_y,
+                    content_frame.offset_y,
+                    content_frame.length_y,
+                ),
+                false => frame.offset_y,
+            },
+            ..frame
+        }
+    }
+
+    fn bump_generation(&mut self, constraint_key: ConstraintKey, frame: Frame) {
+        let (generation, last_frame) = self.generations.entry(constraint_key).or_insert((0, frame));
+        if *last_frame != frame {
+            *generation += 1;
+            *last_frame = frame;
+        };
+    }
+
+    fn get_layout(
+        &self,
+        constraint_key: ConstraintKey,
+        content: Content,
+    ) -> Option<Arc<dyn CustomLayout>> {
+        self.layouts.get(&constraint_key).cloned().or_else(|| {
+            let layout_id = content.layout?;
+            let (_, layout) = self.registry.get(&layout_id)?;
+            Some(layout.clone())
+        })
+    }
+
+    fn get_constraint(&self, constraint_key: ConstraintKey) -> Constraint {
+        resolve_constraint(
+            self.constraint_tree,
+            self.parent_keys,
+            self.islands,
+            constraint_key,
+        )
+        .resolve_logical(self.layout_direction)
+    }
+
+    fn record_baseline(
+        &mut self,
+        constraint_key: ConstraintKey,
+        constraint_node: &Node<'_, ConstraintKey, Constraint>,
+        frame: Frame,
+    ) {
+        let baseline = match self.baseline_providers.get(&constraint_key) {
+            Some(provider) => provider(frame.length_x, frame.length_y),
+            None => {
+                let (child_constraint_keys, _) =
+                    partition_layout_ignored(self.constraint_tree, constraint_node.child_keys);
+                let child_baselines = child_constraint_keys
+                    .iter()
+                    .filter(|&&child_constraint_key| {
+                        let child_constraint = self.get_constraint(child_constraint_key);
+                        !child_constraint.is_out_of_flow()
+                            && child_constraint.display == Display::Flow
+                    })
+                    .filter_map(|child_constraint_key| {
+                        let child_baseline =
+                            self.solve_outputs.get(child_constraint_key)?.baseline?;
+                        let child_frame = self.get_frame(*child_constraint_key);
+                        Some(Baseline {
+                            first: child_frame.offset_y + child_baseline.first,
+                            last: child_frame.offset_y + child_baseline.last,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                match (child_baselines.first(), child_baselines.last()) {
+                    (Some(first_baseline), Some(last_baseline)) => Baseline {
+                        first: first_baseline.first,
+                        last: last_baseline.last,
+                    },
+                    _ => Baseline {
+                        first: frame.length_y,
+                        last: frame.length_y,
+                    },
+                }
+            }
+        };
+        self.solve_output(constraint_key).baseline = Some(baseline);
+    }
+
+    fn solve_output(&mut self, constraint_key: ConstraintKey) -> &mut SolveOutput {
+        self.solve_outputs.entry(constraint_key).or_default()
+    }
+
+    fn shift_frame(&mut self, constraint_key: ConstraintKey, delta_y: f64) {
+        let frame_key = self.key_map[&constraint_key];
+        let frame = self.get_frame(constraint_key);
+        let frame = Frame {
+            offset_y: frame.offset_y + delta_y,
+            ..frame
+        };
+        self.frame_tree.set(frame_key, frame);
+        self.bump_generation(constraint_key, frame);
+    }
+
+    fn parent_transform(&self, constraint_key: ConstraintKey) -> Transform {
+        self.parent_keys
+            .get(&constraint_key)
+            .and_then(|parent_constraint_key| self.transforms.get(parent_constraint_key))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn get_frame(&self, constraint_key: ConstraintKey) -> Frame {
+        let frame_key = *self
+            .key_map
+            .get(&constraint_key)
+            .unwrap_or_else(|| panic!("{} has no frame", self.describe(constraint_key)));
+        *self.frame_tree.get(frame_key).unwrap().value
+    }
+
+    fn describe(&self, constraint_key: ConstraintKey) -> String {
+        describe(self.labels, self.parent_keys, constraint_key)
+    }
+
+    fn world_offset_y(&self, constraint_key: ConstraintKey) -> f64 {
+        let mut offset_y = 0.;
+        let mut current_constraint_key = Some(constraint_key);
+        while let Some(constraint_key) = current_constraint_key {
+            offset_y += self.get_frame(constraint_key).offset_y;
+            current_constraint_key = self.parent_keys.get(&constraint_key).copied();
+        }
+        offset_y
+    }
+
+    fn find_content_frame(&self, constraint_key: ConstraintKey) -> Frame {
+        let frame = self.get_frame(constraint_key);
+        let containing_frame = match self.parent_keys.get(&constraint_key) {
+            Some(&parent_constraint_key) => self.find_content_frame(parent_constraint_key),
+            None => frame,
+        };
+        let content = self.get_constraint(constraint_key).content;
+
+        resolve_content_frame(content, frame, containing_frame).to_frame(content.direction)
+    }
+}
+
+pub(super) fn solve(context: &mut Context, length_x: f64, length_y: f64) {
+    let constraint_keys = context
+        .constraint_tree
+        .root_key()
+        .map(|root_constraint_key| pre_order(context.constraint_tree, root_constraint_key))
+        .unwrap_or_default();
+
+    let scroll_anchors = capture_scroll_anchors(context);
+    context.solve_outputs.clear();
+
+    measure(context, &constraint_keys);
+    arrange(context, length_x, length_y);
+    apply_scroll_anchors(context, scroll_anchors);
+    snap(context);
+    compose(context);
+    finalize(context, &constraint_keys);
+}
+
+pub(super) fn minimize_root(
+    context: &mut Context,
+    max_length_x: f64,
+    max_length_y: f64,
+) -> (f64, f64) {
+    let (root_constraint_key, root_constraint_node) =
+        context.constraint_tree.root_key_value().unwrap();
+    context.content_frame = Frame {
+        length_x: finite_length(max_length_x),
+        length_y: finite_length(max_length_y),
+        ..Default::default()
+    };
+
+    minimize(
+        context,
+        root_constraint_key,
+        &root_constraint_node,
+        Direction::Horizontal,
+        Bound::AtMost(max_length_x),
+        Bound::AtMost(max_length_y),
+    )
+}
+
+pub(super) fn solve_subtree(context: &mut Context, constraint_key: ConstraintKey, frame: Frame) {
+    let constraint_tree = context.constraint_tree;
+    let constraint_node = constraint_tree.get(constraint_key).unwrap();
+    let frame_key = *context
+        .key_map
+        .get(&constraint_key)
+        .unwrap_or_else(|| panic!("{} has no frame", context.describe(constraint_key)));
+    let constraint_keys = pre_order(constraint_tree, constraint_key)
+        .into_iter()
+        .skip(1)
+        .collect::<Vec<_>>();
+
+    let scroll_anchors = capture_scroll_anchors(context);
+
+    context.content_frame = match context.parent_keys.get(&constraint_key) {
+        Some(&parent_constraint_key) => context.find_content_frame(parent_constraint_key),
+        None => frame,
+    };
+    context.frame_tree.set(frame_key, frame);
+    context.bump_generation(constraint_key, frame);
+
+    let child_frame_keys = context
+        .frame_tree
+        .get(frame_key)
+        .unwrap()
+        .child_keys
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+    for child_frame_key in child_frame_keys {
+        context.frame_tree.remove(child_frame_key, None);
+    }
+
+    measure(context, &constraint_keys);
+
+    let start = context.start_timer();
+    solve_children(context, constraint_key, &constraint_node, frame_key, frame);
+    context.stop_timer(constraint_key, start);
+
+    let mut ancestor_constraint_key = context.parent_keys.get(&constraint_key).copied();
+    while let Some(parent_constraint_key) = ancestor_constraint_key {
+        let parent_constraint_node = constraint_tree.get(parent_constraint_key).unwrap();
+        let parent_frame = context.get_frame(parent_constraint_key);
+        context.record_baseline(parent_constraint_key, &parent_constraint_node, parent_frame);
+        ancestor_constraint_key = context.parent_keys.get(&parent_constraint_key).copied();
+    }
+
+    apply_scroll_anchors(context, scroll_anchors);
+    snap_subtree(context, constraint_key);
+    compose_subtree(context, constraint_key);
+    finalize(context, &constraint_keys);
+}
+
+pub(super) fn restore(context: &mut Context, frames: &BTreeMap<ConstraintKey, Frame>) {
+    let constraint_tree = context.constraint_tree;
+    let mut to_visit = constraint_tree
+        .root_key()
+        .map(|root_constraint_key| (root_constraint_key, None))
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    while let Some((constraint_key, parent_frame_key)) = to_visit.pop() {
+        let constraint_node = constraint_tree.get(constraint_key).unwrap();
+        let frame = frames[&constraint_key];
+        let number_of_child_keys = constraint_node.child_keys.len();
+
+        let frame_key = match parent_frame_key {
+            Some(parent_frame_key) => context
+                .frame_tree
+                .insert_with_capacity(frame, parent_frame_key, number_of_child_keys)
+                .unwrap(),
+            None => context
+                .frame_tree
+                .insert_root_with_capacity(frame, number_of_child_keys),
+        };
+        context.key_map.insert(constraint_key, frame_key);
+        context.bump_generation(constraint_key, frame);
+
+        to_visit.extend(
+            constraint_node
+                .child_keys
+                .iter()
+                .rev()
+                .map(|&child_constraint_key| (child_constraint_key, Some(frame_key))),
+        );
+    }
+
+    snap(context);
+    compose(context);
+}
+
+pub(super) fn recompose(context: &mut Context, constraint_key: ConstraintKey) {
+    compose_subtree(context, constraint_key);
+}
+
+fn measure(context: &mut Context, constraint_keys: &[ConstraintKey]) {
+    if context.hooks.is_empty() {
+        return;
+    };
+
+    for &constraint_key in constraint_keys {
+        context.run_hook(constraint_key, Phase::Measure, None);
+    }
+}
+
+fn finalize(context: &mut Context, constraint_keys: &[ConstraintKey]) {
+    if context.hooks.is_empty() && context.on_layouts.is_empty() {
+        return;
+    };
+
+    for &constraint_key in constraint_keys {
+        let frame = context.get_frame(constraint_key);
+        context.run_hook(constraint_key, Phase::Finalize, Some(frame));
+        context.run_on_layout(constraint_key, frame);
+    }
+}
+
+fn arrange(context: &mut Context, length_x: f64, length_y: f64) {
+    let constraint_tree = context.constraint_tree;
     let (root_constraint_key, root_constraint_node) = constraint_tree.root_key_value().unwrap();
+    let start = context.start_timer();
 
     let relative_fill = root_constraint_node
         .value
@@ -42,128 +562,1065 @@ pub(super) fn solve(
     };
 
     let number_of_child_keys = root_constraint_node.child_keys.len();
-    let root_frame_key = frame_tree.insert_root_with_capacity(root_frame, number_of_child_keys);
-    key_map.insert(root_constraint_key, root_frame_key);
+    let root_frame_key = context
+        .frame_tree
+        .insert_root_with_capacity(root_frame, number_of_child_keys);
+    context.content_frame = root_frame;
+    context.key_map.insert(root_constraint_key, root_frame_key);
+    context.bump_generation(root_constraint_key, root_frame);
+    context.run_hook(root_constraint_key, Phase::Arrange, Some(root_frame));
 
-    let relative_padding = root_constraint_node
-        .value
-        .content
-        .padding
-        .to_relative_padding(Direction::Vertical);
-    let root_relative_content_frame =
-        generate_content_frame_relative(relative_padding, length_y, length_x);
-
-    solve_child_keys_relative(
-        constraint_tree,
-        frame_tree,
-        key_map,
-        root_constraint_node.child_keys,
+    solve_children(
+        context,
+        root_constraint_key,
+        &root_constraint_node,
         root_frame_key,
-        root_relative_content_frame,
-        root_constraint_node.value.content,
+        root_frame,
+    );
+
+    context.stop_timer(root_constraint_key, start);
+}
+
+fn solve_children(
+    context: &mut Context,
+    constraint_key: ConstraintKey,
+    constraint_node: &Node<'_, ConstraintKey, Constraint>,
+    frame_key: FrameKey,
+    frame: Frame,
+) {
+    let content = context.get_constraint(constraint_key).content;
+    let relative_frame = frame.to_relative_frame(content.direction);
+    let containing_frame = context.content_frame;
+    let relative_content_frame = resolve_content_frame(content, frame, containing_frame);
+    context.content_frame = relative_content_frame.to_frame(content.direction);
+
+    context
+        .solve_outputs
+        .insert(constraint_key, SolveOutput::default());
+
+    let (constraint_keys, ignored_constraint_keys) =
+        partition_layout_ignored(context.constraint_tree, constraint_node.child_keys);
+
+    match (context.get_layout(constraint_key, content), content.mode) {
+        (Some(layout), _) => arrange_custom(
+            context,
+            layout,
+            &constraint_keys,
+            frame_key,
+            relative_content_frame.to_frame(content.direction),
+            content.direction,
+        ),
+        (None, LayoutMode::Split) => solve_child_keys_split(
+            context,
+            constraint_key,
+            &constraint_keys,
+            frame_key,
+            relative_content_frame,
+            content,
+        ),
+        (None, LayoutMode::Grid) => solve_child_keys_grid(
+            context,
+            constraint_key,
+            &constraint_keys,
+            frame_key,
+            relative_content_frame,
+            content,
+        ),
+        (None, LayoutMode::Table) => solve_child_keys_table(
+            context,
+            &constraint_keys,
+            frame_key,
+            relative_content_frame,
+            content,
+        ),
+        (None, LayoutMode::Stack) => solve_ignored_child_keys(
+            context,
+            &constraint_keys.iter().copied().collect::<Vec<_>>(),
+            frame_key,
+            relative_content_frame,
+            content.direction,
+            Some((content.align_main, content.align_cross)),
+        ),
+        (None, LayoutMode::Columns(number_of_columns)) => solve_child_keys_columns(
+            context,
+            constraint_key,
+            &constraint_keys,
+            frame_key,
+            relative_content_frame,
+            content,
+            number_of_columns,
+        ),
+        (None, LayoutMode::Flow | LayoutMode::Wrap) => solve_child_keys_flow(
+            context,
+            constraint_key,
+            &constraint_keys,
+            frame_key,
+            relative_content_frame,
+            content,
+        ),
+        (None, LayoutMode::Relative) => {
+            let (constraint_keys, collapsed_constraint_keys) = collapse_child_keys(
+                context,
+                constraint_key,
+                constraint_keys,
+                relative_content_frame,
+                content,
+            );
+
+            solve_child_keys_relative(
+                context,
+                constraint_key,
+                &constraint_keys,
+                frame_key,
+                relative_content_frame,
+                content,
+            );
+            solve_collapsed_child_keys(
+                context,
+                &collapsed_constraint_keys,
+                frame_key,
+                relative_content_frame,
+                content,
+            );
+
+            if !constraint_keys.is_empty() {
+                let line = LineInfo {
+                    child_range: 0..constraint_keys.len(),
+                    offset_cross: relative_content_frame.offset_cross,
+                    length_cross: relative_content_frame.length_cross,
+                };
+                context.solve_output(constraint_key).lines = vec![line];
+            };
+        }
+    }
+
+    record_overflow(
+        context,
+        constraint_key,
+        constraint_node.child_keys,
+        relative_content_frame.to_frame(content.direction),
+    );
+    solve_ignored_child_keys(
+        context,
+        &ignored_constraint_keys,
+        frame_key,
+        RelativeFrame {
+            offset_main: 0.,
+            offset_cross: 0.,
+            ..relative_frame
+        },
+        content.direction,
+        None,
+    );
+    solve_collapsed_child_keys(
+        context,
+        &partition_hidden(context.constraint_tree, constraint_node.child_keys),
+        frame_key,
+        relative_content_frame,
+        content,
     );
+
+    context.record_baseline(constraint_key, constraint_node, frame);
+    context.content_frame = containing_frame;
+}
+
+fn finite_length(length: f64) -> f64 {
+    match length.is_finite() {
+        true => length,
+        false => 0.,
+    }
+}
+
+fn resolve_content_frame(content: Content, frame: Frame, containing_frame: Frame) -> RelativeFrame {
+    let relative_frame = frame.to_relative_frame(content.direction);
+    let relative_padding = content
+        .resolve_insets(containing_frame.length_x, containing_frame.length_y)
+        .to_relative_padding(content.direction);
+
+    generate_content_frame_relative(
+        relative_padding,
+        relative_frame.length_main,
+        relative_frame.length_cross,
+    )
+}
+
+fn record_overflow(
+    context: &mut Context,
+    constraint_key: ConstraintKey,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    content_frame: Frame,
+) {
+    let constraint_tree = context.constraint_tree;
+    let solve_output = context.solve_output(constraint_key);
+    let collapsed_constraint_keys = solve_output.collapsed.clone();
+    let (mut overflow_x, mut overflow_y) = solve_output.overflow.unwrap_or_default();
+    let mut extent = None::<Frame>;
+
+    // Hidden children are only solved after this, so their key map entries may still be stale.
+    for (child_constraint_key, child_constraint_node) in iter(constraint_tree, constraint_keys) {
+        if child_constraint_node.value.is_out_of_flow()
+            || child_constraint_node.value.display == Display::None
+            || collapsed_constraint_keys.contains(&child_constraint_key)
+        {
+            continue;
+        };
+        let Some(&frame_key) = context.key_map.get(&child_constraint_key) else {
+            continue;
+        };
+        let frame = *context.frame_tree.get(frame_key).unwrap().value;
+        extent = Some(match extent {
+            Some(extent) => extent.union(frame),
+            None => frame,
+        });
+    }
+
+    if let Some(extent) = extent {
+        let overflow_length =
+            |offset: f64, length: f64, content_offset: f64, content_length: f64| {
+                (content_offset - offset).max(0.)
+                    + (offset + length - content_offset - content_length).max(0.)
+            };
+        overflow_x = overflow_x.max(overflow_length(
+            extent.offset_x,
+            extent.length_x,
+            content_frame.offset_x,
+            content_frame.length_x,
+        ));
+        overflow_y = overflow_y.max(overflow_length(
+            extent.offset_y,
+            extent.length_y,
+            content_frame.offset_y,
+            content_frame.length_y,
+        ));
+    };
+
+    if overflow_x > 0. || overflow_y > 0. {
+        context.solve_output(constraint_key).overflow = Some((overflow_x, overflow_y));
+    };
+}
+
+fn solve_ignored_child_keys(
+    context: &mut Context,
+    constraint_keys: &[ConstraintKey],
+    parent_frame_key: FrameKey,
+    parent_relative_frame: RelativeFrame,
+    direction: Direction,
+    parent_align: Option<(Align, Align)>,
+) {
+    let constraint_tree = context.constraint_tree;
+
+    for &constraint_key in constraint_keys {
+        let start = context.start_timer();
+        let constraint_node = constraint_tree.get(constraint_key).unwrap();
+        let constraint = context.get_constraint(constraint_key);
+        let (align_main, align_cross) = match parent_align {
+            Some((align_main, align_cross)) => (
+                constraint.justify_self.unwrap_or(align_main),
+                constraint.align_self.unwrap_or(align_cross),
+            ),
+            None => (
+                constraint.content.align_main,
+                constraint.content.align_cross,
+            ),
+        };
+        let unresolved_fill = constraint.fill.to_relative_fill(direction);
+        let relative_fill = unresolved_fill.resolve(
+            parent_relative_frame.length_main,
+            parent_relative_frame.length_cross,
+        );
+        let bounded_length_main = bound_length(
+            unresolved_fill.main,
+            parent_relative_frame.length_main,
+            align_main == Align::Stretch,
+        );
+        let bounded_length_cross = bound_length(
+            unresolved_fill.cross,
+            parent_relative_frame.length_cross,
+            align_cross == Align::Stretch,
+        );
+
+        let is_minimizing = matches!(relative_fill.main, ResolvedFillType::Minimize)
+            || matches!(relative_fill.cross, ResolvedFillType::Minimize);
+        let (minimizing_length_main, minimizing_length_cross) = match is_minimizing {
+            true => minimize(
+                context,
+                constraint_key,
+                &constraint_node,
+                direction,
+                Bound::AtMost(parent_relative_frame.length_main),
+                Bound::AtMost(parent_relative_frame.length_cross),
+            ),
+            false => (0., 0.),
+        };
+
+        let length_main = match (bounded_length_main, relative_fill.main) {
+            (Some(bounded_length_main), _) => bounded_length_main,
+            (None, ResolvedFillType::Exact(exact_main)) => {
+                exact_main.min(parent_relative_frame.length_main)
+            }
+            (None, ResolvedFillType::Scale(0)) => 0.,
+            (None, ResolvedFillType::Scale(_)) => parent_relative_frame.length_main,
+            (None, ResolvedFillType::Minimize) => minimizing_length_main,
+        };
+        let length_cross = match (bounded_length_cross, relative_fill.cross) {
+            (Some(bounded_length_cross), _) => bounded_length_cross,
+            (None, ResolvedFillType::Exact(exact_cross)) => {
+                exact_cross.min(parent_relative_frame.length_cross)
+            }
+            (None, ResolvedFillType::Scale(0)) => 0.,
+            (None, ResolvedFillType::Scale(_)) => parent_relative_frame.length_cross,
+            (None, ResolvedFillType::Minimize) => minimizing_length_cross,
+        };
+
+        let remaining_length_main = parent_relative_frame.length_main - length_main;
+        let remaining_length_cross = parent_relative_frame.length_cross - length_cross;
+
+        let relative_frame = RelativeFrame {
+            offset_main: parent_relative_frame.offset_main
+                + align_main.offset(remaining_length_main),
+            length_main,
+            offset_cross: parent_relative_frame.offset_cross
+                + align_cross.offset(remaining_length_cross),
+            length_cross,
+        };
+
+        let number_of_child_keys = constraint_node.child_keys.len();
+        let frame = match constraint.position {
+            Position::Flow => relative_frame.to_frame(direction),
+            Position::Absolute {
+                left,
+                right,
+                top,
+                bottom,
+            } => {
+                let frame = relative_frame.to_frame(direction);
+                let parent_frame = parent_relative_frame.to_frame(direction);
+                let (is_scaling_x, is_scaling_y) = relative_lengths(
+                    direction,
+                    matches!(relative_fill.main, ResolvedFillType::Scale(1..)),
+                    matches!(relative_fill.cross, ResolvedFillType::Scale(1..)),
+                );
+                let (offset_x, length_x) = inset(
+                    (left, right),
+                    frame.offset_x,
+                    frame.length_x,
+                    parent_frame.length_x,
+                    is_scaling_x,
+                );
+                let (offset_y, length_y) = inset(
+                    (top, bottom),
+                    frame.offset_y,
+                    frame.length_y,
+                    parent_frame.length_y,
+                    is_scaling_y,
+                );
+                Frame {
+                    offset_x,
+                    length_x,
+                    offset_y,
+                    length_y,
+                }
+            }
+        };
+        let frame_key = context.insert_frame(
+            constraint_key,
+            frame,
+            parent_frame_key,
+            number_of_child_keys,
+        );
+
+        solve_children(context, constraint_key, &constraint_node, frame_key, frame);
+
+        context.stop_timer(constraint_key, start);
+    }
+}
+
+struct FlexItem {
+    weight: f64,
+    basis: f64,
+    min: f64,
+    max: Option<f64>,
+}
+
+fn flex(
+    flex_items: &[Option<FlexItem>],
+    lengths: &mut [Option<f64>],
+    free_length: f64,
+    gap_weight: f64,
+    is_shrinking: bool,
+    is_deterministic: bool,
+) -> f64 {
+    let mut is_frozen = flex_items.iter().map(Option::is_none).collect::<Vec<_>>();
+
+    loop {
+        let mut unfrozen_free_length = Accumulator::new(free_length, is_deterministic);
+        let mut unfrozen_weight = gap_weight;
+        for ((flex_item, length), &is_frozen) in flex_items.iter().zip(&*lengths).zip(&is_frozen) {
+            match (flex_item, is_frozen) {
+                (Some(_), true) => unfrozen_free_length.add(-length.unwrap_or_default()),
+                (Some(flex_item), false) => {
+                    unfrozen_weight += flex_item.weight;
+                    unfrozen_free_length.add(-flex_item.basis);
+                }
+                (None, _) => (),
+            };
+        }
+        let unfrozen_free_length = match is_shrinking {
+            true => unfrozen_free_length.value(),
+            false => unfrozen_free_length.value().max(0.),
+        };
+
+        let share_length = match unfrozen_weight > 0. {
+            true => unfrozen_free_length / unfrozen_weight,
+            false => 0.,
+        };
+        let mut total_violation = 0.;
+        let mut violations = vec![0.; flex_items.len()];
+
+        for (((flex_item, length), &is_frozen), violation) in flex_items
+            .iter()
+            .zip(&mut *lengths)
+            .zip(&is_frozen)
+            .zip(&mut violations)
+        {
+            if let (Some(flex_item), false) = (flex_item, is_frozen) {
+                let flexed_length = flex_item.basis + flex_item.weight * share_length;
+                let clamped_length = flex_item
+                    .max
+                    .map_or(flexed_length.max(0.), |max| flexed_length.max(0.).min(max))
+                    .max(flex_item.min);
+
+                *violation = clamped_length - flexed_length;
+                total_violation += *violation;
+                *length = Some(clamped_length);
+            };
+        }
+
+        let mut did_freeze = false;
+        for (violation, is_frozen) in violations.into_iter().zip(&mut is_frozen) {
+            let is_violating = match total_violation {
+                total_violation if total_violation > 0. => violation > 0.,
+                total_violation if total_violation < 0. => violation < 0.,
+                _ => violation != 0.,
+            };
+            if is_violating {
+                *is_frozen = true;
+                did_freeze = true;
+            };
+        }
+
+        if !did_freeze {
+            break share_length;
+        };
+    }
+}
+
+fn scale_overflow(context: &Context, parent_constraint_key: ConstraintKey) -> ! {
+    panic!(
+        "scale weights overflow in the children of {}",
+        context.describe(parent_constraint_key)
+    )
+}
+
+fn bound_length(fill_type: FillType, available_length: f64, is_growing: bool) -> Option<f64> {
+    let FillType::Bounded {
+        min,
+        preferred,
+        max,
+    } = fill_type
+    else {
+        return None;
+    };
+    let length = match is_growing {
+        true => available_length,
+        false => preferred.min(available_length),
+    };
+
+    Some(length.min(max).max(min))
+}
+
+fn inset(
+    (start, end): (Option<f64>, Option<f64>),
+    offset: f64,
+    length: f64,
+    parent_length: f64,
+    is_scaling: bool,
+) -> (f64, f64) {
+    let length = match is_scaling {
+        true => (parent_length - start.unwrap_or_default() - end.unwrap_or_default()).max(0.),
+        false => length,
+    };
+
+    match (start, end) {
+        (Some(start), _) => (start, length),
+        (None, Some(end)) => (parent_length - end - length, length),
+        (None, None) => (offset, length),
+    }
 }
 
 fn solve_child_keys_relative(
-    constraint_tree: &Tree<ConstraintKey, Constraint>,
-    frame_tree: &mut Tree<FrameKey, Frame>,
-    key_map: &mut BTreeMap<ConstraintKey, FrameKey>,
+    context: &mut Context,
+    parent_constraint_key: ConstraintKey,
     constraint_keys: &IndexSet<ConstraintKey>,
     parent_frame_key: FrameKey,
     relative_content_frame: RelativeFrame,
     parent_content: Content,
 ) {
+    let constraint_tree = context.constraint_tree;
     let mut remaining_length_main = relative_content_frame.length_main;
     let mut total_scale_main: usize = 0;
+    let is_clamping = parent_content.overflow == Overflow::Clip
+        && parent_content.precedence == Precedence::ExactFirst
+        && parent_content.overflow_anchor.is_none();
+
+    let mut clamped_length_main = 0.;
+    let mut overflow_length_cross = 0_f64;
+
+    let number_of_gaps = constraint_keys.len().saturating_sub(1);
+    let mut gap_length_main = 0.;
+    let mut gap_scale_main: usize = 0;
+    let gap_main = parent_content
+        .gap_main
+        .map(|gap_main| gap_main.resolve(relative_content_frame.length_main));
+    match gap_main {
+        Some(ResolvedFillType::Exact(exact_gap)) => {
+            let total_gap_length_main = match is_clamping {
+                true => (exact_gap * number_of_gaps as f64).min(remaining_length_main),
+                false => exact_gap * number_of_gaps as f64,
+            };
+            clamped_length_main += exact_gap * number_of_gaps as f64 - total_gap_length_main;
+            remaining_length_main -= total_gap_length_main;
+            gap_length_main = total_gap_length_main / number_of_gaps.max(1) as f64;
+        }
+        Some(ResolvedFillType::Scale(scale_gap)) => {
+            gap_scale_main = scale_gap
+                .checked_mul(number_of_gaps)
+                .unwrap_or_else(|| scale_overflow(context, parent_constraint_key));
+            total_scale_main = gap_scale_main;
+        }
+        Some(ResolvedFillType::Minimize) | None => (),
+    };
+
+    let mut relative_margins = constraint_keys
+        .iter()
+        .map(|&constraint_key| {
+            context
+                .get_constraint(constraint_key)
+                .margin
+                .to_relative_padding(parent_content.direction)
+        })
+        .collect::<Vec<_>>();
+    if parent_content.collapse_margins {
+        collapse_margins(&mut relative_margins);
+    };
 
     let mut relative_lengths = iter(constraint_tree, constraint_keys)
-        .map(|(_, constraint_node)| {
-            let relatve_fill = constraint_node
-                .value
-                .fill
-                .to_relative_fill(parent_content.direction);
+        .zip(&relative_margins)
+        .map(|((constraint_key, constraint_node), relative_margin)| {
+            let constraint = context.get_constraint(constraint_key);
+            let relative_fill = constraint.fill.to_relative_fill(parent_content.direction);
+            let resolved_fill = relative_fill.resolve(
+                relative_content_frame.length_main,
+                relative_content_frame.length_cross,
+            );
+            let relative_bounds = constraint
+                .bounds
+                .to_relative_bounds(parent_content.direction);
+            let available_length_cross =
+                (relative_content_frame.length_cross - relative_margin.length_cross()).max(0.);
+            let align_cross = constraint.align_self.unwrap_or(parent_content.align_cross);
+            remaining_length_main -= relative_margin.length_main();
 
-            let mut cache = None;
+            let bounded_length_cross = bound_length(
+                relative_fill.cross,
+                available_length_cross,
+                align_cross == Align::Stretch,
+            );
+            let mut length_cross = match (resolved_fill.cross, bounded_length_cross) {
+                (_, Some(bounded_length_cross)) => {
+                    overflow_length_cross =
+                        overflow_length_cross.max(bounded_length_cross - available_length_cross);
+                    Some(bounded_length_cross)
+                }
+                (ResolvedFillType::Exact(exact_cross), None) => {
+                    overflow_length_cross =
+                        overflow_length_cross.max(exact_cross - available_length_cross);
+                    Some(exact_cross.min(available_length_cross))
+                }
+                (ResolvedFillType::Scale(0), None) => Some(0.),
+                (ResolvedFillType::Scale(_), None) => {
+                    Some(relative_bounds.clamp_cross(available_length_cross))
+                }
+                (ResolvedFillType::Minimize, None) if align_cross == Align::Stretch => {
+                    Some(relative_bounds.clamp_cross(available_length_cross))
+                }
+                (ResolvedFillType::Minimize, None) => None,
+            };
 
-            let length_main = match relatve_fill.main {
-                FillType::Exact(exact_main) => {
-                    let exact_main = exact_main.min(remaining_length_main);
+            let length_main = match (relative_fill.main, resolved_fill.main) {
+                (FillType::Bounded { min, .. }, ResolvedFillType::Exact(preferred_main)) => {
+                    remaining_length_main -= min;
+                    Some(preferred_main)
+                }
+                (_, ResolvedFillType::Exact(exact_main)) => {
+                    let clamped_exact_main = match is_clamping {
+                        true => exact_main.min(remaining_length_main.max(0.)),
+                        false => exact_main,
+                    };
+                    clamped_length_main += exact_main - clamped_exact_main;
+                    let exact_main = clamped_exact_main;
                     remaining_length_main -= exact_main;
                     Some(exact_main)
                 }
-                FillType::Scale(scale_main) => {
-                    total_scale_main = total_scale_main.checked_add(scale_main).unwrap();
+                (_, ResolvedFillType::Scale(scale_main)) => {
+                    total_scale_main = total_scale_main
+                        .checked_add(scale_main)
+                        .unwrap_or_else(|| scale_overflow(context, parent_constraint_key));
                     None
                 }
-                FillType::Minimize => {
-                    let (minimizing_length_main, minimizing_length_cross) =
-                        find_minimizing_length_relative(
-                            constraint_tree,
-                            constraint_node.child_keys,
-                            constraint_node.value.content.direction,
-                            remaining_length_main,
-                            relative_content_frame.length_cross,
-                        );
-                    cache = Some(minimizing_length_cross);
+                (_, ResolvedFillType::Minimize) => {
+                    let bound_main = match is_clamping {
+                        true => Bound::AtMost(remaining_length_main.max(0.)),
+                        false => Bound::AtMost(relative_content_frame.length_main),
+                    };
+                    let bound_cross =
+                        length_cross.map_or(Bound::AtMost(available_length_cross), Bound::Exactly);
+                    let (minimizing_length_main, minimizing_length_cross) = minimize(
+                        context,
+                        constraint_key,
+                        &constraint_node,
+                        parent_content.direction,
+                        bound_main,
+                        bound_cross,
+                    );
+                    remaining_length_main -= minimizing_length_main;
+                    length_cross.get_or_insert(minimizing_length_cross);
                     Some(minimizing_length_main)
                 }
             };
 
-            let length_cross = match relatve_fill.cross {
-                FillType::Exact(exact_cross) => {
-                    exact_cross.min(relative_content_frame.length_cross)
+            (resolved_fill, length_main, length_cross, relative_bounds)
+        })
+        .collect::<Vec<_>>();
+
+    remaining_length_main -= equalize_groups(
+        iter(constraint_tree, constraint_keys)
+            .zip(&mut relative_lengths)
+            .map(|((_, constraint_node), (_, length_main, _, _))| {
+                (constraint_node.value.group, length_main)
+            })
+            .collect(),
+    );
+
+    let bounded_items_main = constraint_keys
+        .iter()
+        .zip(&relative_lengths)
+        .map(|(&constraint_key, (_, length_main, _, relative_bounds))| {
+            let constraint = context.get_constraint(constraint_key);
+            match constraint
+                .fill
+                .to_relative_fill(parent_content.direction)
+                .main
+            {
+                FillType::Bounded { min, max, .. } => {
+                    let basis_main = length_main.unwrap_or_default();
+                    remaining_length_main -= basis_main - min;
+                    Some(FlexItem {
+                        weight: 1.,
+                        basis: basis_main,
+                        min: min.max(relative_bounds.min_main),
+                        max: Some(relative_bounds.clamp_main(max).max(min)),
+                    })
+                }
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    if bounded_items_main.iter().any(Option::is_some)
+        && (total_scale_main == 0 || remaining_length_main < 0.)
+    {
+        let mut bounded_lengths_main = relative_lengths
+            .iter()
+            .map(|(_, length_main, ..)| *length_main)
+            .collect::<Vec<_>>();
+        let bases_length_main = bounded_items_main
+            .iter()
+            .flatten()
+            .map(|bounded_item_main| bounded_item_main.basis)
+            .sum::<f64>();
+        flex(
+            &bounded_items_main,
+            &mut bounded_lengths_main,
+            remaining_length_main + bases_length_main,
+            0.,
+            true,
+            context.is_deterministic,
+        );
+        for (((_, length_main, ..), bounded_length_main), bounded_item_main) in relative_lengths
+            .iter_mut()
+            .zip(bounded_lengths_main)
+            .zip(&bounded_items_main)
+        {
+            if let Some(bounded_item_main) = bounded_item_main {
+                remaining_length_main +=
+                    bounded_item_main.basis - bounded_length_main.unwrap_or_default();
+                *length_main = bounded_length_main;
+            };
+        }
+    };
+
+    let mut basis_proportion_main = 1.;
+    if parent_content.precedence == Precedence::Proportional && total_scale_main > 0 {
+        let mut exact_length_main = Accumulator::new(0., context.is_deterministic);
+        let mut basis_length_main = Accumulator::new(0., context.is_deterministic);
+        for (&constraint_key, (relative_fill, length_main, _, _)) in
+            constraint_keys.iter().zip(&relative_lengths)
+        {
+            match (relative_fill.main, length_main) {
+                (ResolvedFillType::Exact(..), Some(length_main)) => {
+                    exact_length_main.add(*length_main);
                 }
-                FillType::Scale(0) => 0.,
-                FillType::Scale(_) => relative_content_frame.length_cross,
-                FillType::Minimize => cache.unwrap_or_else(|| {
-                    let (_, minimizing_length_cross) = find_minimizing_length_relative(
-                        constraint_tree,
-                        constraint_node.child_keys,
-                        constraint_node.value.content.direction,
-                        remaining_length_main,
-                        relative_content_frame.length_cross,
+                (ResolvedFillType::Scale(..), _) => {
+                    basis_length_main.add(
+                        context
+                            .get_constraint(constraint_key)
+                            .basis
+                            .unwrap_or_default(),
                     );
-                    minimizing_length_cross
-                }),
+                }
+                _ => (),
             };
+        }
+        let exact_length_main = exact_length_main.value();
+        let demanded_length_main = exact_length_main + basis_length_main.value();
 
-            let remaining_length_cross = relative_content_frame.length_cross - length_cross;
-            let offset_cross = relative_content_frame.offset_cross
-                + match parent_content.align_cross {
-                    Align::Start => 0.,
-                    Align::Middle => remaining_length_cross / 2.,
-                    Align::End => remaining_length_cross,
+        if demanded_length_main > 0. {
+            let available_length_main = (remaining_length_main + exact_length_main).max(0.);
+            let proportion = available_length_main / (available_length_main + demanded_length_main);
+            for (relative_fill, length_main, _, _) in &mut relative_lengths {
+                if let (ResolvedFillType::Exact(..), Some(length_main)) =
+                    (relative_fill.main, length_main)
+                {
+                    *length_main *= proportion;
                 };
+            }
+            remaining_length_main = available_length_main - exact_length_main * proportion;
+            basis_proportion_main = proportion;
+        };
+    };
 
-            (relatve_fill, length_main, length_cross, offset_cross)
-        })
-        .collect::<Vec<_>>();
+    let overflow_length_main = clamped_length_main + (-remaining_length_main).max(0.);
+    if overflow_length_main > 0. || overflow_length_cross > 0. {
+        let overflow_lengths = self::relative_lengths(
+            parent_content.direction,
+            overflow_length_main,
+            overflow_length_cross,
+        );
+        context.solve_output(parent_constraint_key).overflow = Some(overflow_lengths);
+    };
+
+    if remaining_length_main < 0. {
+        match parent_content.overflow {
+            Overflow::Clip => {
+                let anchor_fraction = parent_content
+                    .overflow_anchor
+                    .map_or(0., |overflow_anchor| overflow_anchor.offset(1.))
+                    .clamp(0., 1.);
+                let mut trim_start = -remaining_length_main * anchor_fraction;
+                let mut trim_end = -remaining_length_main - trim_start;
+                for (_, length_main, _, _) in &mut relative_lengths {
+                    if let Some(length_main) = length_main {
+                        let trim = length_main.min(trim_start);
+                        *length_main -= trim;
+                        trim_start -= trim;
+                    };
+                }
+                for (_, length_main, _, _) in relative_lengths.iter_mut().rev() {
+                    if let Some(length_main) = length_main {
+                        let trim = length_main.min(trim_end);
+                        *length_main -= trim;
+                        trim_end -= trim;
+                    };
+                }
+                remaining_length_main = 0.;
+            }
+            Overflow::ShrinkProportional => {
+                let proportion = relative_content_frame.length_main
+                    / (relative_content_frame.length_main - remaining_length_main);
+                for (_, length_main, _, _) in &mut relative_lengths {
+                    if let Some(length_main) = length_main {
+                        *length_main *= proportion;
+                    };
+                }
+                remaining_length_main = 0.;
+            }
+            Overflow::ShrinkWeighted => {
+                let shrinks_main = constraint_keys
+                    .iter()
+                    .map(|&constraint_key| {
+                        context
+                            .get_constraint(constraint_key)
+                            .shrink
+                            .unwrap_or(1.)
+                            .max(0.)
+                    })
+                    .collect::<Vec<_>>();
+                let mut deficit_length_main = -remaining_length_main;
+                while deficit_length_main > 0. {
+                    let total_weight_main = relative_lengths
+                        .iter()
+                        .zip(&shrinks_main)
+                        .filter_map(|((_, length_main, _, _), shrink_main)| {
+                            length_main.map(|length_main| length_main * shrink_main)
+                        })
+                        .sum::<f64>();
+                    if total_weight_main <= 0. {
+                        break;
+                    };
 
+                    let ratio = deficit_length_main / total_weight_main;
+                    let is_exhausting = shrinks_main.iter().zip(&relative_lengths).any(
+                        |(shrink_main, (_, length_main, _, _))| {
+                            length_main.is_some_and(|length_main| length_main > 0.)
+                                && shrink_main * ratio > 1.
+                        },
+                    );
+                    for ((_, length_main, _, _), shrink_main) in
+                        relative_lengths.iter_mut().zip(&shrinks_main)
+                    {
+                        let Some(length_main) = length_main else {
+                            continue;
+                        };
+                        match (is_exhausting, shrink_main * ratio > 1.) {
+                            (true, true) => {
+                                deficit_length_main -= *length_main;
+                                *length_main = 0.;
+                            }
+                            (true, false) => (),
+                            (false, _) => *length_main -= *length_main * shrink_main * ratio,
+                        };
+                    }
+                    if !is_exhausting {
+                        deficit_length_main = 0.;
+                    };
+                }
+                remaining_length_main = 0.;
+            }
+            Overflow::Relax => {
+                let mut priorities = constraint_keys
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, &constraint_key)| {
+                        context
+                            .get_constraint(constraint_key)
+                            .priority
+                            .map(|priority| (priority, Reverse(index)))
+                    })
+                    .collect::<Vec<_>>();
+                priorities.sort();
+
+                let mut deficit_length_main = -remaining_length_main;
+                for (_, Reverse(index)) in priorities {
+                    let (_, length_main, _, relative_bounds) = &mut relative_lengths[index];
+                    if let Some(length_main) = length_main {
+                        let min_main = relative_bounds.min_main.min(*length_main);
+                        let trim = (*length_main - min_main).min(deficit_length_main);
+                        *length_main -= trim;
+                        deficit_length_main -= trim;
+                    };
+                }
+                remaining_length_main = -deficit_length_main;
+            }
+            Overflow::Collapse | Overflow::Overflow => (),
+        };
+    };
+
+    let mut spacing_length_main = 0.;
     let mut offset_main = match total_scale_main {
         0 => {
-            relative_content_frame.offset_main
-                + match parent_content.align_main {
-                    Align::Start => 0.,
-                    Align::Middle => remaining_length_main / 2.,
-                    Align::End => remaining_length_main,
-                }
+            let align_main = match (remaining_length_main < 0., parent_content.overflow_anchor) {
+                (true, Some(overflow_anchor)) => overflow_anchor,
+                _ => parent_content.align_main,
+            };
+            let (leading_length_main, distributed_length_main) =
+                align_main.distribute(remaining_length_main, constraint_keys.len());
+            spacing_length_main = distributed_length_main;
+            relative_content_frame.offset_main + leading_length_main
         }
         _ => {
-            for (relative_fill, length_main, _, _) in &mut relative_lengths {
-                if let FillType::Scale(scale_main) = relative_fill.main {
-                    let proportion = (scale_main as f64) / (total_scale_main as f64);
-                    *length_main = Some(proportion * remaining_length_main);
-                };
+            let scale_items_main = constraint_keys
+                .iter()
+                .zip(&relative_lengths)
+                .map(
+                    |(&constraint_key, (relative_fill, _, _, relative_bounds))| match relative_fill
+                        .main
+                    {
+                        ResolvedFillType::Scale(scale_main) => Some(FlexItem {
+                            weight: scale_main as f64,
+                            basis: context
+                                .get_constraint(constraint_key)
+                                .basis
+                                .unwrap_or_default()
+                                * basis_proportion_main,
+                            min: relative_bounds.min_main,
+                            max: relative_bounds.max_main,
+                        }),
+                        _ => None,
+                    },
+                )
+                .collect::<Vec<_>>();
+            let is_basing = constraint_keys
+                .iter()
+                .any(|&constraint_key| context.get_constraint(constraint_key).basis.is_some());
+            let mut scaled_lengths_main = relative_lengths
+                .iter()
+                .map(|(_, length_main, ..)| *length_main)
+                .collect::<Vec<_>>();
+
+            let scale_length_main = flex(
+                &scale_items_main,
+                &mut scaled_lengths_main,
+                remaining_length_main,
+                gap_scale_main as f64,
+                is_basing,
+                context.is_deterministic,
+            );
+            for ((_, length_main, ..), scaled_length_main) in
+                relative_lengths.iter_mut().zip(scaled_lengths_main)
+            {
+                *length_main = scaled_length_main;
             }
 
-            0.
+            if gap_scale_main != 0 {
+                gap_length_main =
+                    (gap_scale_main / number_of_gaps) as f64 * scale_length_main.max(0.);
+                relative_content_frame.offset_main
+            } else {
+                // Scaled children clamped by their maximum bounds leave space for alignment.
+                let scaled_length_main = scale_items_main
+                    .iter()
+                    .zip(&relative_lengths)
+                    .filter(|(scale_item_main, _)| scale_item_main.is_some())
+                    .map(|(_, (_, length_main, ..))| length_main.unwrap_or_default())
+                    .sum::<f64>();
+                let (leading_length_main, distributed_length_main) =
+                    parent_content.align_main.distribute(
+                        remaining_length_main - scaled_length_main,
+                        constraint_keys.len(),
+                    );
+                spacing_length_main = distributed_length_main;
+                relative_content_frame.offset_main + leading_length_main.max(0.)
+            }
         }
     };
 
-    for ((constraint_key, constraint_node), (_, length_main, length_cross, offset_cross)) in
-        iter(constraint_tree, constraint_keys).zip(relative_lengths)
+    let mut lengths = iter(constraint_tree, constraint_keys)
+        .zip(&relative_lengths)
+        .zip(&relative_margins)
+        .map(
+            |(
+                ((constraint_key, constraint_node), (_, length_main, length_cross, _)),
+                relative_margin,
+            )| {
+                let length_main = length_main.unwrap_or_default();
+                let length_cross = length_cross.unwrap_or_else(|| {
+                    let (_, minimizing_length_cross) = minimize(
+                        context,
+                        constraint_key,
+                        &constraint_node,
+                        parent_content.direction,
+                        Bound::Exactly(length_main),
+                        Bound::AtMost(
+                            (relative_content_frame.length_cross - relative_margin.length_cross())
+                                .max(0.),
+                        ),
+                    );
+                    minimizing_length_cross
+                });
+                (length_main, length_cross)
+            },
+        )
+        .collect::<Vec<_>>();
+
+    if parent_content.stretch_to_tallest {
+        let tallest_length_cross = relative_lengths
+            .iter()
+            .zip(&lengths)
+            .filter(|((relative_fill, ..), _)| {
+                !matches!(relative_fill.cross, ResolvedFillType::Scale(..))
+            })
+            .map(|(_, &(_, length_cross))| length_cross)
+            .reduce(f64::max);
+        if let Some(tallest_length_cross) = tallest_length_cross {
+            for (_, length_cross) in &mut lengths {
+                *length_cross = tallest_length_cross;
+            }
+        };
+    };
+
+    let used_length_main = lengths
+        .iter()
+        .zip(&relative_margins)
+        .map(|(&(length_main, _), relative_margin)| length_main + relative_margin.length_main())
+        .sum::<f64>()
+        + gap_length_main * number_of_gaps as f64;
+    context.solve_output(parent_constraint_key).free_space =
+        Some(relative_content_frame.length_main - used_length_main);
+
+    let is_baseline_aligned = parent_content.direction == Direction::Horizontal
+        && matches!(
+            parent_content.align_cross,
+            Align::FirstBaseline | Align::LastBaseline
+        );
+    let mut baseline_aligned_constraint_keys = vec![];
+
+    for (index, (constraint_key, constraint_node)) in
+        iter(constraint_tree, constraint_keys).enumerate()
     {
-        let length_main = length_main.unwrap_or_default();
+        let start = context.start_timer();
+        let (length_main, length_cross) = lengths[index];
+        let relative_margin = relative_margins[index];
+        let align_self = context.get_constraint(constraint_key).align_self;
+
+        let offset_cross = relative_content_frame.offset_cross
+            + relative_margin.cross_start
+            + match is_baseline_aligned && align_self.is_none() {
+                true => 0.,
+                false => {
+                    let remaining_length_cross = relative_content_frame.length_cross
+                        - relative_margin.length_cross()
+                        - length_cross;
+                    align_self
+                        .unwrap_or(parent_content.align_cross)
+                        .offset(remaining_length_cross)
+                }
+            };
+
+        offset_main += relative_margin.main_start;
+        if let (Direction::Vertical, Some(baseline_grid)) =
+            (parent_content.direction, context.baseline_grid)
+        {
+            let parent_offset_y = context.world_offset_y(parent_constraint_key);
+            offset_main = match parent_content.reverse {
+                true => {
+                    // Reversed children are mirrored when inserted, so snap their mirrored edges.
+                    let mirror = |offset: f64| {
+                        2. * relative_content_frame.offset_main + relative_content_frame.length_main
+                            - offset
+                            - length_main
+                    };
+                    let mirrored_offset_y = parent_offset_y + mirror(offset_main);
+                    mirror(
+                        (mirrored_offset_y / baseline_grid).floor() * baseline_grid
+                            - parent_offset_y,
+                    )
+                }
+                false => {
+                    ((parent_offset_y + offset_main) / baseline_grid).ceil() * baseline_grid
+                        - parent_offset_y
+                }
+            };
+        };
 
         let relative_frame = RelativeFrame {
             offset_main,
@@ -172,35 +1629,143 @@ fn solve_child_keys_relative(
             length_cross,
         };
 
-        offset_main -= length_main;
+        offset_main +=
+            length_main + relative_margin.main_end + gap_length_main + spacing_length_main;
 
         let number_of_child_keys = constraint_node.child_keys.len();
         let frame = relative_frame.to_frame(parent_content.direction);
-        let frame_key = frame_tree
-            .insert_with_capacity(frame, parent_frame_key, number_of_child_keys)
-            .unwrap();
-        key_map.insert(constraint_key, frame_key);
+        let frame_key = context.insert_frame(
+            constraint_key,
+            frame,
+            parent_frame_key,
+            number_of_child_keys,
+        );
 
-        let relative_padding = constraint_node
-            .value
-            .content
-            .padding
-            .to_relative_padding(parent_content.direction);
-        let relative_content_frame =
-            generate_content_frame_relative(relative_padding, length_main, length_cross);
-
-        solve_child_keys_relative(
-            constraint_tree,
-            frame_tree,
-            key_map,
-            constraint_node.child_keys,
-            frame_key,
-            relative_content_frame,
-            constraint_node.value.content,
+        solve_children(context, constraint_key, &constraint_node, frame_key, frame);
+        if is_baseline_aligned && align_self.is_none() {
+            baseline_aligned_constraint_keys.push(constraint_key);
+        };
+
+        context.stop_timer(constraint_key, start);
+    }
+
+    let is_first = parent_content.align_cross == Align::FirstBaseline;
+    let baselines = baseline_aligned_constraint_keys
+        .iter()
+        .map(|constraint_key| {
+            let baseline = context.solve_outputs[constraint_key]
+                .baseline
+                .unwrap_or_default();
+            match is_first {
+                true => baseline.first,
+                false => baseline.last,
+            }
+        })
+        .collect::<Vec<_>>();
+    if let Some(max_baseline) = baselines.iter().copied().reduce(f64::max) {
+        for (&constraint_key, baseline) in baseline_aligned_constraint_keys.iter().zip(baselines) {
+            context.shift_frame(constraint_key, max_baseline - baseline);
+        }
+    };
+}
+
+fn arrange_custom(
+    context: &mut Context,
+    layout: Arc<dyn CustomLayout>,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    parent_frame_key: FrameKey,
+    content_frame: Frame,
+    direction: Direction,
+) {
+    let constraint_tree = context.constraint_tree;
+    let children = layout_children(
+        context,
+        constraint_keys,
+        direction,
+        content_frame.length_x,
+        content_frame.length_y,
+    );
+    let frames = layout.arrange(&children, content_frame);
+
+    for (index, (constraint_key, constraint_node)) in
+        iter(constraint_tree, constraint_keys).enumerate()
+    {
+        let start = context.start_timer();
+
+        let number_of_child_keys = constraint_node.child_keys.len();
+        let frame = frames.get(index).copied().unwrap_or_default();
+        let frame_key = context.insert_frame(
+            constraint_key,
+            frame,
+            parent_frame_key,
+            number_of_child_keys,
         );
+
+        solve_children(context, constraint_key, &constraint_node, frame_key, frame);
+
+        context.stop_timer(constraint_key, start);
     }
 }
 
+fn layout_children(
+    context: &mut Context,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    direction: Direction,
+    max_length_x: f64,
+    max_length_y: f64,
+) -> Vec<LayoutChild> {
+    let constraint_tree = context.constraint_tree;
+    let (max_length_main, max_length_cross) =
+        relative_lengths(direction, max_length_x, max_length_y);
+
+    iter(constraint_tree, constraint_keys)
+        .map(|(constraint_key, constraint_node)| {
+            let constraint = context.get_constraint(constraint_key);
+            let unresolved_fill = constraint.fill.to_relative_fill(direction);
+            let relative_fill = unresolved_fill.resolve(max_length_main, max_length_cross);
+
+            let is_minimizing = matches!(relative_fill.main, ResolvedFillType::Minimize)
+                || matches!(relative_fill.cross, ResolvedFillType::Minimize);
+            let minimizing_lengths = is_minimizing.then(|| {
+                minimize(
+                    context,
+                    constraint_key,
+                    &constraint_node,
+                    direction,
+                    Bound::AtMost(max_length_main),
+                    Bound::AtMost(max_length_cross),
+                )
+            });
+
+            let length_main = match relative_fill.main {
+                ResolvedFillType::Exact(exact_main) => {
+                    bound_length(unresolved_fill.main, max_length_main, false)
+                        .unwrap_or(exact_main.min(max_length_main))
+                }
+                ResolvedFillType::Scale(..) => constraint.basis.unwrap_or_default(),
+                ResolvedFillType::Minimize => minimizing_lengths.unwrap().0,
+            };
+            let length_cross = match relative_fill.cross {
+                ResolvedFillType::Exact(exact_cross) => {
+                    bound_length(unresolved_fill.cross, max_length_cross, false)
+                        .unwrap_or(exact_cross.min(max_length_cross))
+                }
+                ResolvedFillType::Scale(..) => 0.,
+                ResolvedFillType::Minimize => minimizing_lengths.unwrap().1,
+            };
+
+            let (length_x, length_y) = relative_lengths(direction, length_main, length_cross);
+
+            LayoutChild {
+                constraint_key,
+                constraint,
+                length_x,
+                length_y,
+            }
+        })
+        .collect()
+}
+
 fn generate_content_frame_relative(
     relative_padding: RelativePadding,
     length_main: f64,
@@ -222,40 +1787,223 @@ fn generate_content_frame_relative(
     }
 }
 
+fn fit_measured_length(length: f64, bound: Bound) -> f64 {
+    match bound {
+        Bound::AtMost(max_length) => length.max(0.).min(max_length),
+        Bound::Exactly(exact_length) => exact_length,
+    }
+}
+
+fn fit_intrinsic_size(
+    (intrinsic_x, intrinsic_y): (f64, f64),
+    bound_x: Bound,
+    bound_y: Bound,
+) -> (f64, f64) {
+    let scale = |length: f64, from: f64, to: f64| match from > 0. {
+        true => length * to / from,
+        false => to,
+    };
+
+    match (bound_x, bound_y) {
+        (Bound::Exactly(length_x), Bound::Exactly(length_y)) => (length_x, length_y),
+        (Bound::Exactly(length_x), Bound::AtMost(max_length_y)) => (
+            length_x,
+            scale(length_x, intrinsic_x, intrinsic_y).min(max_length_y),
+        ),
+        (Bound::AtMost(max_length_x), Bound::Exactly(length_y)) => (
+            scale(length_y, intrinsic_y, intrinsic_x).min(max_length_x),
+            length_y,
+        ),
+        (Bound::AtMost(max_length_x), Bound::AtMost(max_length_y)) => {
+            let mut ratio: f64 = 1.;
+            if intrinsic_x > max_length_x {
+                ratio = ratio.min(max_length_x / intrinsic_x);
+            };
+            if intrinsic_y > max_length_y {
+                ratio = ratio.min(max_length_y / intrinsic_y);
+            };
+            (intrinsic_x * ratio, intrinsic_y * ratio)
+        }
+    }
+}
+
 fn find_minimizing_length_relative(
-    constraint_tree: &Tree<ConstraintKey, Constraint>,
+    context: &mut Context,
+    parent_constraint_key: ConstraintKey,
     constraint_keys: &IndexSet<ConstraintKey>,
     direction: Direction,
-    max_length_main: f64,
-    max_length_cross: f64,
+    bound_main: Bound,
+    bound_cross: Bound,
 ) -> (f64, f64) {
-    let mut remaining_length_main: f64 = max_length_main;
+    let constraint_tree = context.constraint_tree;
+    let start = context.start_timer();
+    let max_length_main = bound_main.max_length();
+    let max_length_cross = bound_cross.max_length();
+    let (constraint_keys, _) = partition_layout_ignored(constraint_tree, constraint_keys);
+    let constraint_keys = &constraint_keys;
+
+    let parent_content = context.get_constraint(parent_constraint_key).content;
+
+    if let Some(layout) = context.get_layout(parent_constraint_key, parent_content) {
+        let (bound_x, bound_y) = relative_lengths(direction, bound_main, bound_cross);
+        let children = layout_children(
+            context,
+            constraint_keys,
+            direction,
+            bound_x.max_length(),
+            bound_y.max_length(),
+        );
+        let (length_x, length_y) = match (bound_x, bound_y) {
+            (Bound::Exactly(length_x), Bound::Exactly(length_y)) => (length_x, length_y),
+            (Bound::Exactly(length_x), Bound::AtMost(..)) => {
+                (length_x, layout.measure_y_for_x(&children, length_x))
+            }
+            (Bound::AtMost(..), Bound::Exactly(length_y)) => {
+                (layout.measure_x_for_y(&children, length_y), length_y)
+            }
+            (Bound::AtMost(max_length_x), Bound::AtMost(max_length_y)) => {
+                layout.measure(&children, max_length_x, max_length_y)
+            }
+        };
+        let (length_main, length_cross) = relative_lengths(direction, length_x, length_y);
+
+        context.stop_timer(parent_constraint_key, start);
+
+        return (
+            length_main.max(0.).min(max_length_main),
+            length_cross.max(0.).min(max_length_cross),
+        );
+    };
+
+    let minimizing_lengths = match parent_content.mode {
+        LayoutMode::Grid => Some(find_minimizing_length_grid(
+            context,
+            parent_constraint_key,
+            constraint_keys,
+            parent_content,
+            direction,
+            bound_main,
+            bound_cross,
+        )),
+        LayoutMode::Table => Some(find_minimizing_length_table(
+            context,
+            constraint_keys,
+            parent_content,
+            direction,
+            bound_main,
+            bound_cross,
+        )),
+        LayoutMode::Stack => {
+            let (max_length_x, max_length_y) =
+                relative_lengths(direction, max_length_main, max_length_cross);
+            let children = layout_children(
+                context,
+                constraint_keys,
+                direction,
+                max_length_x,
+                max_length_y,
+            );
+            let (length_x, length_y) = children.iter().fold((0_f64, 0_f64), |(x, y), child| {
+                (x.max(child.length_x), y.max(child.length_y))
+            });
+            let (length_main, length_cross) = relative_lengths(direction, length_x, length_y);
+            Some((
+                length_main.min(max_length_main),
+                length_cross.min(max_length_cross),
+            ))
+        }
+        LayoutMode::Columns(number_of_columns) => Some(find_minimizing_length_columns(
+            context,
+            constraint_keys,
+            parent_content,
+            number_of_columns,
+            direction,
+            bound_main,
+            bound_cross,
+        )),
+        LayoutMode::Flow | LayoutMode::Wrap => Some(find_minimizing_length_flow(
+            context,
+            constraint_keys,
+            parent_content,
+            direction,
+            bound_main,
+            bound_cross,
+        )),
+        LayoutMode::Relative | LayoutMode::Split => None,
+    };
+    if let Some(minimizing_lengths) = minimizing_lengths {
+        context.stop_timer(parent_constraint_key, start);
+
+        return minimizing_lengths;
+    };
+
+    let number_of_gaps = constraint_keys.len().saturating_sub(1);
+    let gap_length_main =
+        exact_gap(parent_content.gap_main, max_length_main) * number_of_gaps as f64;
+
+    let mut remaining_length_main: f64 = (max_length_main - gap_length_main).max(0.);
+    let mut used_length_main = gap_length_main;
     let mut max_seen_length_cross: f64 = 0.;
+    let mut grouped_lengths_main = Vec::with_capacity(constraint_keys.len());
 
-    let mut cache = None;
+    let mut relative_margins = constraint_keys
+        .iter()
+        .map(|&constraint_key| {
+            context
+                .get_constraint(constraint_key)
+                .margin
+                .to_relative_padding(direction)
+        })
+        .collect::<Vec<_>>();
+    if parent_content.collapse_margins {
+        collapse_margins(&mut relative_margins);
+    };
 
-    for (_, constraint_node) in iter(constraint_tree, constraint_keys) {
-        let relative_fill = constraint_node.value.fill.to_relative_fill(direction);
-        let relative_padding = constraint_node
-            .value
+    for ((constraint_key, constraint_node), relative_margin) in
+        iter(constraint_tree, constraint_keys).zip(relative_margins)
+    {
+        let mut cache = None;
+        let constraint = context.get_constraint(constraint_key);
+        let relative_fill = constraint
+            .fill
+            .to_relative_fill(direction)
+            .resolve(max_length_main, max_length_cross);
+        let relative_padding = constraint
             .content
-            .padding
+            .resolve_insets(
+                context.content_frame.length_x,
+                context.content_frame.length_y,
+            )
             .to_relative_padding(direction);
+        let relative_bounds = constraint.bounds.to_relative_bounds(direction);
+        let margin_length_main = relative_margin.length_main().min(remaining_length_main);
+        remaining_length_main -= margin_length_main;
+        used_length_main += margin_length_main;
 
         let length_main = match relative_fill.main {
-            FillType::Exact(exact_main) => {
-                exact_main + relative_padding.main_start + relative_padding.main_end
-            }
-            FillType::Scale(..) => relative_padding.main_start + relative_padding.main_end,
-            FillType::Minimize => {
-                let (sub_minimizing_length_main, sub_minimizing_length_cross) =
-                    find_minimizing_length_relative(
-                        constraint_tree,
-                        constraint_node.child_keys,
-                        constraint_node.value.content.direction,
-                        remaining_length_main,
-                        max_length_cross,
-                    );
+            ResolvedFillType::Exact(exact_main) => exact_main,
+            ResolvedFillType::Scale(..) => (relative_padding.main_start
+                + relative_padding.main_end)
+                .max(relative_bounds.min_main)
+                .max(constraint.basis.unwrap_or_default()),
+            ResolvedFillType::Minimize => {
+                let sub_bound_cross = match (relative_fill.cross, bound_cross) {
+                    (ResolvedFillType::Exact(exact_cross), _) => {
+                        Bound::Exactly(exact_cross.min(max_length_cross))
+                    }
+                    (ResolvedFillType::Scale(..), Bound::Exactly(length_cross)) => {
+                        Bound::Exactly(length_cross)
+                    }
+                    _ => Bound::AtMost(max_length_cross),
+                };
+                let (sub_minimizing_length_main, sub_minimizing_length_cross) = minimize(
+                    context,
+                    constraint_key,
+                    &constraint_node,
+                    direction,
+                    Bound::AtMost(remaining_length_main),
+                    sub_bound_cross,
+                );
                 cache = Some(sub_minimizing_length_cross);
                 sub_minimizing_length_main
             }
@@ -263,32 +2011,220 @@ fn find_minimizing_length_relative(
         .min(remaining_length_main);
 
         let length_cross = match relative_fill.cross {
-            FillType::Exact(exact_cross) => {
-                exact_cross + relative_padding.cross_start + relative_padding.cross_end
-            }
-            FillType::Scale(..) => relative_padding.cross_start + relative_padding.cross_end,
-            FillType::Minimize => cache.unwrap_or_else(|| {
-                let (_, sub_minimizing_length_cross) = find_minimizing_length_relative(
-                    constraint_tree,
-                    constraint_node.child_keys,
-                    constraint_node.value.content.direction,
-                    remaining_length_main,
-                    max_length_cross,
+            ResolvedFillType::Exact(exact_cross) => exact_cross,
+            ResolvedFillType::Scale(..) => (relative_padding.cross_start
+                + relative_padding.cross_end)
+                .max(relative_bounds.min_cross),
+            ResolvedFillType::Minimize => cache.unwrap_or_else(|| {
+                let sub_bound_main = match relative_fill.main {
+                    ResolvedFillType::Exact(..) => Bound::Exactly(length_main),
+                    _ => Bound::AtMost(remaining_length_main),
+                };
+                let (_, sub_minimizing_length_cross) = minimize(
+                    context,
+                    constraint_key,
+                    &constraint_node,
+                    direction,
+                    sub_bound_main,
+                    Bound::AtMost(max_length_cross),
                 );
                 sub_minimizing_length_cross
             }),
         };
 
         remaining_length_main -= length_main;
-        max_seen_length_cross = max_seen_length_cross.max(length_cross);
+        used_length_main += length_main;
+        max_seen_length_cross =
+            max_seen_length_cross.max(length_cross + relative_margin.length_cross());
+        grouped_lengths_main.push((constraint_node.value.group, Some(length_main)));
     }
 
-    let minimizing_length_main = max_length_main - remaining_length_main;
+    let grouped_length_main = equalize_groups(
+        grouped_lengths_main
+            .iter_mut()
+            .map(|(group, length_main)| (*group, length_main))
+            .collect(),
+    );
+
+    let minimizing_length_main = (used_length_main + grouped_length_main).min(max_length_main);
     let minimizing_length_cross = max_seen_length_cross.min(max_length_cross);
 
+    context.stop_timer(parent_constraint_key, start);
+
     (minimizing_length_main, minimizing_length_cross)
 }
 
+fn equalize_groups(grouped_lengths_main: Vec<(Option<u32>, &mut Option<f64>)>) -> f64 {
+    let mut max_lengths_main = BTreeMap::<u32, f64>::new();
+
+    for (group, length_main) in &grouped_lengths_main {
+        if let (Some(group), Some(length_main)) = (group, length_main) {
+            let max_length_main = max_lengths_main.entry(*group).or_default();
+            *max_length_main = max_length_main.max(*length_main);
+        };
+    }
+
+    let mut added_length_main = 0.;
+
+    for (group, length_main) in grouped_lengths_main {
+        if let (Some(group), Some(length_main)) = (group, length_main) {
+            let max_length_main = max_lengths_main[&group];
+            added_length_main += max_length_main - *length_main;
+            *length_main = max_length_main;
+        };
+    }
+
+    added_length_main
+}
+
+fn minimize(
+    context: &mut Context,
+    constraint_key: ConstraintKey,
+    constraint_node: &Node<'_, ConstraintKey, Constraint>,
+    direction: Direction,
+    bound_main: Bound,
+    bound_cross: Bound,
+) -> (f64, f64) {
+    let constraint = context.get_constraint(constraint_key);
+    let content = constraint.content;
+    let sub_direction = content.direction;
+    let (bound_x, bound_y) = relative_lengths(direction, bound_main, bound_cross);
+
+    if let (Some((_, measure)), Some(measure_context), true) = (
+        context.measures.get(&constraint_key),
+        context.measure_context.as_deref_mut(),
+        constraint_node.child_keys.is_empty(),
+    ) {
+        if let Some((length_x, length_y)) =
+            measure(measure_context, bound_x.max_length(), bound_y.max_length())
+        {
+            let length_x = fit_measured_length(length_x, bound_x);
+            let length_y = fit_measured_length(length_y, bound_y);
+            return relative_lengths(direction, length_x, length_y);
+        };
+    };
+
+    if let (Some(intrinsic_size), true) = (
+        constraint.intrinsic_size,
+        constraint_node.child_keys.is_empty(),
+    ) {
+        let (length_x, length_y) = fit_intrinsic_size(intrinsic_size, bound_x, bound_y);
+        return relative_lengths(direction, length_x, length_y);
+    };
+    let (sub_bound_main, sub_bound_cross) = relative_lengths(sub_direction, bound_x, bound_y);
+
+    let containing_frame = context.content_frame;
+    let relative_padding = content
+        .resolve_insets(containing_frame.length_x, containing_frame.length_y)
+        .to_relative_padding(sub_direction);
+    let padding_main = relative_padding.main_start + relative_padding.main_end;
+    let padding_cross = relative_padding.cross_start + relative_padding.cross_end;
+    let content_bound_main = sub_bound_main.shrink(padding_main);
+    let content_bound_cross = sub_bound_cross.shrink(padding_cross);
+    context.content_frame = RelativeFrame {
+        offset_main: relative_padding.main_start,
+        length_main: finite_length(content_bound_main.max_length()),
+        offset_cross: relative_padding.cross_start,
+        length_cross: finite_length(content_bound_cross.max_length()),
+    }
+    .to_frame(sub_direction);
+
+    let (sub_length_main, sub_length_cross) = find_minimizing_length_relative(
+        context,
+        constraint_key,
+        constraint_node.child_keys,
+        sub_direction,
+        content_bound_main,
+        content_bound_cross,
+    );
+    context.content_frame = containing_frame;
+    let sub_length_main = (sub_length_main + padding_main).min(sub_bound_main.max_length());
+    let sub_length_cross = (sub_length_cross + padding_cross).min(sub_bound_cross.max_length());
+
+    let (length_x, length_y) = relative_lengths(sub_direction, sub_length_main, sub_length_cross);
+    relative_lengths(direction, length_x, length_y)
+}
+
+struct Accumulator {
+    sum: f64,
+    compensation: f64,
+    is_compensated: bool,
+}
+
+impl Accumulator {
+    fn new(sum: f64, is_compensated: bool) -> Self {
+        Self {
+            sum,
+            compensation: 0.,
+            is_compensated,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        match self.is_compensated {
+            true => {
+                let compensated_value = value - self.compensation;
+                let sum = self.sum + compensated_value;
+                self.compensation = (sum - self.sum) - compensated_value;
+                self.sum = sum;
+            }
+            false => self.sum += value,
+        };
+    }
+
+    fn value(&self) -> f64 {
+        self.sum
+    }
+}
+
+pub(crate) fn partition_layout_ignored(
+    constraint_tree: &Tree<ConstraintKey, Constraint>,
+    constraint_keys: &IndexSet<ConstraintKey>,
+) -> (IndexSet<ConstraintKey>, Vec<ConstraintKey>) {
+    let mut ignored_constraint_keys = vec![];
+
+    let mut constraint_keys = iter(constraint_tree, constraint_keys)
+        .filter(|(_, constraint_node)| constraint_node.value.display != Display::None)
+        .filter_map(|(constraint_key, constraint_node)| {
+            match constraint_node.value.is_out_of_flow() {
+                true => {
+                    ignored_constraint_keys.push(constraint_key);
+                    None
+                }
+                false => Some((constraint_node.value.order, constraint_key)),
+            }
+        })
+        .collect::<Vec<_>>();
+    constraint_keys.sort_by_key(|&(order, _)| order);
+
+    let constraint_keys = constraint_keys
+        .into_iter()
+        .map(|(_, constraint_key)| constraint_key)
+        .collect();
+    (constraint_keys, ignored_constraint_keys)
+}
+
+fn collapse_margins(relative_margins: &mut [RelativePadding]) {
+    for index in 1..relative_margins.len() {
+        let previous_margin_main_end = relative_margins[index - 1].main_end;
+        let relative_margin = &mut relative_margins[index];
+        let margin_main_start = relative_margin.main_start;
+        let collapsed_margin_main = previous_margin_main_end.max(margin_main_start).max(0.)
+            + previous_margin_main_end.min(margin_main_start).min(0.);
+        relative_margin.main_start = collapsed_margin_main - previous_margin_main_end;
+    }
+}
+
+pub(super) fn partition_hidden(
+    constraint_tree: &Tree<ConstraintKey, Constraint>,
+    constraint_keys: &IndexSet<ConstraintKey>,
+) -> Vec<ConstraintKey> {
+    iter(constraint_tree, constraint_keys)
+        .filter(|(_, constraint_node)| constraint_node.value.display == Display::None)
+        .map(|(constraint_key, _)| constraint_key)
+        .collect()
+}
+
 fn iter<'a>(
     constraint_tree: &'a Tree<ConstraintKey, Constraint>,
     constraint_keys: &'a IndexSet<ConstraintKey>,
@@ -298,3 +2234,26 @@ fn iter<'a>(
         (constraint_key, constraint_node)
     })
 }
+
+fn pre_order(
+    constraint_tree: &Tree<ConstraintKey, Constraint>,
+    constraint_key: ConstraintKey,
+) -> Vec<ConstraintKey> {
+    let mut constraint_keys = vec![];
+    let mut to_visit_constraint_keys = vec![constraint_key];
+
+    while let Some(constraint_key) = to_visit_constraint_keys.pop() {
+        constraint_keys.push(constraint_key);
+        let constraint_node = constraint_tree.get(constraint_key).unwrap();
+        to_visit_constraint_keys.extend(constraint_node.child_keys.iter().rev());
+    }
+
+    constraint_keys
+}
+
+fn relative_lengths<T>(direction: Direction, length_a: T, length_b: T) -> (T, T) {
+    match direction {
+        Direction::Horizontal => (length_a, length_b),
+        Direction::Vertical => (length_b, length_a),
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{solver::Context, ConstraintKey};
+
+pub(super) struct ScrollAnchor {
+    constraint_key: ConstraintKey,
+    anchor_constraint_key: ConstraintKey,
+    delta_x: f64,
+    delta_y: f64,
+}
+
+pub(super) fn capture_scroll_anchors(context: &Context) -> Vec<ScrollAnchor> {
+    context
+        .scrolls
+        .iter()
+        .filter(|(&constraint_key, scroll)| {
+            scroll.is_anchoring
+                && context.constraint_tree.contains(constraint_key)
+                && context.key_map.contains_key(&constraint_key)
+        })
+        .filter_map(|(&constraint_key, scroll)| {
+            let constraint_node = context.constraint_tree.get(constraint_key).unwrap();
+            constraint_node
+                .child_keys
+                .iter()
+                .filter(|child_constraint_key| context.key_map.contains_key(child_constraint_key))
+                .map(|&child_constraint_key| {
+                    (
+                        child_constraint_key,
+                        context.get_frame(child_constraint_key),
+                    )
+                })
+                .find(|(_, frame)| {
+                    frame.offset_x + frame.length_x > scroll.offset_x
+                        && frame.offset_y + frame.length_y > scroll.offset_y
+                })
+                .map(|(anchor_constraint_key, frame)| ScrollAnchor {
+                    constraint_key,
+                    anchor_constraint_key,
+                    delta_x: frame.offset_x - scroll.offset_x,
+                    delta_y: frame.offset_y - scroll.offset_y,
+                })
+        })
+        .collect()
+}
+
+pub(super) fn apply_scroll_anchors(context: &mut Context, scroll_anchors: Vec<ScrollAnchor>) {
+    for scroll_anchor in scroll_anchors {
+        let is_still_child = context
+            .parent_keys
+            .get(&scroll_anchor.anchor_constraint_key)
+            == Some(&scroll_anchor.constraint_key);
+        if !is_still_child
+            || !context
+                .key_map
+                .contains_key(&scroll_anchor.anchor_constraint_key)
+        {
+            continue;
+        };
+
+        let frame = context.get_frame(scroll_anchor.anchor_constraint_key);
+        let scroll = context
+            .scrolls
+            .get_mut(&scroll_anchor.constraint_key)
+            .unwrap();
+        scroll.offset_x = (frame.offset_x - scroll_anchor.delta_x).max(0.);
+        scroll.offset_y = (frame.offset_y - scroll_anchor.delta_y).max(0.);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{solver::Context, ConstraintKey, Frame, FrameI32, RoundingPolicy};
+
+type ToVisit = (ConstraintKey, (f64, f64), (i32, i32));
+
+pub(super) fn snap(context: &mut Context) {
+    context.physical_frames.clear();
+    context.frames_i32.clear();
+
+    let to_visit = context
+        .constraint_tree
+        .root_key()
+        .map(|root_constraint_key| (root_constraint_key, (0., 0.), (0, 0)))
+        .into_iter()
+        .collect();
+
+    snap_from(context, to_visit);
+}
+
+pub(super) fn snap_subtree(context: &mut Context, constraint_key: ConstraintKey) {
+    let scale_factor = context.scale_factor;
+    let rounding_policy = context.rounding_policy;
+    let (parent_x, parent_y) = context.parent_transform(constraint_key).apply(0., 0.);
+    let parent_x = parent_x * scale_factor;
+    let parent_y = parent_y * scale_factor;
+    let parent_snapped = (
+        rounding_policy.round_start(parent_x),
+        rounding_policy.round_start(parent_y),
+    );
+
+    snap_from(
+        context,
+        vec![(constraint_key, (parent_x, parent_y), parent_snapped)],
+    );
+}
+
+fn snap_from(context: &mut Context, mut to_visit: Vec<ToVisit>) {
+    let constraint_tree = context.constraint_tree;
+    let scale_factor = context.scale_factor;
+    let rounding_policy = context.rounding_policy;
+
+    while let Some((constraint_key, (parent_x, parent_y), (parent_snapped_x, parent_snapped_y))) =
+        to_visit.pop()
+    {
+        let frame = context.get_frame(constraint_key);
+
+        let start_x = parent_x + frame.offset_x * scale_factor;
+        let start_y = parent_y + frame.offset_y * scale_factor;
+        let end_x = start_x + frame.length_x * scale_factor;
+        let end_y = start_y + frame.length_y * scale_factor;
+
+        let physical_frame = Frame {
+            offset_x: start_x - parent_x,
+            length_x: end_x - start_x,
+            offset_y: start_y - parent_y,
+            length_y: end_y - start_y,
+        };
+        context
+            .physical_frames
+            .insert(constraint_key, physical_frame);
+
+        let snapped_start_x = rounding_policy.round_start(start_x);
+        let snapped_start_y = rounding_policy.round_start(start_y);
+        let snapped_end_x = rounding_policy.round_end(end_x);
+        let snapped_end_y = rounding_policy.round_end(end_y);
+
+        let frame_i32 = FrameI32 {
+            x: snapped_start_x - parent_snapped_x,
+            y: snapped_start_y - parent_snapped_y,
+            w: snapped_end_x - snapped_start_x,
+            h: snapped_end_y - snapped_start_y,
+        };
+        context.frames_i32.insert(constraint_key, frame_i32);
+
+        let constraint_node = constraint_tree.get(constraint_key).unwrap();
+        to_visit.extend(
+            constraint_node
+                .child_keys
+                .iter()
+                .map(|&child_constraint_key| {
+                    (
+                        child_constraint_key,
+                        (start_x, start_y),
+                        (snapped_start_x, snapped_start_y),
+                    )
+                }),
+        );
+    }
+}
+
+impl RoundingPolicy {
+    fn round_start(self, value: f64) -> i32 {
+        match self {
+            Self::Round => value.round() as i32,
+            Self::RoundHalfEven => value.round_ties_even() as i32,
+            Self::FloorOffsetsCeilExtents => value.floor() as i32,
+            Self::Truncate => value.trunc() as i32,
+        }
+    }
+
+    fn round_end(self, value: f64) -> i32 {
+        match self {
+            Self::FloorOffsetsCeilExtents => value.ceil() as i32,
+            _ => self.round_start(value),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use indexmap::IndexSet;
+
+use crate::{
+    solver::{iter, solve_children, Context},
+    ConstraintKey, Content, FrameKey, RelativeFrame,
+};
+
+pub(super) fn solve_child_keys_split(
+    context: &mut Context,
+    parent_constraint_key: ConstraintKey,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    parent_frame_key: FrameKey,
+    relative_content_frame: RelativeFrame,
+    parent_content: Content,
+) {
+    let constraint_tree = context.constraint_tree;
+    let direction = parent_content.direction;
+    let length_main = relative_content_frame.length_main;
+
+    let min_lengths_main = constraint_keys
+        .iter()
+        .map(|&constraint_key| {
+            context
+                .get_constraint(constraint_key)
+                .bounds
+                .to_relative_bounds(direction)
+                .min_main
+        })
+        .collect::<Vec<_>>();
+    let ratios = context
+        .splits
+        .get(&parent_constraint_key)
+        .map_or(&[][..], Vec::as_slice);
+    let positions = divider_positions(ratios, &min_lengths_main, length_main);
+
+    for (index, (constraint_key, constraint_node)) in
+        iter(constraint_tree, constraint_keys).enumerate()
+    {
+        let start = context.start_timer();
+
+        let pane_start = match index {
+            0 => 0.,
+            _ => positions[index - 1],
+        };
+        let pane_end = positions.get(index).copied().unwrap_or(length_main);
+
+        let relative_frame = RelativeFrame {
+            offset_main: relative_content_frame.offset_main + pane_start,
+            length_main: (pane_end - pane_start).max(0.),
+            ..relative_content_frame
+        };
+
+        let number_of_child_keys = constraint_node.child_keys.len();
+        let frame = relative_frame.to_frame(direction);
+        let frame_key = context.insert_frame(
+            constraint_key,
+            frame,
+            parent_frame_key,
+            number_of_child_keys,
+        );
+
+        solve_children(context, constraint_key, &constraint_node, frame_key, frame);
+
+        context.stop_timer(constraint_key, start);
+    }
+}
+
+pub(crate) fn divider_positions(
+    ratios: &[f64],
+    min_lengths_main: &[f64],
+    length_main: f64,
+) -> Vec<f64> {
+    let number_of_panes = min_lengths_main.len();
+    let number_of_dividers = number_of_panes.saturating_sub(1);
+
+    let mut positions = match ratios.len() == number_of_dividers {
+        true => ratios.iter().map(|ratio| ratio * length_main).collect(),
+        false => (1..number_of_panes)
+            .map(|index| index as f64 / number_of_panes as f64 * length_main)
+            .collect::<Vec<_>>(),
+    };
+
+    let mut lower_bound = 0.;
+    for (index, position) in positions.iter_mut().enumerate() {
+        lower_bound += min_lengths_main[index];
+        *position = position.max(lower_bound);
+        lower_bound = *position;
+    }
+
+    let mut upper_bound = length_main;
+    for (index, position) in positions.iter_mut().enumerate().rev() {
+        upper_bound -= min_lengths_main[index + 1];
+        *position = position.min(upper_bound).max(0.);
+        upper_bound = *position;
+    }
+
+    positions
+}
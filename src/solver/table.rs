@@ -0,0 +1,262 @@
+use indexmap::IndexSet;
+
+use crate::{
+    exact_gap,
+    solver::{
+        bound_length, collapse::solve_collapsed_child_keys, iter, minimize, partition_hidden,
+        partition_layout_ignored, solve_children, solve_ignored_child_keys, Bound, Context,
+    },
+    ConstraintKey, Content, Direction, Frame, FrameKey, RelativeFrame, ResolvedFillType,
+};
+
+pub(super) fn solve_child_keys_table(
+    context: &mut Context,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    parent_frame_key: FrameKey,
+    relative_content_frame: RelativeFrame,
+    parent_content: Content,
+) {
+    let constraint_tree = context.constraint_tree;
+    let direction = parent_content.direction;
+    let gap_length_main = exact_gap(parent_content.gap_main, relative_content_frame.length_main);
+    let gap_length_cross = exact_gap(
+        parent_content.gap_cross,
+        relative_content_frame.length_cross,
+    );
+    let (column_lengths, row_lengths) = measure_table(
+        context,
+        constraint_keys,
+        direction,
+        gap_length_cross,
+        relative_content_frame.length_main,
+        relative_content_frame.length_cross,
+        false,
+    );
+
+    let mut offset_main = relative_content_frame.offset_main;
+
+    for ((row_constraint_key, row_constraint_node), &row_length_main) in
+        iter(constraint_tree, constraint_keys).zip(&row_lengths)
+    {
+        let start = context.start_timer();
+        let row_relative_frame = RelativeFrame {
+            offset_main,
+            length_main: row_length_main,
+            ..relative_content_frame
+        };
+        offset_main += row_length_main + gap_length_main;
+
+        let row_frame = row_relative_frame.to_frame(direction);
+        let row_frame_key = context.insert_frame(
+            row_constraint_key,
+            row_frame,
+            parent_frame_key,
+            row_constraint_node.child_keys.len(),
+        );
+
+        let (cell_constraint_keys, ignored_cell_constraint_keys) =
+            partition_layout_ignored(constraint_tree, row_constraint_node.child_keys);
+        let mut offset_cross = 0.;
+        let containing_frame = context.content_frame;
+        context.content_frame = Frame {
+            offset_x: 0.,
+            offset_y: 0.,
+            ..row_frame
+        };
+
+        for ((constraint_key, constraint_node), &column_length_cross) in
+            iter(constraint_tree, &cell_constraint_keys).zip(&column_lengths)
+        {
+            let start = context.start_timer();
+            let relative_fill = context
+                .get_constraint(constraint_key)
+                .fill
+                .to_relative_fill(direction);
+            let relative_frame = RelativeFrame {
+                offset_main: 0.,
+                length_main: bound_length(relative_fill.main, row_length_main, true)
+                    .unwrap_or(row_length_main),
+                offset_cross,
+                length_cross: bound_length(relative_fill.cross, column_length_cross, true)
+                    .unwrap_or(column_length_cross),
+            };
+            offset_cross += column_length_cross + gap_length_cross;
+
+            let number_of_child_keys = constraint_node.child_keys.len();
+            let frame = relative_frame.to_frame(direction);
+            let frame_key =
+                context.insert_frame(constraint_key, frame, row_frame_key, number_of_child_keys);
+
+            solve_children(context, constraint_key, &constraint_node, frame_key, frame);
+
+            context.stop_timer(constraint_key, start);
+        }
+
+        let row_content = context.get_constraint(row_constraint_key).content;
+        let row_content_frame = RelativeFrame {
+            offset_main: 0.,
+            offset_cross: 0.,
+            ..row_frame.to_relative_frame(row_content.direction)
+        };
+        solve_ignored_child_keys(
+            context,
+            &ignored_cell_constraint_keys,
+            row_frame_key,
+            row_content_frame,
+            row_content.direction,
+            None,
+        );
+        solve_collapsed_child_keys(
+            context,
+            &partition_hidden(constraint_tree, row_constraint_node.child_keys),
+            row_frame_key,
+            row_content_frame,
+            row_content,
+        );
+        context.content_frame = containing_frame;
+
+        context.stop_timer(row_constraint_key, start);
+    }
+}
+
+pub(super) fn find_minimizing_length_table(
+    context: &mut Context,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    parent_content: Content,
+    direction: Direction,
+    bound_main: Bound,
+    bound_cross: Bound,
+) -> (f64, f64) {
+    let max_length_main = bound_main.max_length();
+    let max_length_cross = bound_cross.max_length();
+    let gap_length_main = exact_gap(parent_content.gap_main, max_length_main);
+    let gap_length_cross = exact_gap(parent_content.gap_cross, max_length_cross);
+    let (column_lengths, row_lengths) = measure_table(
+        context,
+        constraint_keys,
+        direction,
+        gap_length_cross,
+        max_length_main,
+        max_length_cross,
+        true,
+    );
+
+    let length_main = total_length(&row_lengths, gap_length_main);
+    let length_cross = total_length(&column_lengths, gap_length_cross);
+
+    (
+        length_main.min(max_length_main),
+        length_cross.min(max_length_cross),
+    )
+}
+
+fn measure_table(
+    context: &mut Context,
+    constraint_keys: &IndexSet<ConstraintKey>,
+    direction: Direction,
+    gap_length_cross: f64,
+    length_main: f64,
+    length_cross: f64,
+    is_minimizing: bool,
+) -> (Vec<f64>, Vec<f64>) {
+    let constraint_tree = context.constraint_tree;
+    let rows = iter(constraint_tree, constraint_keys)
+        .map(|(constraint_key, constraint_node)| {
+            let (cell_constraint_keys, _) =
+                partition_layout_ignored(constraint_tree, constraint_node.child_keys);
+            (constraint_key, cell_constraint_keys)
+        })
+        .collect::<Vec<_>>();
+
+    let mut column_lengths = vec![];
+    for (_, cell_constraint_keys) in &rows {
+        for (index, (constraint_key, constraint_node)) in
+            iter(constraint_tree, cell_constraint_keys).enumerate()
+        {
+            let relative_fill = context
+                .get_constraint(constraint_key)
+                .fill
+                .to_relative_fill(direction)
+                .resolve(length_main, length_cross);
+            let min_length_cross = match relative_fill.cross {
+                ResolvedFillType::Exact(exact_cross) => exact_cross,
+                ResolvedFillType::Scale(..) | ResolvedFillType::Minimize => {
+                    let (_, minimizing_length_cross) = minimize(
+                        context,
+                        constraint_key,
+                        &constraint_node,
+                        direction,
+                        Bound::AtMost(length_main),
+                        Bound::AtMost(length_cross),
+                    );
+                    minimizing_length_cross
+                }
+            };
+            match column_lengths.get_mut(index) {
+                Some(column_length) => *column_length = min_length_cross.max(*column_length),
+                None => column_lengths.push(min_length_cross),
+            };
+        }
+    }
+
+    let remaining_length_cross = length_cross - total_length(&column_lengths, gap_length_cross);
+    if !is_minimizing && remaining_length_cross > 0. && !column_lengths.is_empty() {
+        let min_length_cross = column_lengths.iter().sum::<f64>();
+        let number_of_columns = column_lengths.len() as f64;
+        for column_length in &mut column_lengths {
+            *column_length += match min_length_cross > 0. {
+                true => remaining_length_cross * *column_length / min_length_cross,
+                false => remaining_length_cross / number_of_columns,
+            };
+        }
+    };
+
+    let row_lengths = rows
+        .iter()
+        .map(|(row_constraint_key, cell_constraint_keys)| {
+            let relative_fill = context
+                .get_constraint(*row_constraint_key)
+                .fill
+                .to_relative_fill(direction)
+                .resolve(length_main, length_cross);
+            if let ResolvedFillType::Exact(exact_main) = relative_fill.main {
+                return exact_main;
+            };
+
+            iter(constraint_tree, cell_constraint_keys)
+                .zip(&column_lengths)
+                .map(|((constraint_key, constraint_node), &column_length)| {
+                    let constraint = context.get_constraint(constraint_key);
+                    let relative_fill = constraint
+                        .fill
+                        .to_relative_fill(direction)
+                        .resolve(length_main, column_length);
+                    let basis_main = match relative_fill.main {
+                        ResolvedFillType::Scale(..) => constraint.basis.unwrap_or_default(),
+                        _ => 0.,
+                    };
+                    match relative_fill.main {
+                        ResolvedFillType::Exact(exact_main) => exact_main,
+                        ResolvedFillType::Scale(..) | ResolvedFillType::Minimize => {
+                            let (minimizing_length_main, _) = minimize(
+                                context,
+                                constraint_key,
+                                &constraint_node,
+                                direction,
+                                Bound::AtMost(length_main),
+                                Bound::Exactly(column_length),
+                            );
+                            minimizing_length_main.max(basis_main)
+                        }
+                    }
+                })
+                .fold(0., f64::max)
+        })
+        .collect();
+
+    (column_lengths, row_lengths)
+}
+
+fn total_length(lengths: &[f64], gap_length: f64) -> f64 {
+    lengths.iter().sum::<f64>() + gap_length * lengths.len().saturating_sub(1) as f64
+}
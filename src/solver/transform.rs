@@ -0,0 +1,55 @@
+use crate::{solver::Context, ConstraintKey, Transform};
+
+pub(super) fn compose(context: &mut Context) {
+    context.transforms.clear();
+
+    let to_visit = context
+        .constraint_tree
+        .root_key()
+        .map(|root_constraint_key| (root_constraint_key, Transform::IDENTITY))
+        .into_iter()
+        .collect();
+
+    compose_from(context, to_visit);
+}
+
+pub(super) fn compose_subtree(context: &mut Context, constraint_key: ConstraintKey) {
+    let parent_transform = context.parent_keys.get(&constraint_key).map_or(
+        Transform::IDENTITY,
+        |&parent_constraint_key| {
+            scrolled(
+                context,
+                parent_constraint_key,
+                context.parent_transform(constraint_key),
+            )
+        },
+    );
+    compose_from(context, vec![(constraint_key, parent_transform)]);
+}
+
+fn scrolled(context: &Context, constraint_key: ConstraintKey, transform: Transform) -> Transform {
+    match context.scrolls.get(&constraint_key) {
+        Some(scroll) => Transform::translation(-scroll.offset_x, -scroll.offset_y).then(transform),
+        None => transform,
+    }
+}
+
+fn compose_from(context: &mut Context, mut to_visit: Vec<(ConstraintKey, Transform)>) {
+    let constraint_tree = context.constraint_tree;
+
+    while let Some((constraint_key, parent_transform)) = to_visit.pop() {
+        let frame = context.get_frame(constraint_key);
+        let transform =
+            Transform::translation(frame.offset_x, frame.offset_y).then(parent_transform);
+        context.transforms.insert(constraint_key, transform);
+
+        let constraint_node = constraint_tree.get(constraint_key).unwrap();
+        let child_parent_transform = scrolled(context, constraint_key, transform);
+        to_visit.extend(
+            constraint_node
+                .child_keys
+                .iter()
+                .map(|&child_constraint_key| (child_constraint_key, child_parent_transform)),
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{solver::divider_positions, ConstraintKey, LayoutMode, Solver};
+
+impl Solver {
+    // Split methods:
+
+    pub fn divider(&self, constraint_key: ConstraintKey, index: usize) -> Option<f64> {
+        let (positions, _) = self.solved_divider_positions(constraint_key)?;
+        positions.get(index).copied()
+    }
+
+    pub fn set_divider(
+        &mut self,
+        constraint_key: ConstraintKey,
+        index: usize,
+        position: f64,
+    ) -> bool {
+        if !position.is_finite() {
+            return false;
+        };
+        let Some((mut positions, length_main)) = self.solved_divider_positions(constraint_key)
+        else {
+            return false;
+        };
+        let Some(divider_position) = positions.get_mut(index) else {
+            return false;
+        };
+
+        *divider_position = position;
+        let ratios = match length_main > 0. {
+            true => positions
+                .iter()
+                .map(|position| position / length_main)
+                .collect(),
+            false => vec![],
+        };
+        self.splits.insert(constraint_key, ratios);
+        self.mark_dirty(constraint_key, self.region(constraint_key));
+        true
+    }
+
+    fn solved_divider_positions(&self, constraint_key: ConstraintKey) -> Option<(Vec<f64>, f64)> {
+        let frame = self.get_last_frame(constraint_key)?;
+        let content = self.resolved_constraint(constraint_key).content;
+        if content.mode != LayoutMode::Split {
+            return None;
+        };
+        let ratios = self
+            .splits
+            .get(&constraint_key)
+            .map_or(&[][..], Vec::as_slice);
+        let relative_padding = self
+            .resolved_insets(constraint_key)
+            .to_relative_padding(content.direction);
+        let relative_frame = frame.to_relative_frame(content.direction);
+        let length_main =
+            (relative_frame.length_main - relative_padding.main_start - relative_padding.main_end)
+                .max(0.);
+
+        let min_lengths_main = self
+            .laid_out_child_keys(constraint_key)?
+            .iter()
+            .map(|&child_constraint_key| {
+                self.resolved_constraint(child_constraint_key)
+                    .bounds
+                    .to_relative_bounds(content.direction)
+                    .min_main
+            })
+            .collect::<Vec<_>>();
+
+        let positions = divider_positions(ratios, &min_lengths_main, length_main);
+        Some((positions, length_main))
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    is_valid_root, Align, AlignFraction, Constraint, ConstraintKey, Fill, FillType, Padding,
+    Position, Solver,
+};
+
+impl Solver {
+    // Staging methods:
+
+    pub fn stage(&mut self, constraint_key: ConstraintKey, constraint: Constraint) -> bool {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key {
+            self.staged.insert(constraint_key, constraint);
+        };
+        contains_constraint_key
+    }
+
+    pub fn staged(&self, constraint_key: ConstraintKey) -> Option<Constraint> {
+        self.staged.get(&constraint_key).copied()
+    }
+
+    pub fn validate_staged(&self) -> Vec<ConstraintKey> {
+        self.staged
+            .iter()
+            .filter(|&(&constraint_key, &constraint)| {
+                !self.is_valid_edit(constraint_key, constraint)
+            })
+            .map(|(&constraint_key, _)| constraint_key)
+            .collect()
+    }
+
+    pub fn commit(&mut self) -> bool {
+        let is_valid = self.validate_staged().is_empty();
+        if is_valid {
+            for (constraint_key, constraint) in std::mem::take(&mut self.staged) {
+                self.set(constraint_key, constraint);
+            }
+        };
+        is_valid
+    }
+
+    pub fn discard(&mut self) {
+        self.staged.clear();
+    }
+
+    fn is_valid_edit(&self, constraint_key: ConstraintKey, constraint: Constraint) -> bool {
+        let is_root = self.constraint_tree.root_key() == Some(constraint_key);
+
+        self.constraint_tree.contains(constraint_key)
+            && (!is_root || is_valid_root(constraint))
+            && !has_nan(constraint)
+    }
+}
+
+fn has_nan(constraint: Constraint) -> bool {
+    let content = constraint.content;
+    let fill_types = match constraint.fill {
+        Fill::Absolute { x, y } => [x, y],
+        Fill::Relative { main, cross } => [main, cross],
+    };
+    let aligns = [
+        Some(content.align_main),
+        Some(content.align_cross),
+        content.align_last_line,
+        content.overflow_anchor,
+        constraint.align_self,
+        constraint.justify_self,
+    ];
+    let (intrinsic_x, intrinsic_y) = constraint.intrinsic_size.unwrap_or_default();
+    let insets = match constraint.position {
+        Position::Flow => [None; 4],
+        Position::Absolute {
+            left,
+            right,
+            top,
+            bottom,
+        } => [left, right, top, bottom],
+    };
+
+    fill_types
+        .into_iter()
+        .chain(content.gap_main)
+        .chain(content.gap_cross)
+        .any(|fill_type| match fill_type {
+            FillType::Exact(length) | FillType::Fraction(length) => length.is_nan(),
+            FillType::Bounded {
+                min,
+                preferred,
+                max,
+            } => min.is_nan() || preferred.is_nan() || max.is_nan(),
+            FillType::Scale(..) | FillType::Minimize => false,
+        })
+        || aligns.into_iter().flatten().any(
+            |align| matches!(align, Align::Fraction(AlignFraction(fraction)) if fraction.is_nan()),
+        )
+        || padding_has_nan(content.padding)
+        || padding_has_nan(content.padding_fraction)
+        || padding_has_nan(content.border)
+        || padding_has_nan(constraint.margin)
+        || padding_has_nan(constraint.hit_slop)
+        || constraint.basis.is_some_and(f64::is_nan)
+        || constraint.shrink.is_some_and(f64::is_nan)
+        || constraint.nudge_x.is_nan()
+        || constraint.nudge_y.is_nan()
+        || constraint.corner_radius.is_nan()
+        || constraint.bounds.min_x.is_nan()
+        || constraint.bounds.min_y.is_nan()
+        || constraint.bounds.max_x.is_some_and(f64::is_nan)
+        || constraint.bounds.max_y.is_some_and(f64::is_nan)
+        || insets.into_iter().flatten().any(f64::is_nan)
+        || intrinsic_x.is_nan()
+        || intrinsic_y.is_nan()
+}
+
+fn padding_has_nan(padding: Padding) -> bool {
+    [
+        padding.left,
+        padding.right,
+        padding.top,
+        padding.bottom,
+        padding.start,
+        padding.end,
+    ]
+    .into_iter()
+    .any(f64::is_nan)
+}
@@ -0,0 +1,109 @@
+use crate::{ConstraintKey, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self {
+        a: 1.,
+        b: 0.,
+        c: 0.,
+        d: 1.,
+        e: 0.,
+        f: 0.,
+    };
+
+    pub fn translation(x: f64, y: f64) -> Self {
+        Self {
+            e: x,
+            f: y,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn then(self, other: Self) -> Self {
+        Self {
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            e: other.a * self.e + other.c * self.f + other.e,
+            f: other.b * self.e + other.d * self.f + other.f,
+        }
+    }
+
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.a * self.d - self.b * self.c;
+
+        (determinant != 0. && determinant.is_finite()).then(|| {
+            let a = self.d / determinant;
+            let b = -self.b / determinant;
+            let c = -self.c / determinant;
+            let d = self.a / determinant;
+
+            Self {
+                a,
+                b,
+                c,
+                d,
+                e: -(a * self.e + c * self.f),
+                f: -(b * self.e + d * self.f),
+            }
+        })
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Solver {
+    // Transform methods:
+
+    pub fn get_transform(&self, constraint_key: ConstraintKey) -> Option<Transform> {
+        match self.is_dirty || self.is_frame_dirty(constraint_key) {
+            true => None,
+            false => self.transforms.get(&constraint_key).copied(),
+        }
+    }
+
+    pub fn convert_point(
+        &self,
+        from_constraint_key: ConstraintKey,
+        to_constraint_key: ConstraintKey,
+        x: f64,
+        y: f64,
+    ) -> Option<(f64, f64)> {
+        let from_transform = self.get_transform(from_constraint_key)?;
+        let to_transform = self.get_transform(to_constraint_key)?.inverse()?;
+        let (world_x, world_y) = from_transform.apply(x, y);
+        Some(to_transform.apply(world_x, world_y))
+    }
+
+    pub fn to_local(&self, constraint_key: ConstraintKey, x: f64, y: f64) -> Option<(f64, f64)> {
+        let transform = self.get_transform(constraint_key)?.inverse()?;
+        Some(transform.apply(x, y))
+    }
+
+    pub fn to_world(&self, constraint_key: ConstraintKey, x: f64, y: f64) -> Option<(f64, f64)> {
+        let transform = self.get_transform(constraint_key)?;
+        Some(transform.apply(x, y))
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use crate::{ConstraintKey, Solver};
+
+impl Solver {
+    // Traversal methods:
+
+    pub fn assign_traversal_indices(&mut self) -> bool {
+        self.traversal_indices.get_or_insert_with(BTreeMap::default);
+        !self.is_dirty() && self.refresh_traversal_indices()
+    }
+
+    pub fn get_traversal_index(&self, constraint_key: ConstraintKey) -> Option<usize> {
+        self.traversal_indices
+            .as_ref()?
+            .get(&constraint_key)
+            .copied()
+    }
+
+    pub(crate) fn refresh_traversal_indices(&mut self) -> bool {
+        if self.traversal_indices.is_none() || self.is_dirty {
+            return false;
+        };
+
+        let traversal_indices = self
+            .constraint_tree
+            .root_key()
+            .map(|root_constraint_key| self.traversal_order(root_constraint_key))
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(traversal_index, constraint_key)| (constraint_key, traversal_index))
+            .collect();
+
+        self.traversal_indices = Some(traversal_indices);
+        true
+    }
+
+    pub(crate) fn refresh_traversal_subtree(&mut self, constraint_key: ConstraintKey) -> bool {
+        if self.is_dirty {
+            return false;
+        };
+        let Some(traversal_indices) = &self.traversal_indices else {
+            return false;
+        };
+
+        let traversal_order = self.traversal_order(constraint_key);
+        let old_indices = traversal_order
+            .iter()
+            .map(|constraint_key| traversal_indices.get(constraint_key).copied())
+            .collect::<Option<Vec<_>>>();
+
+        // The subtree can only be renumbered in place if it still covers exactly the range of
+        // indices it was assigned last time; otherwise every following index shifts.
+        let start = match old_indices {
+            Some(old_indices) => {
+                let start = old_indices.iter().copied().min().unwrap_or_default();
+                let end = old_indices.iter().copied().max().unwrap_or_default();
+                let is_in_place =
+                    old_indices.first() == Some(&start) && end + 1 - start == traversal_order.len();
+                is_in_place.then_some(start)
+            }
+            None => None,
+        };
+        let Some(start) = start else {
+            return self.refresh_traversal_indices();
+        };
+
+        let traversal_indices = self.traversal_indices.as_mut().unwrap();
+        for (offset, constraint_key) in traversal_order.into_iter().enumerate() {
+            traversal_indices.insert(constraint_key, start + offset);
+        }
+        true
+    }
+
+    pub(crate) fn remove_traversal_indices(&mut self, constraint_keys: &[ConstraintKey]) {
+        let Some(traversal_indices) = &mut self.traversal_indices else {
+            return;
+        };
+
+        let mut removed_indices = constraint_keys
+            .iter()
+            .filter_map(|constraint_key| traversal_indices.remove(constraint_key))
+            .collect::<Vec<_>>();
+        removed_indices.sort_unstable();
+        for traversal_index in traversal_indices.values_mut() {
+            *traversal_index -= removed_indices.partition_point(|&index| index < *traversal_index);
+        }
+    }
+
+    fn traversal_order(&self, root_constraint_key: ConstraintKey) -> Vec<ConstraintKey> {
+        let mut traversal_order = vec![];
+        let mut to_visit = vec![root_constraint_key];
+
+        while let Some(constraint_key) = to_visit.pop() {
+            traversal_order.push(constraint_key);
+
+            let constraint_node = self.constraint_tree.get(constraint_key).unwrap();
+            let mut child_keys = constraint_node
+                .child_keys
+                .iter()
+                .map(|&child_constraint_key| {
+                    let (world_x, world_y) = self
+                        .to_world(child_constraint_key, 0., 0.)
+                        .unwrap_or_default();
+                    (child_constraint_key, world_x, world_y)
+                })
+                .collect::<Vec<_>>();
+            child_keys
+                .sort_by(|&(_, x1, y1), &(_, x2, y2)| y1.total_cmp(&y2).then(x1.total_cmp(&x2)));
+            to_visit.extend(
+                child_keys
+                    .into_iter()
+                    .rev()
+                    .map(|(child_constraint_key, _, _)| child_constraint_key),
+            );
+        }
+
+        traversal_order
+    }
+}
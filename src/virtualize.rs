@@ -0,0 +1,93 @@
+use crate::{exact_gap, ConstraintKey, Direction, Solver};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Virtualization {
+    pub(crate) estimated_child_extent: f64,
+    pub(crate) child_count: Option<usize>,
+}
+
+impl Solver {
+    // Virtualization methods:
+
+    pub fn set_estimated_child_extent(
+        &mut self,
+        constraint_key: ConstraintKey,
+        estimated_child_extent: f64,
+    ) -> bool {
+        let is_valid = self.constraint_tree.contains(constraint_key)
+            && estimated_child_extent.is_finite()
+            && estimated_child_extent >= 0.;
+        if is_valid {
+            self.virtualizations
+                .entry(constraint_key)
+                .or_default()
+                .estimated_child_extent = estimated_child_extent;
+        };
+        is_valid
+    }
+
+    pub fn set_virtual_child_count(
+        &mut self,
+        constraint_key: ConstraintKey,
+        child_count: usize,
+    ) -> bool {
+        let contains_constraint_key = self.constraint_tree.contains(constraint_key);
+        if contains_constraint_key {
+            self.virtualizations
+                .entry(constraint_key)
+                .or_default()
+                .child_count = Some(child_count);
+        };
+        contains_constraint_key
+    }
+
+    pub fn remove_virtualization(&mut self, constraint_key: ConstraintKey) -> bool {
+        self.virtualizations.remove(&constraint_key).is_some()
+    }
+
+    pub fn estimated_total_extent(&self, constraint_key: ConstraintKey) -> Option<f64> {
+        let virtualization = self.virtualizations.get(&constraint_key)?;
+        let content_box = self.get_content_box(constraint_key)?;
+
+        let constraint_node = self.constraint_tree.get(constraint_key)?;
+        let content = self.resolved_constraint(constraint_key).content;
+
+        let measured_lengths = constraint_node
+            .child_keys
+            .iter()
+            .filter(|&&child_constraint_key| {
+                !self
+                    .constraint_tree
+                    .get(child_constraint_key)
+                    .unwrap()
+                    .value
+                    .is_out_of_flow()
+            })
+            .filter_map(|&child_constraint_key| self.get_frame(child_constraint_key))
+            .map(|frame| match content.direction {
+                Direction::Horizontal => frame.length_x,
+                Direction::Vertical => frame.length_y,
+            })
+            .collect::<Vec<_>>();
+
+        let realized_count = measured_lengths.len();
+        let child_count = virtualization
+            .child_count
+            .unwrap_or(realized_count)
+            .max(realized_count);
+        let gap_length_main = exact_gap(
+            content.gap_main,
+            match content.direction {
+                Direction::Horizontal => content_box.length_x,
+                Direction::Vertical => content_box.length_y,
+            },
+        );
+
+        let measured_extent = measured_lengths.into_iter().sum::<f64>();
+        let estimated_extent =
+            virtualization.estimated_child_extent * (child_count - realized_count) as f64;
+        let gap_extent = gap_length_main * child_count.saturating_sub(1) as f64;
+
+        Some(measured_extent + estimated_extent + gap_extent)
+    }
+}
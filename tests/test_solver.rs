@@ -1,8 +1,21 @@
 #[path = "../common/mod.rs"]
 mod common;
 
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
 use common::{make_frame_tree, make_solver};
-use stretchbox::{Constraint, Fill, FillType, Frame};
+use stretchbox::{
+    conformance::{run_conformance, run_conformance_case, ConformanceOutcome, FrameDiff},
+    presets, Align, AlignFraction, Baseline, Bounds, ChildDefaults, Constraint, ConstraintKey,
+    Content, CustomLayout, Direction, Display, ExtensionMap, ExtensionValue, Fill, FillType, Frame,
+    FrameGradient, FrameI32, Grid, LayoutChild, LayoutDirection, LayoutId, LayoutMode, LineInfo,
+    LintDiagnostic, NodeId, Overflow, Padding, Phase, PipelinedSolver, Position, Precedence,
+    ReproNode, RoundingPolicy, Sensitivity, Solver, SolverBuilder, StructuralChange, Transform,
+    Visibility,
+};
 
 #[test]
 fn test_solver_with_empty_tree() {
@@ -35,3 +48,5694 @@ fn test_solver_with_single_element_tree() {
         Some(node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. }});
     assert_eq!(actual_frame_tree, expected_frame_tree);
 }
+
+#[test]
+fn test_solver_replays_repro_bundle() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint { fill: Fill::Relative { main: FillType::Exact(3.), cross: FillType::Scale(1) }, ..Default::default() } },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    solver.solve(20., 20.);
+    let bundle = solver.dump_repro();
+    assert_eq!(bundle.solve_lengths, Some((10., 10.)));
+
+    let mut replayed_solver = Solver::from_repro(bundle.clone()).unwrap();
+    assert!(!replayed_solver.is_dirty());
+    assert_eq!(replayed_solver.dump_repro(), bundle);
+    assert_eq!(make_frame_tree(&replayed_solver), make_frame_tree(&solver));
+
+    let (length_x, length_y) = bundle.solve_lengths.unwrap();
+    replayed_solver.solve(length_x, length_y);
+
+    let actual_frame_tree = make_frame_tree(&replayed_solver);
+    let expected_frame_tree = make_frame_tree(&solver);
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_replays_repro_bundle_settings() {
+    fn collect_keys(solver: &Solver, constraint_key: ConstraintKey, keys: &mut Vec<ConstraintKey>) {
+        keys.push(constraint_key);
+        for &child_constraint_key in solver.get(constraint_key).unwrap().child_keys.iter() {
+            collect_keys(solver, child_constraint_key, keys);
+        }
+    }
+    fn strip_frames(repro_node: &mut ReproNode) {
+        repro_node.frame = None;
+        repro_node.children.iter_mut().for_each(strip_frames);
+    }
+    fn assert_replays(solver: &Solver, replayed_solver: &Solver) {
+        let mut constraint_keys = vec![];
+        collect_keys(
+            solver,
+            solver.root_constraint_key().unwrap(),
+            &mut constraint_keys,
+        );
+        let mut replayed_constraint_keys = vec![];
+        collect_keys(
+            replayed_solver,
+            replayed_solver.root_constraint_key().unwrap(),
+            &mut replayed_constraint_keys,
+        );
+        assert_eq!(constraint_keys.len(), replayed_constraint_keys.len());
+
+        for (&constraint_key, &replayed_constraint_key) in
+            constraint_keys.iter().zip(&replayed_constraint_keys)
+        {
+            assert_eq!(
+                replayed_solver.get_frame(replayed_constraint_key),
+                solver.get_frame(constraint_key)
+            );
+            assert_eq!(
+                replayed_solver.get_physical_frame(replayed_constraint_key),
+                solver.get_physical_frame(constraint_key)
+            );
+            assert_eq!(
+                replayed_solver.get_frame_i32(replayed_constraint_key),
+                solver.get_frame_i32(constraint_key)
+            );
+            assert_eq!(
+                replayed_solver.get_transform(replayed_constraint_key),
+                solver.get_transform(constraint_key)
+            );
+            assert_eq!(
+                replayed_solver.estimated_total_extent(replayed_constraint_key),
+                solver.estimated_total_extent(constraint_key)
+            );
+            assert_eq!(
+                replayed_solver.is_clipping_hits(replayed_constraint_key),
+                solver.is_clipping_hits(constraint_key)
+            );
+        }
+    }
+
+    let split = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            mode: LayoutMode::Split,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let item = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(3.),
+            cross: FillType::Exact(2.),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { split, [
+        node! { Constraint::default(), [
+            node! { item },
+            node! { item },
+        ] },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let pane_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    solver.set_layout_direction(LayoutDirection::RightToLeft);
+    assert!(solver.set_scale_factor(1.5));
+    solver.set_rounding_policy(RoundingPolicy::FloorOffsetsCeilExtents);
+    assert!(solver.set_baseline_grid(Some(4.)));
+    solver.set_deterministic(true);
+    assert!(solver.set_scroll_offset(pane_constraint_key, 0., 1.));
+    assert!(solver.set_scroll_anchoring(pane_constraint_key, true));
+    assert!(solver.set_estimated_child_extent(pane_constraint_key, 5.));
+    assert!(solver.set_virtual_child_count(pane_constraint_key, 10));
+    assert!(solver.set_clipping_hits(pane_constraint_key, true));
+    solver.solve(11., 11.);
+    assert!(solver.set_divider(root_constraint_key, 0, 7.));
+    solver.solve(11., 11.);
+
+    let bundle = solver.dump_repro();
+    assert_eq!(bundle.layout_direction, LayoutDirection::RightToLeft);
+
+    let replayed_solver = Solver::from_repro(bundle.clone()).unwrap();
+    assert!(!replayed_solver.is_dirty());
+    assert_eq!(replayed_solver.dump_repro(), bundle);
+    assert_replays(&solver, &replayed_solver);
+
+    let mut unsolved_bundle = bundle.clone();
+    strip_frames(unsolved_bundle.root.as_mut().unwrap());
+    let mut replayed_solver = Solver::from_repro(unsolved_bundle).unwrap();
+    let (length_x, length_y) = bundle.solve_lengths.unwrap();
+    replayed_solver.solve(length_x, length_y);
+    assert_replays(&solver, &replayed_solver);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_solver_round_trips_repro_bundle_through_serde() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint { fill: Fill::Relative { main: FillType::Exact(3.), cross: FillType::Scale(1) }, basis: Some(2.), ..Default::default() } },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+    solver.solve(10., 10.);
+
+    let bundle = solver.dump_repro();
+    let json = serde_json::to_string(&bundle).unwrap();
+    assert_eq!(
+        serde_json::from_str::<stretchbox::ReproBundle>(&json).unwrap(),
+        bundle
+    );
+
+    let mut value = serde_json::to_value(&bundle).unwrap();
+    let constraint = value["root"]["children"][0]["constraint"]
+        .as_object_mut()
+        .unwrap();
+    constraint.remove("basis");
+    constraint.remove("order");
+    constraint.insert("future_field".to_string(), serde_json::json!(7));
+    value["root"]["children"][0]["constraint"]["content"]
+        .as_object_mut()
+        .unwrap()
+        .remove("gap_cross");
+
+    let older_bundle = serde_json::from_value::<stretchbox::ReproBundle>(value).unwrap();
+    let older_child = &older_bundle.root.as_ref().unwrap().children[0];
+    assert_eq!(older_child.constraint.constraint.basis, None);
+    assert_eq!(older_child.constraint.constraint.order, 0);
+    assert_eq!(
+        older_child.constraint.extensions.get("future_field"),
+        Some(&ExtensionValue::Integer(7)),
+    );
+
+    let replayed_solver = Solver::from_repro(older_bundle).unwrap();
+    let replayed_value = serde_json::to_value(replayed_solver.dump_repro()).unwrap();
+    assert_eq!(
+        replayed_value["root"]["children"][0]["constraint"]["future_field"],
+        serde_json::json!(7),
+    );
+}
+
+#[test]
+fn test_solver_restores_solve_results_from_repro_bundle() {
+    fn collect_keys(solver: &Solver, constraint_key: ConstraintKey, keys: &mut Vec<ConstraintKey>) {
+        keys.push(constraint_key);
+        for &child_constraint_key in solver.get(constraint_key).unwrap().child_keys.iter() {
+            collect_keys(solver, child_constraint_key, keys);
+        }
+    }
+
+    let item = |priority| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(4.),
+            cross: FillType::Scale(1),
+        },
+        priority,
+        ..Default::default()
+    };
+    let row = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            overflow: Overflow::Collapse,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let wrapped_row = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            mode: LayoutMode::Wrap,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { row, [
+            node! { item(Some(2)) },
+            node! { item(Some(1)) },
+            node! { item(None) },
+        ] },
+        node! { wrapped_row, [
+            node! { item(None) },
+            node! { item(None) },
+            node! { item(None) },
+        ] },
+    ] }))
+    .unwrap();
+    solver.assign_traversal_indices();
+    solver.solve(10., 4.);
+
+    let replayed_solver = Solver::from_repro(solver.dump_repro()).unwrap();
+    assert!(!replayed_solver.is_dirty());
+
+    let mut constraint_keys = vec![];
+    collect_keys(
+        &solver,
+        solver.root_constraint_key().unwrap(),
+        &mut constraint_keys,
+    );
+    let mut replayed_constraint_keys = vec![];
+    collect_keys(
+        &replayed_solver,
+        replayed_solver.root_constraint_key().unwrap(),
+        &mut replayed_constraint_keys,
+    );
+    assert_eq!(constraint_keys.len(), replayed_constraint_keys.len());
+
+    let row_constraint_key = constraint_keys[1];
+    let wrapped_row_constraint_key = constraint_keys[5];
+    assert_eq!(
+        solver.collapsed_children(row_constraint_key),
+        Some(&[constraint_keys[3]][..])
+    );
+    assert_eq!(solver.lines(wrapped_row_constraint_key).len(), 2);
+    assert!(solver.get_baseline(row_constraint_key).is_some());
+
+    for (&constraint_key, &replayed_constraint_key) in
+        constraint_keys.iter().zip(&replayed_constraint_keys)
+    {
+        assert_eq!(
+            replayed_solver.get_baseline(replayed_constraint_key),
+            solver.get_baseline(constraint_key)
+        );
+        assert_eq!(
+            replayed_solver.get_overflow(replayed_constraint_key),
+            solver.get_overflow(constraint_key)
+        );
+        assert_eq!(
+            replayed_solver.free_space(replayed_constraint_key),
+            solver.free_space(constraint_key)
+        );
+        assert_eq!(
+            replayed_solver.lines(replayed_constraint_key),
+            solver.lines(constraint_key)
+        );
+        assert_eq!(
+            replayed_solver.resize_sensitivity(replayed_constraint_key),
+            solver.resize_sensitivity(constraint_key)
+        );
+        assert_eq!(
+            replayed_solver.get_traversal_index(replayed_constraint_key),
+            solver.get_traversal_index(constraint_key)
+        );
+    }
+    assert_eq!(
+        replayed_solver.collapsed_children(replayed_constraint_keys[1]),
+        Some(&[replayed_constraint_keys[3]][..])
+    );
+}
+
+#[test]
+fn test_solver_round_trips_unknown_extensions() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+
+    let value = ExtensionValue::Map(ExtensionMap::from([(
+        "weight".to_string(),
+        ExtensionValue::Float(0.5),
+    )]));
+    assert!(solver.set_extension(child_constraint_key, "future_field", value.clone()));
+    assert_eq!(
+        solver.extension(child_constraint_key, "future_field"),
+        Some(&value)
+    );
+
+    let bundle = solver.dump_repro();
+    let replayed_solver = Solver::from_repro(bundle.clone()).unwrap();
+    let replayed_root_constraint_key = replayed_solver.root_constraint_key().unwrap();
+    let replayed_child_constraint_key = replayed_solver
+        .get(replayed_root_constraint_key)
+        .unwrap()
+        .child_keys[0];
+    assert_eq!(
+        replayed_solver.extension(replayed_child_constraint_key, "future_field"),
+        Some(&value)
+    );
+    assert_eq!(replayed_solver.dump_repro(), bundle);
+
+    assert_eq!(
+        solver.remove_extension(child_constraint_key, "future_field"),
+        Some(value)
+    );
+    assert_eq!(solver.extensions(child_constraint_key), None);
+}
+
+#[test]
+fn test_conformance_runner_reports_frame_diffs() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+    solver.solve(10., 10.);
+
+    let bundle = solver.dump_repro();
+    assert_eq!(run_conformance_case(&bundle), ConformanceOutcome::Passed);
+
+    let mut broken_bundle = bundle.clone();
+    let broken_node = &mut broken_bundle.root.as_mut().unwrap().children[1];
+    let actual_frame = broken_node.frame;
+    broken_node.frame = Some(Frame::default());
+
+    let directory =
+        std::env::temp_dir().join(format!("stretchbox-conformance-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::write(directory.join("a"), "passing").unwrap();
+    std::fs::write(directory.join("b"), "failing").unwrap();
+    std::fs::write(directory.join("c"), "invalid").unwrap();
+
+    let cases = run_conformance(&directory, |bytes| match bytes {
+        b"passing" => Some(bundle.clone()),
+        b"failing" => Some(broken_bundle.clone()),
+        _ => None,
+    })
+    .unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    let outcomes = cases
+        .into_iter()
+        .map(|case| case.outcome)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        outcomes,
+        vec![
+            ConformanceOutcome::Passed,
+            ConformanceOutcome::Failed {
+                diffs: vec![FrameDiff {
+                    path: vec![1],
+                    expected: Some(Frame::default()),
+                    actual: actual_frame,
+                }],
+            },
+            ConformanceOutcome::Invalid,
+        ]
+    );
+}
+
+#[test]
+fn test_solver_preserves_node_ids_through_repro_bundle() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[1];
+    assert!(solver.set_node_id(constraint_key, NodeId(7)));
+    assert!(!solver.set_node_id(root_constraint_key, NodeId(7)));
+    assert_eq!(solver.key_for_id(NodeId(7)), Some(constraint_key));
+
+    let replayed_solver = Solver::from_repro(solver.dump_repro()).unwrap();
+    let replayed_root_constraint_key = replayed_solver.root_constraint_key().unwrap();
+    let replayed_constraint_key = replayed_solver
+        .get(replayed_root_constraint_key)
+        .unwrap()
+        .child_keys[1];
+    assert_eq!(
+        replayed_solver.key_for_id(NodeId(7)),
+        Some(replayed_constraint_key)
+    );
+
+    let mut bundle = solver.dump_repro();
+    bundle.root.as_mut().unwrap().node_id = Some(NodeId(7));
+    assert!(Solver::from_repro(bundle).is_none());
+
+    solver.remove(constraint_key, None);
+    assert_eq!(solver.key_for_id(NodeId(7)), None);
+}
+
+#[test]
+fn test_solver_builder_streams_nodes() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut builder = SolverBuilder::with_capacity(4);
+    assert!(!builder.push(None, NodeId(0), exact));
+    assert!(builder.push(None, NodeId(0), Constraint::default()));
+    assert!(!builder.push(None, NodeId(1), Constraint::default()));
+    assert!(builder.push(Some(NodeId(0)), NodeId(1), exact));
+    assert!(builder.push(Some(NodeId(1)), NodeId(2), Constraint::default()));
+    assert!(!builder.push(Some(NodeId(1)), NodeId(2), Constraint::default()));
+    assert!(!builder.push(Some(NodeId(9)), NodeId(3), Constraint::default()));
+    assert_eq!(builder.len(), 3);
+
+    let mut solver = builder.build();
+    assert!(solver.is_dirty());
+
+    solver.solve(10., 10.);
+
+    let constraint_key = solver.key_for_id(NodeId(2)).unwrap();
+    assert_eq!(
+        solver.get_frame(constraint_key),
+        Some(Frame {
+            offset_x: 0.,
+            length_x: 10.,
+            offset_y: 0.,
+            length_y: 2.,
+        })
+    );
+}
+
+#[test]
+fn test_pipelined_solver_swaps_solved_back_buffer() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+    solver.solve(10., 10.);
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+
+    let mut pipelined_solver = PipelinedSolver::new(solver);
+    assert!(pipelined_solver.mutate(|solver| solver.set(child_constraint_key, exact).is_some()));
+    assert!(!pipelined_solver.swap());
+    assert_eq!(
+        pipelined_solver.front().get_frame(child_constraint_key),
+        Some(Frame {
+            offset_x: 0.,
+            length_x: 10.,
+            offset_y: 0.,
+            length_y: 10.,
+        })
+    );
+
+    pipelined_solver.solve_back(10., 10.);
+    assert!(pipelined_solver.swap());
+    assert!(pipelined_solver.back().contains(child_constraint_key));
+    assert_eq!(
+        pipelined_solver.front().get_frame(child_constraint_key),
+        Some(Frame {
+            offset_x: 0.,
+            length_x: 10.,
+            offset_y: 0.,
+            length_y: 2.,
+        })
+    );
+}
+
+#[test]
+fn test_pipelined_solver_runs_callbacks_on_swap() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+
+    let frames = Arc::new(Mutex::new(vec![]));
+    {
+        let frames = frames.clone();
+        assert!(solver.set_on_layout(child_constraint_key, move |frame| {
+            frames.lock().unwrap().push(frame);
+        }));
+    }
+    solver.solve(10., 10.);
+    frames.lock().unwrap().clear();
+
+    let mut pipelined_solver = PipelinedSolver::new(solver);
+    assert!(pipelined_solver.mutate(|solver| solver.set(child_constraint_key, exact).is_some()));
+    pipelined_solver.solve_back(10., 10.);
+    assert!(frames.lock().unwrap().is_empty());
+
+    assert!(pipelined_solver.swap());
+    assert_eq!(
+        *frames.lock().unwrap(),
+        vec![Frame {
+            offset_x: 0.,
+            length_x: 10.,
+            offset_y: 0.,
+            length_y: 2.,
+        }],
+    );
+}
+
+#[test]
+fn test_solver_diffs_structure_by_node_id() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut builder = SolverBuilder::default();
+    builder.push(None, NodeId(1), Constraint::default());
+    builder.push(Some(NodeId(1)), NodeId(2), Constraint::default());
+    builder.push(Some(NodeId(1)), NodeId(3), Constraint::default());
+    builder.push(Some(NodeId(1)), NodeId(4), Constraint::default());
+    let solver = builder.build();
+
+    let mut builder = SolverBuilder::default();
+    builder.push(None, NodeId(1), Constraint::default());
+    builder.push(Some(NodeId(1)), NodeId(3), Constraint::default());
+    builder.push(Some(NodeId(1)), NodeId(2), exact);
+    builder.push(Some(NodeId(1)), NodeId(5), Constraint::default());
+    let other_solver = builder.build();
+
+    assert_eq!(solver.diff_structure(&solver), vec![]);
+    assert_eq!(
+        solver.diff_structure(&other_solver),
+        vec![
+            StructuralChange::Removed { node_id: NodeId(4) },
+            StructuralChange::Moved {
+                node_id: NodeId(3),
+                parent_node_id: Some(NodeId(1)),
+                index: 0,
+            },
+            StructuralChange::Moved {
+                node_id: NodeId(2),
+                parent_node_id: Some(NodeId(1)),
+                index: 1,
+            },
+            StructuralChange::Modified {
+                node_id: NodeId(2),
+                constraint: exact,
+            },
+            StructuralChange::Inserted {
+                node_id: NodeId(5),
+                parent_node_id: Some(NodeId(1)),
+                index: 2,
+                constraint: Constraint::default(),
+            },
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "scale weights overflow in the children of sidebar")]
+fn test_solver_panics_with_labels() {
+    let scaled = |scale| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Scale(scale),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { scaled(usize::MAX) },
+        node! { scaled(1) },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    assert!(solver.set_label(root_constraint_key, "sidebar"));
+    solver.solve(10., 10.);
+}
+
+#[test]
+fn test_solver_describes_labelled_nodes() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default(), [
+            node! { Constraint { fill: Fill::Relative { main: FillType::Scale(0), cross: FillType::Scale(1) }, ..Default::default() } },
+        ] },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let header_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    let title_constraint_key = solver.get(header_constraint_key).unwrap().child_keys[0];
+    assert!(solver.set_label(root_constraint_key, "sidebar"));
+    assert!(solver.set_label(header_constraint_key, "header"));
+    assert!(solver.set_label(title_constraint_key, "title"));
+
+    assert_eq!(solver.label(header_constraint_key), Some("header"));
+    assert_eq!(
+        solver.describe(title_constraint_key),
+        "sidebar/header/title"
+    );
+    assert!(format!("{:?}", solver).contains("\"sidebar/header/title\": (Constraint {"));
+    assert_eq!(
+        solver
+            .lint()
+            .into_iter()
+            .map(|diagnostic| solver.describe_diagnostic(diagnostic))
+            .collect::<Vec<_>>(),
+        vec!["sidebar/header: zero scale weight".to_string()]
+    );
+
+    let replayed_solver = Solver::from_repro(solver.dump_repro()).unwrap();
+    let replayed_root_constraint_key = replayed_solver.root_constraint_key().unwrap();
+    assert_eq!(
+        replayed_solver.label(replayed_root_constraint_key),
+        Some("sidebar")
+    );
+
+    solver.remove(header_constraint_key, None);
+    assert_eq!(solver.label(title_constraint_key), None);
+    assert_eq!(
+        solver.remove_label(root_constraint_key),
+        Some("sidebar".to_string())
+    );
+}
+
+#[test]
+fn test_solver_syncs_keyed_children() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    assert!(solver.set_node_id(child_keys[0], NodeId(1)));
+    assert!(solver.set_node_id(child_keys[1], NodeId(2)));
+
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    let items = [(NodeId(3), Constraint::default()), (NodeId(1), exact)];
+    assert!(solver.sync_children(root_constraint_key, items));
+
+    let new_constraint_key = solver.key_for_id(NodeId(3)).unwrap();
+    let actual_child_keys = solver
+        .get(root_constraint_key)
+        .unwrap()
+        .child_keys
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+    assert_eq!(actual_child_keys, vec![new_constraint_key, child_keys[0]]);
+    assert_eq!(solver.get(child_keys[0]).unwrap().value, &exact);
+    assert!(!solver.contains(child_keys[1]));
+    assert!(!solver.contains(child_keys[2]));
+
+    solver.solve(10., 10.);
+    assert!(solver.sync_children(root_constraint_key, items));
+    assert!(!solver.is_dirty());
+}
+
+#[test]
+fn test_solver_profiles_containers_when_profiling() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default(), [node! { Constraint::default() }] },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+    assert!(solver.profile().is_empty());
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let horizontal = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    solver.set_profiling(true);
+    solver.set(root_constraint_key, horizontal);
+    solver.solve(10., 10.);
+
+    let profile = solver.profile();
+    let profiled_constraint_keys = profile
+        .iter()
+        .map(|&(constraint_key, _)| constraint_key)
+        .collect::<BTreeSet<_>>();
+    let mut constraint_keys = BTreeSet::new();
+    let mut to_visit_keys = vec![root_constraint_key];
+    while let Some(constraint_key) = to_visit_keys.pop() {
+        constraint_keys.insert(constraint_key);
+        to_visit_keys.extend(
+            solver
+                .get(constraint_key)
+                .unwrap()
+                .child_keys
+                .iter()
+                .copied(),
+        );
+    }
+    assert_eq!(profiled_constraint_keys, constraint_keys);
+    assert!(profile.windows(2).all(|window| window[0].1 >= window[1].1));
+}
+
+#[test]
+fn test_solver_memory_usage() {
+    let mut solver = make_solver(None).unwrap();
+    assert_eq!(solver.memory_usage().total(), 0);
+
+    let root_constraint_key = solver.insert_root(Constraint::default()).unwrap();
+    solver
+        .insert(Constraint::default(), root_constraint_key)
+        .unwrap();
+
+    let unsolved_memory_report = solver.memory_usage();
+    assert!(unsolved_memory_report.constraint_tree > 0);
+    assert_eq!(unsolved_memory_report.frame_tree, 0);
+
+    solver.solve(10., 10.);
+
+    let solved_memory_report = solver.memory_usage();
+    assert!(solved_memory_report.frame_tree > 0);
+    assert!(solved_memory_report.key_map > 0);
+    assert!(solved_memory_report.caches > 0);
+    assert!(solved_memory_report.total() > unsolved_memory_report.total());
+
+    assert!(solver.set_label(root_constraint_key, "root"));
+    assert!(solver.memory_usage().attributes > solved_memory_report.attributes);
+}
+
+#[test]
+fn test_solver_runs_hooks_in_phase_order() {
+    let mut solver = make_solver(Some(&node! { Constraint::default() })).unwrap();
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+
+    let events = Arc::new(Mutex::new(vec![]));
+    for phase in [Phase::Finalize, Phase::Arrange, Phase::Measure] {
+        let events = events.clone();
+        assert!(
+            solver.set_hook(root_constraint_key, phase, move |_, frame| {
+                events.lock().unwrap().push((phase, frame));
+            })
+        );
+    }
+
+    solver.solve(10., 10.);
+
+    let frame = Frame {
+        offset_x: 0.,
+        length_x: 10.,
+        offset_y: 0.,
+        length_y: 10.,
+    };
+    let actual_events = events.lock().unwrap().clone();
+    let expected_events = vec![
+        (Phase::Measure, None),
+        (Phase::Arrange, Some(frame)),
+        (Phase::Finalize, Some(frame)),
+    ];
+    assert_eq!(actual_events, expected_events);
+}
+
+#[test]
+fn test_solver_skips_no_op_set() {
+    let mut solver = make_solver(Some(&node! { Constraint::default() })).unwrap();
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+
+    solver.solve(10., 10.);
+
+    let old_constraint = solver.set(root_constraint_key, Constraint::default());
+    assert_eq!(old_constraint, Some(Constraint::default()));
+    assert!(!solver.is_dirty());
+
+    let new_constraint = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    solver.set(root_constraint_key, new_constraint);
+    assert!(solver.is_dirty());
+}
+
+#[test]
+fn test_solver_dirty_keys() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+
+    solver.solve(10., 10.);
+    assert_eq!(solver.dirty_keys().count(), 0);
+
+    solver.set(child_keys[0], Constraint::default());
+    assert_eq!(solver.dirty_keys().count(), 0);
+
+    let new_constraint = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    solver.set(child_keys[1], new_constraint);
+    solver.remove(child_keys[0], None);
+    let actual_dirty_keys = solver.dirty_keys().collect::<Vec<_>>();
+    let mut expected_dirty_keys = vec![root_constraint_key, child_keys[1]];
+    expected_dirty_keys.sort();
+    assert_eq!(actual_dirty_keys, expected_dirty_keys);
+
+    solver.solve(10., 10.);
+    assert_eq!(solver.dirty_keys().count(), 0);
+}
+
+#[test]
+fn test_solver_invalidates_measure() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+
+    solver.solve(10., 10.);
+    assert!(!solver.is_dirty());
+
+    assert!(solver.invalidate_measure(constraint_key));
+    assert!(solver.is_dirty());
+    assert_eq!(
+        solver.get(constraint_key).unwrap().value,
+        &Constraint::default()
+    );
+    assert_eq!(
+        solver.dirty_keys().collect::<Vec<_>>(),
+        vec![constraint_key]
+    );
+
+    solver.remove(constraint_key, None);
+    assert!(!solver.invalidate_measure(constraint_key));
+}
+
+#[test]
+fn test_solver_frames_hash() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+    let hash = solver.frames_hash();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(5.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    solver.set(constraint_key, exact);
+    solver.solve(10., 10.);
+    assert_eq!(solver.frames_hash(), hash);
+
+    solver.set(constraint_key, Constraint::default());
+    solver.solve(20., 10.);
+    assert_ne!(solver.frames_hash(), hash);
+}
+
+#[test]
+fn test_solver_frame_generations() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    assert_eq!(solver.frame_generation(root_constraint_key), None);
+
+    solver.solve(10., 10.);
+    assert_eq!(solver.frame_generation(root_constraint_key), Some(0));
+    assert_eq!(solver.frame_generation(child_keys[1]), Some(0));
+
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    solver.set(child_keys[0], exact);
+    solver.solve(10., 10.);
+    assert_eq!(solver.frame_generation(root_constraint_key), Some(0));
+    assert_eq!(solver.frame_generation(child_keys[0]), Some(1));
+    assert_eq!(solver.frame_generation(child_keys[1]), Some(1));
+}
+
+#[test]
+fn test_solver_lines() {
+    let root = Constraint {
+        content: Content {
+            padding: Padding {
+                left: 1.,
+                right: 1.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    assert_eq!(solver.lines(root_constraint_key), vec![]);
+
+    solver.solve(10., 10.);
+
+    let expected_lines = vec![LineInfo {
+        child_range: 0..2,
+        offset_cross: 1.,
+        length_cross: 8.,
+    }];
+    assert_eq!(solver.lines(root_constraint_key), expected_lines);
+
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    assert_eq!(solver.lines(constraint_key), vec![]);
+}
+
+#[test]
+fn test_solver_with_wrap() {
+    let exact = |length_x, length_y| Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Exact(length_x),
+            y: FillType::Exact(length_y),
+        },
+        ..Default::default()
+    };
+    let scale = |scale, min_x, length_y| Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Scale(scale),
+            y: FillType::Exact(length_y),
+        },
+        bounds: Bounds {
+            min_x,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            mode: LayoutMode::Wrap,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { exact(4., 2.) },
+        node! { scale(1, 3., 2.) },
+        node! { exact(6., 3.) },
+        node! { scale(2, 1., 1.) },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 4., length_x: 6., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 0., length_x: 6., offset_y: 2., length_y: 3. } },
+            node! { Frame { offset_x: 6., length_x: 4., offset_y: 2., length_y: 1. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let lines = solver.lines(root_constraint_key);
+    assert_eq!(
+        lines
+            .iter()
+            .map(|line| line.length_cross)
+            .collect::<Vec<_>>(),
+        vec![2., 3.]
+    );
+}
+
+#[test]
+fn test_solver_with_gaps_between_lines() {
+    let item = Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Exact(4.),
+            y: FillType::Exact(2.),
+        },
+        ..Default::default()
+    };
+
+    for (gap_cross, expected_offset_y) in [(FillType::Exact(2.), 4.), (FillType::Scale(1), 8.)] {
+        let root = Constraint {
+            content: Content {
+                direction: Direction::Horizontal,
+                mode: LayoutMode::Wrap,
+                gap_main: Some(FillType::Exact(1.)),
+                gap_cross: Some(gap_cross),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { item },
+            node! { item },
+            node! { item },
+        ] }))
+        .unwrap();
+
+        solver.solve(10., 10.);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+            [
+                node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 2. } },
+                node! { Frame { offset_x: 5., length_x: 4., offset_y: 0., length_y: 2. } },
+                node! { Frame { offset_x: 0., length_x: 4., offset_y: expected_offset_y, length_y: 2. } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+    }
+}
+
+#[test]
+fn test_solver_relayouts_only_around_invalidated_measure() {
+    let panel = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(4.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    let label = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Minimize,
+            cross: FillType::Minimize,
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { panel, [node! { label }] },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    let label_constraint_key = solver.get(child_keys[0]).unwrap().child_keys[0];
+    assert!(
+        solver.set_measure(label_constraint_key, |length_x: &mut f64, _, _| {
+            (*length_x, 1.)
+        })
+    );
+
+    let arrange_count = Arc::new(Mutex::new(0));
+    {
+        let arrange_count = arrange_count.clone();
+        assert!(solver.set_hook(child_keys[1], Phase::Arrange, move |_, _| {
+            *arrange_count.lock().unwrap() += 1;
+        }));
+    }
+
+    solver.solve_with_context(&mut 2., 10., 10.);
+    assert_eq!(*arrange_count.lock().unwrap(), 1);
+
+    assert!(solver.invalidate_measure(label_constraint_key));
+    assert!(solver.is_dirty());
+    assert_eq!(
+        solver.dirty_keys().collect::<Vec<_>>(),
+        vec![label_constraint_key]
+    );
+    assert_eq!(solver.get_frame(label_constraint_key), None);
+    assert!(solver.get_frame(child_keys[0]).is_some());
+    assert!(solver.get_frame(child_keys[1]).is_some());
+
+    solver.solve_with_context(&mut 5., 10., 10.);
+    assert!(!solver.is_dirty());
+    assert_eq!(solver.dirty_keys().count(), 0);
+    assert_eq!(*arrange_count.lock().unwrap(), 1);
+    assert_eq!(
+        solver.get_frame(label_constraint_key),
+        Some(Frame {
+            offset_x: 0.,
+            length_x: 5.,
+            offset_y: 0.,
+            length_y: 1.,
+        })
+    );
+}
+
+#[test]
+fn test_solver_with_split_dividers() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            mode: LayoutMode::Split,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let min_pane = Constraint {
+        bounds: Bounds {
+            min_x: 2.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+        node! { min_pane },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    assert!(!solver.set_divider(root_constraint_key, 0, 7.));
+
+    solver.solve(12., 4.);
+    assert_eq!(solver.divider(root_constraint_key, 0), Some(4.));
+    assert_eq!(solver.divider(root_constraint_key, 1), Some(8.));
+    assert_eq!(solver.divider(root_constraint_key, 2), None);
+
+    assert!(solver.set_divider(root_constraint_key, 0, 7.));
+    assert!(solver.is_dirty());
+    solver.solve(12., 4.);
+
+    let lengths = |solver: &Solver| {
+        child_keys
+            .iter()
+            .map(|&constraint_key| solver.get_frame(constraint_key).unwrap().length_x)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(lengths(&solver), vec![7., 2., 3.]);
+
+    solver.invalidate_measure(root_constraint_key);
+    solver.solve(24., 4.);
+    assert_eq!(lengths(&solver), vec![14., 2., 8.]);
+}
+
+#[test]
+fn test_solver_with_split_dividers_and_hidden_pane() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            mode: LayoutMode::Split,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let hidden = Constraint {
+        display: Display::None,
+        ..Default::default()
+    };
+    let leading_pane = Constraint {
+        bounds: Bounds {
+            min_x: 8.,
+            ..Default::default()
+        },
+        order: -1,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+        node! { hidden },
+        node! { leading_pane },
+    ] }))
+    .unwrap();
+
+    solver.solve(12., 4.);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    let lengths = |solver: &Solver| {
+        child_keys
+            .iter()
+            .map(|&constraint_key| solver.get_frame(constraint_key).unwrap().length_x)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(lengths(&solver), vec![4., 0., 8.]);
+    assert_eq!(solver.divider(root_constraint_key, 0), Some(8.));
+    assert_eq!(solver.divider(root_constraint_key, 1), None);
+
+    assert!(solver.set_divider(root_constraint_key, 0, 9.));
+    solver.solve(12., 4.);
+    assert_eq!(lengths(&solver), vec![3., 0., 9.]);
+    assert_eq!(solver.divider(root_constraint_key, 0), Some(9.));
+}
+
+#[test]
+fn test_solver_with_flow_line_positions_of_hidden_and_ordered_children() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            mode: LayoutMode::Flow,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let icon = Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Exact(4.),
+            y: FillType::Exact(2.),
+        },
+        ..Default::default()
+    };
+    let hidden = Constraint {
+        display: Display::None,
+        ..icon
+    };
+    let last = Constraint { order: 1, ..icon };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { hidden },
+        node! { icon },
+        node! { icon },
+    ] }))
+    .unwrap();
+    solver.solve(10., 10.);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    let line_positions = |solver: &Solver| {
+        child_keys
+            .iter()
+            .map(|&constraint_key| solver.line_position(constraint_key))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        line_positions(&solver),
+        vec![None, Some((0, 0)), Some((0, 1))]
+    );
+
+    solver.set(child_keys[0], icon);
+    solver.set(child_keys[1], last);
+    solver.solve(10., 10.);
+    assert_eq!(
+        line_positions(&solver),
+        vec![Some((0, 0)), Some((1, 0)), Some((0, 1))]
+    );
+}
+
+#[test]
+fn test_solver_with_flow() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            mode: LayoutMode::Flow,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let make_icon = |length_y| Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Exact(4.),
+            y: FillType::Exact(length_y),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { make_icon(2.) },
+        node! { make_icon(3.) },
+        node! { make_icon(1.) },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 4., length_x: 4., offset_y: 0., length_y: 3. } },
+            node! { Frame { offset_x: 0., length_x: 4., offset_y: 3., length_y: 1. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    let actual_positions = child_keys
+        .iter()
+        .map(|&constraint_key| solver.line_position(constraint_key))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        actual_positions,
+        vec![Some((0, 0)), Some((0, 1)), Some((1, 0))]
+    );
+    assert_eq!(solver.line_position(root_constraint_key), None);
+}
+
+#[test]
+fn test_solver_with_last_line_alignment() {
+    let make_root = |mode| Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            mode,
+            align_main: Align::Start,
+            align_last_line: Some(Align::End),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(4.),
+            cross: FillType::Exact(1.),
+        },
+        ..Default::default()
+    };
+
+    for (mode, number_of_children, expected_offsets) in [
+        (LayoutMode::Wrap, 3, vec![(0., 0.), (4., 0.), (6., 1.)]),
+        (LayoutMode::Wrap, 2, vec![(0., 0.), (4., 0.)]),
+        (LayoutMode::Relative, 2, vec![(0., 0.), (4., 0.)]),
+    ] {
+        let mut solver = make_solver(Some(&node! { make_root(mode) })).unwrap();
+        let root_constraint_key = solver.root_constraint_key().unwrap();
+        for _ in 0..number_of_children {
+            solver.insert(exact, root_constraint_key).unwrap();
+        }
+
+        solver.solve(10., 10.);
+
+        let actual_offsets = solver
+            .get(root_constraint_key)
+            .unwrap()
+            .child_keys
+            .iter()
+            .map(|&constraint_key| {
+                let frame = solver.get_frame(constraint_key).unwrap();
+                (frame.offset_x, frame.offset_y)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(actual_offsets, expected_offsets);
+    }
+}
+
+#[test]
+fn test_solver_with_fractional_alignment() {
+    let root = Constraint {
+        content: Content {
+            align_main: Align::Fraction(AlignFraction(0.25)),
+            align_cross: Align::Fraction(AlignFraction(1.5)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [node! { exact }] })).unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [node! { Frame { offset_x: 12., length_x: 2., offset_y: 2., length_y: 2. } }]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    let nan_align = Align::Fraction(AlignFraction(f64::NAN));
+    assert_eq!(nan_align, nan_align);
+    assert_ne!(root.content.align_main, root.content.align_cross);
+}
+
+#[test]
+fn test_solver_solves_auto_sized_root() {
+    let exact = Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Exact(4.),
+            y: FillType::Exact(3.),
+        },
+        ..Default::default()
+    };
+    let root = Constraint {
+        content: Content {
+            padding: Padding {
+                left: 1.,
+                right: 1.,
+                top: 1.,
+                bottom: 1.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { exact },
+        node! { exact },
+    ] }))
+    .unwrap();
+
+    assert_eq!(solver.solve_auto(None, None), (6., 8.));
+    assert!(!solver.is_dirty());
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 6., offset_y: 0., length_y: 8. },
+        [
+            node! { Frame { offset_x: 1., length_x: 4., offset_y: 1., length_y: 3. } },
+            node! { Frame { offset_x: 1., length_x: 4., offset_y: 4., length_y: 3. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    assert_eq!(solver.solve_auto(None, Some(5.)), (6., 5.));
+}
+
+#[test]
+fn test_solver_solves_many_sizes() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[1];
+
+    solver.solve(10., 10.);
+    let frames_hash = solver.frames_hash();
+
+    let snapshots = solver.solve_many(&[(10., 20.), (4., 8.)]);
+    let actual_frames = snapshots
+        .iter()
+        .map(|snapshot| snapshot.get(constraint_key))
+        .collect::<Vec<_>>();
+    let expected_frames = vec![
+        Some(Frame {
+            offset_x: 0.,
+            length_x: 10.,
+            offset_y: 10.,
+            length_y: 10.,
+        }),
+        Some(Frame {
+            offset_x: 0.,
+            length_x: 4.,
+            offset_y: 4.,
+            length_y: 4.,
+        }),
+    ];
+    assert_eq!(actual_frames, expected_frames);
+    assert_eq!(solver.frames_hash(), frames_hash);
+}
+
+#[test]
+fn test_solver_estimates_frame_gradients() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint { fill: Fill::Relative { main: FillType::Exact(2.), cross: FillType::Scale(1) }, ..Default::default() } },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[1];
+    solver.solve(10., 10.);
+
+    let gradients = solver.solve_gradient(10., 10., 2., 2.);
+    let gradient = gradients[&constraint_key];
+    assert_eq!(
+        gradient,
+        FrameGradient {
+            per_x: Frame {
+                length_x: 1.,
+                ..Default::default()
+            },
+            per_y: Frame {
+                length_y: 1.,
+                ..Default::default()
+            },
+        }
+    );
+    assert_eq!(
+        gradient.extrapolate(solver.get_frame(constraint_key).unwrap(), 3., -1.),
+        Frame {
+            offset_x: 0.,
+            length_x: 13.,
+            offset_y: 2.,
+            length_y: 7.,
+        }
+    );
+}
+
+#[test]
+fn test_solver_solves_subtree_with_fixed_frame() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default(), [
+            node! { Constraint::default() },
+            node! { Constraint::default() },
+        ] },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    let frame = Frame {
+        offset_x: 1.,
+        length_x: 4.,
+        offset_y: 0.,
+        length_y: 6.,
+    };
+    assert!(solver.solve_subtree(constraint_key, frame));
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! {
+                Frame { offset_x: 1., length_x: 4., offset_y: 0., length_y: 6. },
+                [
+                    node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 3. } },
+                    node! { Frame { offset_x: 0., length_x: 4., offset_y: 3., length_y: 3. } },
+                ]
+            },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 5., length_y: 5. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    let grandchild_constraint_key = solver.get(constraint_key).unwrap().child_keys[1];
+    assert_eq!(
+        solver.to_world(grandchild_constraint_key, 0., 0.),
+        Some((1., 3.))
+    );
+
+    solver.remove(grandchild_constraint_key, None);
+    assert!(!solver.solve_subtree(grandchild_constraint_key, frame));
+    assert!(!solver.solve_subtree(constraint_key, frame));
+
+    solver.solve(10., 10.);
+    assert!(!solver.solve_subtree(grandchild_constraint_key, frame));
+    assert!(solver.solve_subtree(constraint_key, frame));
+}
+
+#[test]
+fn test_solver_runs_on_layout_when_frame_changes() {
+    let exact = |length_main| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(length_main),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { exact(2.) },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+
+    let frames = Arc::new(Mutex::new(vec![]));
+    {
+        let frames = frames.clone();
+        assert!(solver.set_on_layout(child_keys[0], move |frame| {
+            frames.lock().unwrap().push(frame);
+        }));
+    }
+
+    solver.solve(10., 10.);
+    solver.set(child_keys[1], exact(5.)).unwrap();
+    solver.solve(10., 10.);
+    solver.set(child_keys[0], exact(3.)).unwrap();
+    solver.solve(10., 10.);
+
+    let actual_frames = frames.lock().unwrap().clone();
+    let expected_frames = vec![
+        Frame {
+            offset_x: 0.,
+            length_x: 10.,
+            offset_y: 0.,
+            length_y: 2.,
+        },
+        Frame {
+            offset_x: 0.,
+            length_x: 10.,
+            offset_y: 0.,
+            length_y: 3.,
+        },
+    ];
+    assert_eq!(actual_frames, expected_frames);
+}
+
+struct DiagonalLayout;
+
+impl CustomLayout for DiagonalLayout {
+    fn measure(&self, children: &[LayoutChild], _: f64, _: f64) -> (f64, f64) {
+        children
+            .iter()
+            .fold((0., 0.), |(length_x, length_y), child| {
+                (length_x + child.length_x, length_y + child.length_y)
+            })
+    }
+
+    fn arrange(&self, children: &[LayoutChild], content_frame: Frame) -> Vec<Frame> {
+        let mut offset_x = content_frame.offset_x;
+        let mut offset_y = content_frame.offset_y;
+
+        children
+            .iter()
+            .map(|child| {
+                let frame = Frame {
+                    offset_x,
+                    length_x: child.length_x,
+                    offset_y,
+                    length_y: child.length_y,
+                };
+                offset_x += child.length_x;
+                offset_y += child.length_y;
+                frame
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_solver_with_custom_layout() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        ..Default::default()
+    };
+    let minimize = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Minimize,
+            cross: FillType::Minimize,
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { minimize, [node! { exact }, node! { exact }] },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    assert!(solver.set_layout(constraint_key, DiagonalLayout));
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [node! {
+            Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 4. },
+            [
+                node! { Frame { offset_x: 0., length_x: 2., offset_y: 0., length_y: 2. } },
+                node! { Frame { offset_x: 2., length_x: 2., offset_y: 2., length_y: 2. } },
+            ]
+        }]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_registered_layout() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        ..Default::default()
+    };
+    let diagonal = Constraint {
+        content: Content {
+            layout: Some(LayoutId::new("diagonal")),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(
+        &node! { diagonal, [node! { exact }, node! { exact }] },
+    ))
+    .unwrap();
+
+    let layout_id = solver.register_layout("diagonal", DiagonalLayout);
+    assert_eq!(layout_id, LayoutId::new("diagonal"));
+    assert_eq!(solver.layout_name(layout_id), Some("diagonal"));
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 2., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 2., length_x: 2., offset_y: 2., length_y: 2. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+struct TextLayout {
+    area: f64,
+}
+
+impl CustomLayout for TextLayout {
+    fn measure(&self, children: &[LayoutChild], available_x: f64, _: f64) -> (f64, f64) {
+        let length_x = available_x.min(self.area);
+        (length_x, self.measure_y_for_x(children, length_x))
+    }
+
+    fn arrange(&self, _: &[LayoutChild], _: Frame) -> Vec<Frame> {
+        vec![]
+    }
+
+    fn measure_y_for_x(&self, _: &[LayoutChild], length_x: f64) -> f64 {
+        (self.area / length_x).ceil()
+    }
+}
+
+#[test]
+fn test_solver_with_height_for_width_measurement() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let container = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Scale(1),
+            cross: FillType::Minimize,
+        },
+        ..Default::default()
+    };
+    let text = Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Scale(1),
+            y: FillType::Minimize,
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { container, [node! { text }] },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let container_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    let text_constraint_key = solver.get(container_constraint_key).unwrap().child_keys[0];
+    assert!(solver.set_layout(text_constraint_key, TextLayout { area: 120. }));
+
+    solver.solve(60., 500.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 60., offset_y: 0., length_y: 500. },
+        [node! {
+            Frame { offset_x: 0., length_x: 60., offset_y: 0., length_y: 2. },
+            [node! { Frame { offset_x: 0., length_x: 60., offset_y: 0., length_y: 2. } }]
+        }]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_images() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::image(4., 2.) },
+        node! { Constraint::image(40., 10.) },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 2., length_y: 2.5 } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_nested_minimize() {
+    let padding = Padding {
+        left: 1.,
+        right: 1.,
+        top: 1.,
+        bottom: 1.,
+        ..Default::default()
+    };
+    let row = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Minimize,
+            cross: FillType::Minimize,
+        },
+        content: Content {
+            direction: Direction::Horizontal,
+            padding,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let exact = Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Exact(2.),
+            y: FillType::Exact(3.),
+        },
+        content: Content {
+            padding,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { row, [node! { exact }, node! { exact }] },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! {
+                Frame { offset_x: 0., length_x: 6., offset_y: 0., length_y: 5. },
+                [
+                    node! { Frame { offset_x: 1., length_x: 2., offset_y: 1., length_y: 3. } },
+                    node! { Frame { offset_x: 3., length_x: 2., offset_y: 1., length_y: 3. } },
+                ]
+            },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 5., length_y: 5. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_min_bounds() {
+    let bounded = Constraint {
+        bounds: Bounds {
+            min_y: 5.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { bounded },
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 5. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 5., length_y: 2.5 } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 7.5, length_y: 2.5 } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_overflow_policies() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(10.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    for (overflow, expected_length_y, expected_offset_y, expected_overflow) in [
+        (Overflow::Clip, [10., 0.], 10., 10.),
+        (Overflow::ShrinkProportional, [5., 5.], 5., 10.),
+        (Overflow::Overflow, [10., 10.], 10., 10.),
+    ] {
+        let root = Constraint {
+            content: Content {
+                overflow,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { exact },
+            node! { exact },
+        ] }))
+        .unwrap();
+
+        solver.solve(10., 10.);
+
+        let root_constraint_key = solver.root_constraint_key().unwrap();
+        let actual_frame_tree = make_frame_tree(&solver);
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+            [
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: expected_length_y[0] } },
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: expected_offset_y, length_y: expected_length_y[1] } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+        assert_eq!(
+            solver.get_overflow(root_constraint_key),
+            Some((0., expected_overflow))
+        );
+    }
+}
+
+#[test]
+fn test_solver_with_shrink_weights() {
+    let root = Constraint {
+        content: Content {
+            overflow: Overflow::ShrinkWeighted,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let exact = |shrink| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(6.),
+            cross: FillType::Scale(1),
+        },
+        shrink,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { exact(Some(0.)) },
+        node! { exact(None) },
+        node! { exact(Some(4.)) },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 6. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 6., length_y: 4. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 10., length_y: 0. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_priority_relaxation() {
+    let root = Constraint {
+        content: Content {
+            overflow: Overflow::Relax,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let exact = |priority, min_y| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(5.),
+            cross: FillType::Scale(1),
+        },
+        bounds: Bounds {
+            min_y,
+            ..Default::default()
+        },
+        priority,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { exact(Some(2), 0.) },
+        node! { exact(None, 0.) },
+        node! { exact(Some(1), 2.) },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 3. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 3., length_y: 5. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 8., length_y: 2. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+    assert_eq!(solver.get_overflow(root_constraint_key), Some((0., 5.)));
+}
+
+#[test]
+fn test_solver_reports_overflow_per_axis() {
+    let wide = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(6.),
+            cross: FillType::Exact(12.),
+        },
+        ..Default::default()
+    };
+
+    for overflow in [
+        Overflow::Clip,
+        Overflow::ShrinkProportional,
+        Overflow::Overflow,
+    ] {
+        let root = Constraint {
+            content: Content {
+                overflow,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { wide },
+            node! { wide },
+        ] }))
+        .unwrap();
+
+        let root_constraint_key = solver.root_constraint_key().unwrap();
+        let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+        assert_eq!(solver.get_overflow(root_constraint_key), None);
+
+        solver.solve(10., 10.);
+        assert_eq!(solver.get_overflow(root_constraint_key), Some((2., 2.)));
+        assert_eq!(solver.get_overflow(constraint_key), Some((0., 0.)));
+    }
+}
+
+#[test]
+fn test_solver_reports_overflow_in_grid() {
+    let root = Constraint {
+        content: Content {
+            mode: LayoutMode::Grid,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    assert!(solver.set_grid(
+        root_constraint_key,
+        Some(Grid {
+            columns: vec![FillType::Exact(6.), FillType::Exact(7.)],
+            rows: vec![FillType::Scale(1)],
+            ..Default::default()
+        }),
+    ));
+
+    solver.solve(10., 10.);
+    assert_eq!(solver.get_overflow(root_constraint_key), Some((3., 0.)));
+}
+
+#[test]
+fn test_solver_reports_free_space() {
+    let root = Constraint {
+        content: Content {
+            gap_main: Some(FillType::Exact(1.)),
+            align_main: Align::Middle,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint { fill: Fill::Relative { main: FillType::Exact(2.), cross: FillType::Scale(1) }, ..Default::default() } },
+        node! { Constraint { fill: Fill::Relative { main: FillType::Exact(3.), cross: FillType::Scale(1) }, ..Default::default() } },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    assert_eq!(solver.free_space(root_constraint_key), None);
+
+    solver.solve(10., 10.);
+    assert_eq!(solver.free_space(root_constraint_key), Some(4.));
+
+    solver.set(
+        root_constraint_key,
+        Constraint {
+            content: Content {
+                overflow: Overflow::Overflow,
+                ..root.content
+            },
+            ..root
+        },
+    );
+    solver.solve(10., 5.);
+    assert_eq!(solver.free_space(root_constraint_key), Some(-1.));
+
+    solver.insert(Constraint::default(), root_constraint_key);
+    solver.solve(10., 10.);
+    assert_eq!(solver.free_space(root_constraint_key), Some(0.));
+}
+
+#[test]
+fn test_solver_clears_solve_outputs_on_mode_change() {
+    let root = Constraint {
+        content: Content {
+            overflow: Overflow::Overflow,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let wide = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(12.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { wide },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    solver.solve(10., 10.);
+    assert_eq!(solver.get_overflow(root_constraint_key), Some((0., 2.)));
+    assert_eq!(solver.free_space(root_constraint_key), Some(-2.));
+    assert_eq!(solver.lines(root_constraint_key).len(), 1);
+
+    solver.set(
+        root_constraint_key,
+        Constraint {
+            content: Content {
+                mode: LayoutMode::Grid,
+                ..root.content
+            },
+            ..root
+        },
+    );
+    assert!(solver.set_grid(
+        root_constraint_key,
+        Some(Grid {
+            columns: vec![FillType::Scale(1)],
+            rows: vec![FillType::Scale(1)],
+            ..Default::default()
+        }),
+    ));
+    solver.solve(10., 10.);
+    assert_eq!(solver.get_overflow(root_constraint_key), Some((0., 0.)));
+    assert_eq!(solver.free_space(root_constraint_key), None);
+    assert_eq!(solver.lines(root_constraint_key), vec![]);
+}
+
+#[test]
+fn test_solver_collapses_lowest_priority_children() {
+    let item = |priority| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(4.),
+            cross: FillType::Scale(1),
+        },
+        priority,
+        ..Default::default()
+    };
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            overflow: Overflow::Collapse,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { item(Some(2)) },
+        node! { item(Some(1)) },
+        node! { item(None) },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    solver.solve(10., 2.);
+
+    assert_eq!(
+        solver.collapsed_children(root_constraint_key),
+        Some(&[child_keys[1]][..])
+    );
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 2. },
+        [
+            node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 0., length_x: 0., offset_y: 0., length_y: 0. } },
+            node! { Frame { offset_x: 4., length_x: 4., offset_y: 0., length_y: 2. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_commits_staged_edits_atomically() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+    solver.solve(10., 10.);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(4.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    let nan = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(f64::NAN),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    assert!(solver.stage(constraint_key, exact));
+    assert!(solver.stage(root_constraint_key, exact));
+    assert_eq!(solver.validate_staged(), vec![root_constraint_key]);
+    assert!(!solver.commit());
+    assert!(!solver.is_dirty());
+    assert_eq!(
+        solver.get(constraint_key).unwrap().value,
+        &Constraint::default()
+    );
+
+    assert!(solver.stage(root_constraint_key, Constraint::default()));
+    assert!(solver.stage(constraint_key, nan));
+    assert!(!solver.commit());
+
+    solver.discard();
+    assert_eq!(solver.staged(constraint_key), None);
+
+    assert!(solver.stage(constraint_key, exact));
+    assert!(solver.commit());
+    assert!(solver.is_dirty());
+    assert_eq!(solver.get(constraint_key).unwrap().value, &exact);
+}
+
+#[test]
+fn test_solver_with_overflow_anchor() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(6.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    for (overflow, overflow_anchor, expected_length_x, expected_offset_x) in [
+        (Overflow::Clip, Align::End, [0., 4., 6.], [0., 0., 4.]),
+        (Overflow::Clip, Align::Middle, [2., 6., 2.], [0., 2., 8.]),
+        (Overflow::Overflow, Align::End, [6., 6., 6.], [-8., -2., 4.]),
+    ] {
+        let root = Constraint {
+            content: Content {
+                direction: Direction::Horizontal,
+                overflow,
+                overflow_anchor: Some(overflow_anchor),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { exact },
+            node! { exact },
+            node! { exact },
+        ] }))
+        .unwrap();
+
+        solver.solve(10., 10.);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+            [
+                node! { Frame { offset_x: expected_offset_x[0], length_x: expected_length_x[0], offset_y: 0., length_y: 10. } },
+                node! { Frame { offset_x: expected_offset_x[1], length_x: expected_length_x[1], offset_y: 0., length_y: 10. } },
+                node! { Frame { offset_x: expected_offset_x[2], length_x: expected_length_x[2], offset_y: 0., length_y: 10. } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+    }
+}
+
+#[test]
+fn test_solver_with_proportional_precedence() {
+    let root = Constraint {
+        content: Content {
+            precedence: Precedence::Proportional,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(10.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { exact },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 5. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 5., length_y: 5. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_gaps() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    for (gap_main, child, length_y, expected_length_y) in [
+        (FillType::Scale(1), exact, 10., 2.),
+        (FillType::Exact(1.), Constraint::default(), 11., 3.),
+    ] {
+        let root = Constraint {
+            content: Content {
+                gap_main: Some(gap_main),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { child },
+            node! { child },
+            node! { child },
+        ] }))
+        .unwrap();
+
+        solver.solve(10., length_y);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y },
+            [
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: expected_length_y } },
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: 4., length_y: expected_length_y } },
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: 8., length_y: expected_length_y } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+    }
+}
+
+#[test]
+fn test_solver_with_margins() {
+    let child = Constraint {
+        margin: Padding {
+            left: 2.,
+            top: 1.,
+            bottom: 1.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { child },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 2., length_x: 8., offset_y: 1., length_y: 4. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 6., length_y: 4. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_resolved_gaps() {
+    let make_root = |mode, gap_main| Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            mode,
+            gap_main: Some(gap_main),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(3.),
+            cross: FillType::Exact(1.),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(
+        &node! { make_root(LayoutMode::Relative, FillType::Fraction(0.1)), [
+            node! { exact },
+            node! { exact },
+            node! { exact },
+        ] },
+    ))
+    .unwrap();
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    let offsets = |solver: &Solver| {
+        child_keys
+            .iter()
+            .map(|&constraint_key| solver.get_frame(constraint_key).unwrap().offset_x)
+            .collect::<Vec<_>>()
+    };
+
+    solver.solve(20., 10.);
+    assert_eq!(offsets(&solver), vec![0., 5., 10.]);
+    assert!(solver.lint().is_empty());
+
+    let bounded = FillType::Bounded {
+        min: 1.,
+        preferred: 4.,
+        max: 3.,
+    };
+    solver.set(root_constraint_key, make_root(LayoutMode::Flow, bounded));
+    solver.solve(20., 10.);
+    assert_eq!(offsets(&solver), vec![0., 6., 12.]);
+
+    solver.set(
+        root_constraint_key,
+        make_root(LayoutMode::Relative, FillType::Minimize),
+    );
+    assert_eq!(
+        solver.lint(),
+        vec![LintDiagnostic::IgnoredGap {
+            constraint_key: root_constraint_key,
+        }],
+    );
+
+    solver.set(
+        root_constraint_key,
+        make_root(LayoutMode::Flow, FillType::Scale(1)),
+    );
+    assert_eq!(
+        solver.lint(),
+        vec![LintDiagnostic::IgnoredGap {
+            constraint_key: root_constraint_key,
+        }],
+    );
+}
+
+#[test]
+fn test_solver_lints_margins_outside_relative_mode() {
+    let stack = Constraint {
+        content: Content {
+            mode: LayoutMode::Stack,
+            collapse_margins: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let child = Constraint {
+        margin: Padding {
+            left: 2.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { stack, [
+        node! { child },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    assert_eq!(
+        solver.lint(),
+        vec![
+            LintDiagnostic::IgnoredMarginCollapse {
+                constraint_key: root_constraint_key,
+            },
+            LintDiagnostic::IgnoredMargin {
+                constraint_key: child_key,
+            },
+        ],
+    );
+}
+
+#[test]
+fn test_solver_with_max_bounds() {
+    let clamped = Constraint {
+        bounds: Bounds {
+            max_x: Some(4.),
+            max_y: Some(10.),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let raised = Constraint {
+        bounds: Bounds {
+            min_y: 50.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { clamped },
+        node! { raised },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 100.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 100. },
+        [
+            node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 10. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 10., length_y: 50. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 60., length_y: 40. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_balances_columns() {
+    let root = Constraint {
+        content: Content {
+            mode: LayoutMode::Columns(2),
+            gap_cross: Some(FillType::Exact(2.)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let child = |length_main| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(length_main),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { child(3.) },
+        node! { child(3.) },
+        node! { child(2.) },
+        node! { child(2.) },
+        node! { child(2.) },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 3. } },
+            node! { Frame { offset_x: 0., length_x: 4., offset_y: 3., length_y: 3. } },
+            node! { Frame { offset_x: 6., length_x: 4., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 6., length_x: 4., offset_y: 2., length_y: 2. } },
+            node! { Frame { offset_x: 6., length_x: 4., offset_y: 4., length_y: 2. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_reversed_direction() {
+    let make_exact = |length_main| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(length_main),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    let root = Constraint {
+        content: Content {
+            padding: Padding {
+                top: 1.,
+                ..Default::default()
+            },
+            reverse: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { make_exact(2.) },
+        node! { make_exact(3.) },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 8., length_y: 2. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 5., length_y: 3. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_right_to_left_layout_direction() {
+    let make_exact = |length_main| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(length_main),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            padding: Padding {
+                start: 1.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    for (layout_direction, expected_offsets_x) in [
+        (LayoutDirection::LeftToRight, [1., 3.]),
+        (LayoutDirection::RightToLeft, [7., 4.]),
+    ] {
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { make_exact(2.) },
+            node! { make_exact(3.) },
+        ] }))
+        .unwrap();
+        solver.set_layout_direction(layout_direction);
+
+        solver.solve(10., 10.);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let [offset_x_1, offset_x_2] = expected_offsets_x;
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+            [
+                node! { Frame { offset_x: offset_x_1, length_x: 2., offset_y: 0., length_y: 10. } },
+                node! { Frame { offset_x: offset_x_2, length_x: 3., offset_y: 0., length_y: 10. } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+    }
+}
+
+#[test]
+fn test_solver_with_grid() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(1.),
+            cross: FillType::Exact(1.),
+        },
+        ..Default::default()
+    };
+
+    let root = Constraint {
+        content: Content {
+            mode: LayoutMode::Grid,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+        node! { exact },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let grid = Grid {
+        columns: vec![FillType::Exact(2.), FillType::Scale(1), FillType::Minimize],
+        rows: vec![FillType::Exact(3.), FillType::Scale(1)],
+        ..Default::default()
+    };
+    assert!(solver.set_grid(root_constraint_key, Some(grid.clone())));
+    assert_eq!(solver.grid(root_constraint_key), Some(&grid));
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 2., offset_y: 0., length_y: 3. } },
+            node! { Frame { offset_x: 2., length_x: 7., offset_y: 0., length_y: 3. } },
+            node! { Frame { offset_x: 9., length_x: 1., offset_y: 0., length_y: 1. } },
+            node! { Frame { offset_x: 0., length_x: 2., offset_y: 3., length_y: 7. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_grid_areas() {
+    let root = Constraint {
+        content: Content {
+            mode: LayoutMode::Grid,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    for (&child_key, area) in child_keys
+        .iter()
+        .zip(["content", "header", "footer", "sidebar"])
+    {
+        assert!(solver.set_grid_area(child_key, Some(area.to_string())));
+    }
+    assert_eq!(solver.grid_area(child_keys[1]), Some("header"));
+
+    let areas = |rows: [[&str; 2]; 3]| {
+        rows.iter()
+            .map(|row| row.iter().map(|area| area.to_string()).collect())
+            .collect()
+    };
+    let rows = vec![FillType::Exact(2.), FillType::Scale(1), FillType::Exact(1.)];
+
+    solver.set_grid(
+        root_constraint_key,
+        Some(Grid {
+            columns: vec![FillType::Exact(3.), FillType::Scale(1)],
+            rows: rows.clone(),
+            areas: areas([
+                ["header", "header"],
+                ["sidebar", "content"],
+                ["footer", "footer"],
+            ]),
+        }),
+    );
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 3., length_x: 7., offset_y: 2., length_y: 7. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 9., length_y: 1. } },
+            node! { Frame { offset_x: 0., length_x: 3., offset_y: 2., length_y: 7. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    solver.set_grid(
+        root_constraint_key,
+        Some(Grid {
+            columns: vec![FillType::Scale(1), FillType::Exact(3.)],
+            rows,
+            areas: areas([
+                ["header", "header"],
+                ["content", "sidebar"],
+                ["footer", "footer"],
+            ]),
+        }),
+    );
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 7., offset_y: 2., length_y: 7. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 9., length_y: 1. } },
+            node! { Frame { offset_x: 7., length_x: 3., offset_y: 2., length_y: 7. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    let mut bundle = solver.dump_repro();
+    bundle.solve_lengths = None;
+    let mut replayed_solver = Solver::from_repro(bundle).unwrap();
+    assert!(replayed_solver.is_dirty());
+    replayed_solver.solve(10., 10.);
+    assert_eq!(make_frame_tree(&replayed_solver), expected_frame_tree);
+
+    assert!(solver.lint().is_empty());
+    solver.set_grid_area(child_keys[0], Some("aside".to_string()));
+    assert_eq!(
+        solver.lint(),
+        vec![LintDiagnostic::IgnoredGridPlacement {
+            constraint_key: child_keys[0],
+        }],
+    );
+}
+
+#[test]
+fn test_solver_with_non_rectangular_grid_area() {
+    let root = Constraint {
+        content: Content {
+            mode: LayoutMode::Grid,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    solver.set_grid_area(child_keys[0], Some("header".to_string()));
+    solver.set_grid_area(child_keys[1], Some("sidebar".to_string()));
+    solver.set_grid(
+        root_constraint_key,
+        Some(Grid {
+            columns: vec![FillType::Scale(1), FillType::Scale(1)],
+            rows: vec![FillType::Scale(1), FillType::Scale(1)],
+            areas: vec![
+                vec!["sidebar".to_string(), "header".to_string()],
+                vec!["header".to_string(), "sidebar".to_string()],
+            ],
+        }),
+    );
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 5., offset_y: 0., length_y: 5. } },
+            node! { Frame { offset_x: 5., length_x: 5., offset_y: 0., length_y: 5. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    assert_eq!(
+        solver.lint(),
+        vec![
+            LintDiagnostic::MalformedGridArea {
+                constraint_key: root_constraint_key,
+            },
+            LintDiagnostic::IgnoredGridPlacement {
+                constraint_key: child_keys[0],
+            },
+            LintDiagnostic::IgnoredGridPlacement {
+                constraint_key: child_keys[1],
+            },
+        ],
+    );
+}
+
+#[test]
+fn test_solver_with_grid_spans() {
+    let exact = |length_x, length_y| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(length_y),
+            cross: FillType::Exact(length_x),
+        },
+        ..Default::default()
+    };
+
+    let root = Constraint {
+        content: Content {
+            mode: LayoutMode::Grid,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { exact(6., 2.) },
+        node! { exact(2., 2.) },
+        node! { exact(1., 1.) },
+        node! { exact(1., 3.) },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    solver.set_grid(
+        root_constraint_key,
+        Some(Grid {
+            columns: vec![FillType::Minimize, FillType::Minimize, FillType::Scale(1)],
+            ..Default::default()
+        }),
+    );
+    assert!(solver.set_grid_span(child_keys[0], 2, 1));
+    assert!(solver.set_grid_span(child_keys[3], 1, 2));
+    assert!(!solver.set_grid_span(child_keys[1], 0, 1));
+    assert_eq!(solver.grid_span(child_keys[1]), (1, 1));
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 6., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 6., length_x: 2., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 0., length_x: 1., offset_y: 2., length_y: 1. } },
+            node! { Frame { offset_x: 3., length_x: 1., offset_y: 2., length_y: 3. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_table() {
+    let root = Constraint {
+        content: Content {
+            mode: LayoutMode::Table,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let cell = |length_x, length_y| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(length_y),
+            cross: FillType::Exact(length_x),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default(), [
+            node! { cell(2., 1.) },
+            node! { cell(3., 1.) },
+        ] },
+        node! { Constraint::default(), [
+            node! { cell(1., 2.) },
+            node! { cell(1., 1.) },
+        ] },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 1. }, [
+                node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 1. } },
+                node! { Frame { offset_x: 4., length_x: 6., offset_y: 0., length_y: 1. } },
+            ] },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 1., length_y: 2. }, [
+                node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 2. } },
+                node! { Frame { offset_x: 4., length_x: 6., offset_y: 0., length_y: 2. } },
+            ] },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_solves_with_measure_context() {
+    struct FontSystem {
+        glyph_length: f64,
+        measure_count: usize,
+    }
+
+    let minimize = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Minimize,
+            cross: FillType::Minimize,
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { minimize },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    assert!(
+        solver.set_measure(child_key, |font_system: &mut FontSystem, _, _| {
+            font_system.measure_count += 1;
+            (font_system.glyph_length * 3., font_system.glyph_length)
+        })
+    );
+
+    let mut font_system = FontSystem {
+        glyph_length: 2.,
+        measure_count: 0,
+    };
+    solver.solve_with_context(&mut font_system, 10., 10.);
+
+    assert!(font_system.measure_count > 0);
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 6., offset_y: 0., length_y: 2. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_remeasures_when_measure_context_changes() {
+    struct FontSystem {
+        glyph_length: f64,
+    }
+
+    let minimize = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Minimize,
+            cross: FillType::Minimize,
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { minimize },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    assert!(
+        solver.set_measure(child_key, |font_system: &mut FontSystem, _, _| {
+            (font_system.glyph_length * 3., font_system.glyph_length)
+        })
+    );
+
+    let mismatched_diagnostics = vec![LintDiagnostic::MismatchedMeasureContext {
+        constraint_key: child_key,
+    }];
+
+    solver.solve(10., 10.);
+    assert_eq!(solver.lint(), mismatched_diagnostics);
+    assert_eq!(
+        solver.get_frame(child_key),
+        Some(Frame {
+            offset_x: 0.,
+            length_x: 0.,
+            offset_y: 0.,
+            length_y: 0.,
+        }),
+    );
+
+    solver.solve_with_context(&mut 0_u32, 10., 10.);
+    assert_eq!(solver.lint(), mismatched_diagnostics);
+
+    let mut font_system = FontSystem { glyph_length: 2. };
+    solver.solve_with_context(&mut font_system, 10., 10.);
+    assert!(solver.lint().is_empty());
+    assert_eq!(
+        solver.get_frame(child_key),
+        Some(Frame {
+            offset_x: 0.,
+            length_x: 6.,
+            offset_y: 0.,
+            length_y: 2.,
+        }),
+    );
+}
+
+#[test]
+fn test_solver_with_fraction_fill() {
+    let fraction = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Fraction(0.3),
+            cross: FillType::Fraction(0.5),
+        },
+        ..Default::default()
+    };
+    let scale = |scale| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Scale(scale),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { fraction },
+        node! { scale(1) },
+        node! { scale(3) },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 20.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 20. },
+        [
+            node! { Frame { offset_x: 0., length_x: 5., offset_y: 0., length_y: 6. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 6., length_y: 3.5 } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 9.5, length_y: 10.5 } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_bounded_fill() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let bounded = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Bounded {
+                min: 2.,
+                preferred: 3.,
+                max: 4.,
+            },
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(1.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    for (length_x, expected_lengths_x) in
+        [(10., [4., 4., 1.]), (7., [3., 3., 1.]), (5., [2., 2., 1.])]
+    {
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { bounded },
+            node! { bounded },
+            node! { exact },
+        ] }))
+        .unwrap();
+
+        solver.solve(length_x, 1.);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let [first, second, third] = expected_lengths_x;
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x, offset_y: 0., length_y: 1. },
+            [
+                node! { Frame { offset_x: 0., length_x: first, offset_y: 0., length_y: 1. } },
+                node! { Frame { offset_x: first, length_x: second, offset_y: 0., length_y: 1. } },
+                node! { Frame { offset_x: first + second, length_x: third, offset_y: 0., length_y: 1. } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+    }
+}
+
+#[test]
+fn test_solver_with_bounded_fill_in_wrap_and_cross() {
+    let bounded = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Bounded {
+                min: 2.,
+                preferred: 3.,
+                max: 4.,
+            },
+            cross: FillType::Bounded {
+                min: 1.,
+                preferred: 2.,
+                max: 3.,
+            },
+        },
+        ..Default::default()
+    };
+
+    let wrap = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            mode: LayoutMode::Wrap,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut solver = make_solver(Some(&node! { wrap, [
+        node! { bounded },
+        node! { bounded },
+    ] }))
+    .unwrap();
+
+    solver.solve(5., 4.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 5., offset_y: 0., length_y: 4. },
+        [
+            node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 0., length_x: 4., offset_y: 2., length_y: 2. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    let stretch = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            align_cross: Align::Stretch,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    for (length_y, expected_length_y) in [(4., 3.), (0.5, 1.)] {
+        let mut solver = make_solver(Some(&node! { stretch, [
+            node! { bounded },
+        ] }))
+        .unwrap();
+
+        solver.solve(10., length_y);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y },
+            [
+                node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: expected_length_y } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+    }
+}
+
+#[test]
+fn test_solver_with_flex_basis() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let based = Constraint {
+        basis: Some(4.),
+        ..Default::default()
+    };
+    let scaled = Constraint::default();
+
+    for (length_x, expected_lengths_x) in [(10., [7., 3.]), (6., [5., 1.]), (2., [2., 0.])] {
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { based },
+            node! { scaled },
+        ] }))
+        .unwrap();
+
+        solver.solve(length_x, 1.);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let [first, second] = expected_lengths_x;
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x, offset_y: 0., length_y: 1. },
+            [
+                node! { Frame { offset_x: 0., length_x: first, offset_y: 0., length_y: 1. } },
+                node! { Frame { offset_x: first, length_x: second, offset_y: 0., length_y: 1. } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+    }
+}
+
+#[test]
+fn test_solver_with_flex_basis_outside_relative_mode() {
+    let make_root = |mode, precedence| Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            mode,
+            precedence,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let make_child = |main, basis| Constraint {
+        fill: Fill::Relative {
+            main,
+            cross: FillType::Exact(1.),
+        },
+        basis,
+        ..Default::default()
+    };
+    let scaled = make_child(FillType::Scale(1), None);
+    let based = make_child(FillType::Scale(1), Some(4.));
+    let exact = make_child(FillType::Exact(4.), None);
+
+    for (root, first, expected_lengths_x) in [
+        (
+            make_root(LayoutMode::Wrap, Precedence::default()),
+            scaled,
+            [3., 7.],
+        ),
+        (
+            make_root(LayoutMode::Grid, Precedence::default()),
+            scaled,
+            [6., 4.],
+        ),
+        (
+            make_root(LayoutMode::Relative, Precedence::Proportional),
+            exact,
+            [2., 6.],
+        ),
+    ] {
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { first },
+            node! { based },
+        ] }))
+        .unwrap();
+
+        let root_constraint_key = solver.root_constraint_key().unwrap();
+        if root.content.mode == LayoutMode::Grid {
+            assert!(solver.set_grid(
+                root_constraint_key,
+                Some(Grid {
+                    columns: vec![FillType::Scale(1), FillType::Minimize],
+                    ..Default::default()
+                }),
+            ));
+        };
+
+        let length_x = expected_lengths_x.iter().sum::<f64>();
+        solver.solve(length_x, 1.);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let [first, second] = expected_lengths_x;
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x, offset_y: 0., length_y: 1. },
+            [
+                node! { Frame { offset_x: 0., length_x: first, offset_y: 0., length_y: 1. } },
+                node! { Frame { offset_x: first, length_x: second, offset_y: 0., length_y: 1. } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+    }
+}
+
+#[test]
+fn test_solver_with_layout_order() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let exact = |length_x, order| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(length_x),
+            cross: FillType::Scale(1),
+        },
+        order,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { exact(1., 1) },
+        node! { exact(2., 0) },
+        node! { exact(3., -1) },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 5., length_x: 1., offset_y: 0., length_y: 10. } },
+            node! { Frame { offset_x: 3., length_x: 2., offset_y: 0., length_y: 10. } },
+            node! { Frame { offset_x: 0., length_x: 3., offset_y: 0., length_y: 10. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_display_none() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let hidden = Constraint {
+        display: Display::None,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+        node! { hidden, [
+            node! { Constraint::default() },
+        ] },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 5., offset_y: 0., length_y: 10. } },
+            node! { Frame { offset_x: 0., length_x: 0., offset_y: 0., length_y: 0. }, [
+                node! { Frame { offset_x: 0., length_x: 0., offset_y: 0., length_y: 0. } },
+            ] },
+            node! { Frame { offset_x: 5., length_x: 5., offset_y: 0., length_y: 10. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_resolves_display_none_after_tree_change() {
+    let hidden = Constraint {
+        display: Display::None,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { hidden },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    solver
+        .insert(Constraint::default(), root_constraint_key)
+        .unwrap();
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 5. } },
+            node! { Frame { offset_x: 0., length_x: 0., offset_y: 0., length_y: 0. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 5., length_y: 5. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_hidden_visibility() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let hidden = Constraint {
+        visibility: Visibility::Hidden,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { hidden, [
+            node! { Constraint::default() },
+        ] },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 5., offset_y: 0., length_y: 10. }, [
+                node! { Frame { offset_x: 0., length_x: 5., offset_y: 0., length_y: 10. } },
+            ] },
+            node! { Frame { offset_x: 5., length_x: 5., offset_y: 0., length_y: 10. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    let grandchild_key = solver.get(child_keys[0]).unwrap().child_keys[0];
+    assert_eq!(
+        solver.get_visibility(root_constraint_key),
+        Some(Visibility::Visible)
+    );
+    assert_eq!(
+        solver.get_visibility(grandchild_key),
+        Some(Visibility::Hidden)
+    );
+    assert_eq!(
+        solver.get_visibility(child_keys[1]),
+        Some(Visibility::Visible)
+    );
+    assert_eq!(solver.hit_test(2., 2.), Some(root_constraint_key));
+
+    solver.set(
+        child_keys[0],
+        Constraint {
+            layer: Some(1),
+            ..Default::default()
+        },
+    );
+    assert!(!solver.is_dirty());
+    assert_eq!(
+        solver.get_visibility(grandchild_key),
+        Some(Visibility::Visible)
+    );
+    assert_eq!(solver.hit_test(2., 2.), Some(grandchild_key));
+    assert_eq!(make_frame_tree(&solver), expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_paint_order() {
+    let overlay = Constraint {
+        layer: Some(1),
+        ..Default::default()
+    };
+    let underlay = Constraint {
+        layer: Some(-1),
+        ..Default::default()
+    };
+    let root = Constraint {
+        content: Content {
+            mode: LayoutMode::Stack,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { overlay, [
+            node! { Constraint::default() },
+        ] },
+        node! { Constraint::default() },
+        node! { underlay },
+    ] }))
+    .unwrap();
+
+    assert_eq!(solver.paint_order(), None);
+
+    solver.solve(10., 10.);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    let grandchild_key = solver.get(child_keys[0]).unwrap().child_keys[0];
+    assert_eq!(
+        solver.paint_order(),
+        Some(vec![
+            child_keys[2],
+            root_constraint_key,
+            child_keys[1],
+            child_keys[0],
+            grandchild_key,
+        ])
+    );
+    assert_eq!(solver.hit_test(5., 5.), Some(grandchild_key));
+
+    solver.set(child_keys[0], Constraint::default());
+    solver.solve(10., 10.);
+
+    assert_eq!(solver.hit_test(5., 5.), Some(child_keys[1]));
+}
+
+#[test]
+fn test_solver_with_padding_fraction() {
+    let root = Constraint {
+        content: Content {
+            padding: Padding {
+                left: 1.,
+                ..Default::default()
+            },
+            padding_fraction: Padding {
+                left: 0.1,
+                top: 0.25,
+                bottom: 0.25,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(20., 8.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 20., offset_y: 0., length_y: 8. },
+        [
+            node! { Frame { offset_x: 3., length_x: 17., offset_y: 2., length_y: 4. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_padding_fraction_in_minimize_parent() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let minimize = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Minimize,
+            cross: FillType::Minimize,
+        },
+        content: Content {
+            padding_fraction: Padding {
+                left: 0.1,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let leaf = Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Exact(20.),
+            y: FillType::Exact(10.),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { minimize, [
+            node! { leaf },
+        ] },
+    ] }))
+    .unwrap();
+
+    solver.solve(100., 50.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 100., offset_y: 0., length_y: 50. },
+        [
+            node! { Frame { offset_x: 0., length_x: 30., offset_y: 0., length_y: 10. }, [
+                node! { Frame { offset_x: 10., length_x: 20., offset_y: 0., length_y: 10. } },
+            ] },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_border() {
+    let root = Constraint {
+        content: Content {
+            padding: Padding {
+                left: 2.,
+                ..Default::default()
+            },
+            border: Padding {
+                left: 1.,
+                right: 1.,
+                top: 1.,
+                bottom: 1.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 3., length_x: 6., offset_y: 1., length_y: 8. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    assert_eq!(
+        solver.get_border_box(root_constraint_key),
+        Some(Frame {
+            offset_x: 0.,
+            length_x: 10.,
+            offset_y: 0.,
+            length_y: 10.
+        })
+    );
+    assert_eq!(
+        solver.get_padding_box(root_constraint_key),
+        Some(Frame {
+            offset_x: 1.,
+            length_x: 8.,
+            offset_y: 1.,
+            length_y: 8.
+        })
+    );
+    assert_eq!(
+        solver.get_content_box(root_constraint_key),
+        Some(Frame {
+            offset_x: 3.,
+            length_x: 6.,
+            offset_y: 1.,
+            length_y: 8.
+        })
+    );
+}
+
+#[test]
+fn test_solver_with_collapsed_margins() {
+    let exact = |margin| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        margin,
+        ..Default::default()
+    };
+
+    for (bottom, top, collapse_margins, expected_offset_y) in [
+        (3., 1., false, 6.),
+        (3., 1., true, 5.),
+        (3., -1., true, 4.),
+        (-1., -2., false, -1.),
+        (-1., -2., true, 0.),
+    ] {
+        let first = exact(Padding {
+            bottom,
+            ..Default::default()
+        });
+        let second = exact(Padding {
+            top,
+            ..Default::default()
+        });
+        let root = Constraint {
+            content: Content {
+                collapse_margins,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { first },
+            node! { second },
+        ] }))
+        .unwrap();
+
+        solver.solve(10., 10.);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+            [
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 2. } },
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: expected_offset_y, length_y: 2. } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+    }
+}
+
+#[test]
+fn test_solver_advances_sibling_offsets_after_padding() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            padding: Padding {
+                left: 1.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { exact },
+        node! { Constraint::default() },
+        node! { exact },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 1., length_x: 2., offset_y: 0., length_y: 10. } },
+            node! { Frame { offset_x: 3., length_x: 5., offset_y: 0., length_y: 10. } },
+            node! { Frame { offset_x: 8., length_x: 2., offset_y: 0., length_y: 10. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_perpendicular_child_padding() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let column = Constraint {
+        content: Content {
+            direction: Direction::Vertical,
+            padding: Padding {
+                left: 1.,
+                top: 2.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { column, [
+            node! { Constraint::default() },
+        ] },
+    ] }))
+    .unwrap();
+
+    solver.solve(20., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 20., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 20., offset_y: 0., length_y: 10. }, [
+                node! { Frame { offset_x: 1., length_x: 19., offset_y: 2., length_y: 8. } },
+            ] },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_stacked_children() {
+    let root = Constraint {
+        content: Content {
+            padding: Padding {
+                left: 1.,
+                right: 1.,
+                top: 1.,
+                bottom: 1.,
+                ..Default::default()
+            },
+            mode: LayoutMode::Stack,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let control = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        justify_self: Some(Align::End),
+        align_self: Some(Align::Middle),
+        content: Content {
+            align_main: Align::Start,
+            align_cross: Align::Start,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+        node! { control, [
+            node! { Constraint { fill: Fill::Relative { main: FillType::Exact(1.), cross: FillType::Exact(1.) }, layout_ignored: true, ..Default::default() } },
+        ] },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 1., length_x: 8., offset_y: 1., length_y: 8. } },
+            node! { Frame { offset_x: 4., length_x: 2., offset_y: 7., length_y: 2. }, [
+                node! { Frame { offset_x: 0., length_x: 1., offset_y: 0., length_y: 1. } },
+            ] },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_space_distribution() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    for (align_main, expected_offsets_y) in [
+        (Align::SpaceBetween, [0., 5., 10.]),
+        (Align::SpaceAround, [1., 5., 9.]),
+        (Align::SpaceEvenly, [1.5, 5., 8.5]),
+    ] {
+        let root = Constraint {
+            content: Content {
+                align_main,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { exact },
+            node! { exact },
+            node! { exact },
+        ] }))
+        .unwrap();
+
+        solver.solve(10., 12.);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let [offset_y_1, offset_y_2, offset_y_3] = expected_offsets_y;
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 12. },
+            [
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: offset_y_1, length_y: 2. } },
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: offset_y_2, length_y: 2. } },
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: offset_y_3, length_y: 2. } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+    }
+}
+
+#[test]
+fn test_solver_with_equal_length_groups() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let grouped = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Minimize,
+            cross: FillType::Scale(1),
+        },
+        group: Some(0),
+        ..Default::default()
+    };
+    let exact = |length_x| Constraint {
+        fill: Fill::Absolute {
+            x: FillType::Exact(length_x),
+            y: FillType::Exact(1.),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { grouped, [node! { exact(2.) }] },
+        node! { grouped, [node! { exact(4.) }] },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! {
+                Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 10. },
+                [node! { Frame { offset_x: 0., length_x: 2., offset_y: 0., length_y: 1. } }]
+            },
+            node! {
+                Frame { offset_x: 4., length_x: 4., offset_y: 0., length_y: 10. },
+                [node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 1. } }]
+            },
+            node! { Frame { offset_x: 8., length_x: 2., offset_y: 0., length_y: 10. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_layout_ignored_children() {
+    let badge = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        content: Content {
+            align_main: Align::End,
+            align_cross: Align::End,
+            ..Default::default()
+        },
+        layout_ignored: true,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { badge },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. } },
+            node! { Frame { offset_x: 8., length_x: 2., offset_y: 8., length_y: 2. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_reports_resize_sensitivity() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    let minimize = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Minimize,
+            cross: FillType::Minimize,
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { exact, [
+            node! { Constraint::default() },
+        ] },
+        node! { minimize },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    assert_eq!(solver.resize_sensitivity(root_constraint_key), None);
+
+    solver.solve(10., 10.);
+
+    let root_node = solver.get(root_constraint_key).unwrap();
+    let exact_constraint_key = root_node.child_keys[0];
+    let minimize_constraint_key = root_node.child_keys[1];
+    let nested_constraint_key = solver.get(exact_constraint_key).unwrap().child_keys[0];
+    assert_eq!(
+        solver.resize_sensitivity(root_constraint_key),
+        Some((Sensitivity::Root, Sensitivity::Root))
+    );
+    assert_eq!(
+        solver.resize_sensitivity(exact_constraint_key),
+        Some((Sensitivity::Root, Sensitivity::Exact))
+    );
+    assert_eq!(
+        solver.resize_sensitivity(nested_constraint_key),
+        Some((Sensitivity::Root, Sensitivity::Exact))
+    );
+    assert_eq!(
+        solver.resize_sensitivity(minimize_constraint_key),
+        Some((Sensitivity::Content, Sensitivity::Content))
+    );
+}
+
+#[test]
+fn test_solver_reports_occupancy() {
+    let header = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    let badge = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        layout_ignored: true,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { header },
+        node! { badge },
+    ] }))
+    .unwrap();
+    assert!(solver.occupancy(2, 2).is_empty());
+
+    solver.solve(4., 4.);
+
+    assert_eq!(solver.occupancy(2, 2), vec![2, 1, 0, 0]);
+}
+
+#[test]
+fn test_solver_with_absolutely_positioned_children() {
+    let tooltip = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        position: Position::Absolute {
+            left: None,
+            right: Some(1.),
+            top: None,
+            bottom: Some(1.),
+        },
+        ..Default::default()
+    };
+    let overlay = Constraint {
+        position: Position::Absolute {
+            left: Some(1.),
+            right: Some(1.),
+            top: Some(2.),
+            bottom: None,
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { tooltip },
+        node! { overlay },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. } },
+            node! { Frame { offset_x: 7., length_x: 2., offset_y: 7., length_y: 2. } },
+            node! { Frame { offset_x: 1., length_x: 8., offset_y: 2., length_y: 8. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_nudges_frames_after_layout() {
+    let nudged = Constraint {
+        nudge_x: 1.,
+        nudge_y: -0.5,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { nudged },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 1., length_x: 10., offset_y: -0.5, length_y: 5. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 5., length_y: 5. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_baseline_alignment() {
+    let make_root = |align_cross| Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            align_cross,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let make_child = |cross| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(5.),
+            cross: FillType::Exact(cross),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { make_root(Align::FirstBaseline), [
+        node! { make_child(2.) },
+        node! { make_child(6.) },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    assert!(
+        solver.set_baseline_provider(child_keys[1], |_, length_y| Baseline {
+            first: 1.,
+            last: length_y - 1.,
+        })
+    );
+
+    solver.solve(20., 10.);
+
+    let actual_offsets = (
+        solver.get_frame(child_keys[0]).unwrap().offset_y,
+        solver.get_frame(child_keys[1]).unwrap().offset_y,
+    );
+    assert_eq!(actual_offsets, (0., 1.));
+
+    solver.set(root_constraint_key, make_root(Align::LastBaseline));
+    solver.solve(20., 10.);
+
+    let actual_offsets = (
+        solver.get_frame(child_keys[0]).unwrap().offset_y,
+        solver.get_frame(child_keys[1]).unwrap().offset_y,
+    );
+    assert_eq!(actual_offsets, (3., 0.));
+    assert_eq!(
+        solver.get_baseline(child_keys[0]),
+        Some(Baseline {
+            first: 2.,
+            last: 2.
+        })
+    );
+    assert_eq!(
+        solver.get_baseline(child_keys[1]),
+        Some(Baseline {
+            first: 1.,
+            last: 5.
+        })
+    );
+
+    let container = Constraint {
+        content: Content {
+            padding: Padding {
+                top: 2.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..make_child(6.)
+    };
+    let container_key = solver.insert(container, root_constraint_key).unwrap();
+    let label_key = solver.insert(Constraint::default(), container_key).unwrap();
+    solver.set_baseline_provider(label_key, |_, _| Baseline {
+        first: 1.,
+        last: 1.,
+    });
+    solver.set(root_constraint_key, make_root(Align::FirstBaseline));
+    solver.solve(20., 10.);
+
+    let actual_offsets = (
+        solver.get_frame(child_keys[0]).unwrap().offset_y,
+        solver.get_frame(child_keys[1]).unwrap().offset_y,
+        solver.get_frame(container_key).unwrap().offset_y,
+    );
+    assert_eq!(actual_offsets, (1., 2., 0.));
+    assert_eq!(
+        solver.get_baseline(container_key),
+        Some(Baseline {
+            first: 3.,
+            last: 3.
+        })
+    );
+    assert_eq!(
+        solver.get_baseline(root_constraint_key),
+        Some(Baseline {
+            first: 3.,
+            last: 3.
+        })
+    );
+}
+
+#[test]
+fn test_solver_with_baselines_in_layout_order() {
+    let item = |order| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        order,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { item(1) },
+        node! { item(0) },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    for &constraint_key in &child_keys {
+        solver.set_baseline_provider(constraint_key, |_, _| Baseline {
+            first: 1.,
+            last: 1.,
+        });
+    }
+    solver.solve(10., 10.);
+
+    assert_eq!(solver.get_frame(child_keys[0]).unwrap().offset_y, 2.);
+    assert_eq!(
+        solver.get_baseline(root_constraint_key),
+        Some(Baseline {
+            first: 1.,
+            last: 3.
+        })
+    );
+}
+
+#[test]
+fn test_solver_with_stretch_alignment() {
+    let minimize = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Minimize,
+        },
+        ..Default::default()
+    };
+
+    for (align_cross, expected_length_x) in [(Align::Start, 0.), (Align::Stretch, 10.)] {
+        let root = Constraint {
+            content: Content {
+                align_cross,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { minimize },
+        ] }))
+        .unwrap();
+
+        solver.solve(10., 10.);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+            [
+                node! { Frame { offset_x: 0., length_x: expected_length_x, offset_y: 0., length_y: 2. } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree);
+    }
+}
+
+#[test]
+fn test_solver_with_align_self() {
+    let scale = |align_self| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        intrinsic_size: Some((3., 2.)),
+        align_self,
+        ..Default::default()
+    };
+    let minimize = |align_self| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Minimize,
+        },
+        intrinsic_size: Some((4., 2.)),
+        align_self,
+        ..Default::default()
+    };
+
+    for (mode, [scale_x, end_x, stretch_x, middle_x]) in [
+        (
+            LayoutMode::Relative,
+            [(0., 10.), (7., 3.), (0., 10.), (3., 4.)],
+        ),
+        (LayoutMode::Flow, [(0., 4.), (1., 3.), (0., 4.), (0., 4.)]),
+    ] {
+        let root = Constraint {
+            content: Content {
+                mode,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut solver = make_solver(Some(&node! { root, [
+            node! { scale(None) },
+            node! { scale(Some(Align::End)) },
+            node! { minimize(Some(Align::Stretch)) },
+            node! { minimize(Some(Align::Middle)) },
+        ] }))
+        .unwrap();
+
+        solver.solve(10., 10.);
+
+        let actual_frame_tree = make_frame_tree(&solver);
+        let expected_frame_tree = Some(node! {
+            Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+            [
+                node! { Frame { offset_x: scale_x.0, length_x: scale_x.1, offset_y: 0., length_y: 2. } },
+                node! { Frame { offset_x: end_x.0, length_x: end_x.1, offset_y: 2., length_y: 2. } },
+                node! { Frame { offset_x: stretch_x.0, length_x: stretch_x.1, offset_y: 4., length_y: 2. } },
+                node! { Frame { offset_x: middle_x.0, length_x: middle_x.1, offset_y: 6., length_y: 2. } },
+            ]
+        });
+        assert_eq!(actual_frame_tree, expected_frame_tree, "{mode:?}");
+    }
+}
+
+#[test]
+fn test_solver_stretches_to_tallest_child() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            stretch_to_tallest: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let make_child = |cross| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(5.),
+            cross,
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { make_child(FillType::Exact(3.)) },
+        node! { make_child(FillType::Exact(6.)) },
+        node! { make_child(FillType::Scale(1)) },
+    ] }))
+    .unwrap();
+
+    solver.solve(20., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 20., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 5., offset_y: 0., length_y: 6. } },
+            node! { Frame { offset_x: 5., length_x: 5., offset_y: 0., length_y: 6. } },
+            node! { Frame { offset_x: 10., length_x: 5., offset_y: 0., length_y: 6. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_child_defaults() {
+    let root = Constraint {
+        content: Content {
+            default_child: Some(ChildDefaults {
+                fill: Fill::Relative {
+                    main: FillType::Exact(2.),
+                    cross: FillType::Scale(1),
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let explicit = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(5.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { Constraint::default() },
+        node! { explicit },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 2. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 2., length_y: 5. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 7., length_y: 2. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_lints_suspicious_constraints() {
+    let zero_scale = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Scale(0),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    let minimize = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Minimize,
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+    let padded = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        content: Content {
+            padding: Padding {
+                top: 3.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let badge = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        content: Content {
+            align_main: Align::Fraction(AlignFraction(2.)),
+            ..Default::default()
+        },
+        layout_ignored: true,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { zero_scale },
+        node! { minimize },
+        node! { padded },
+        node! { badge },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+
+    let actual_diagnostics = solver.lint();
+    let expected_diagnostics = vec![
+        LintDiagnostic::ZeroScaleWeight {
+            constraint_key: root_constraint_key,
+        },
+        LintDiagnostic::UnmeasuredMinimize {
+            constraint_key: child_keys[1],
+        },
+        LintDiagnostic::PaddingExceedsLength {
+            constraint_key: child_keys[2],
+        },
+    ];
+    assert_eq!(actual_diagnostics, expected_diagnostics);
+
+    solver.solve(10., 10.);
+
+    let actual_diagnostics = solver.lint();
+    let expected_diagnostics = vec![
+        LintDiagnostic::UnreachableLayoutIgnored {
+            constraint_key: child_keys[3],
+        },
+        LintDiagnostic::ZeroScaleWeight {
+            constraint_key: root_constraint_key,
+        },
+        LintDiagnostic::UnmeasuredMinimize {
+            constraint_key: child_keys[1],
+        },
+        LintDiagnostic::PaddingExceedsLength {
+            constraint_key: child_keys[2],
+        },
+    ];
+    assert_eq!(actual_diagnostics, expected_diagnostics);
+}
+
+#[test]
+fn test_solver_snaps_frames_to_integer_pixels() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let actual_frames = solver
+        .get(root_constraint_key)
+        .unwrap()
+        .child_keys
+        .iter()
+        .map(|&constraint_key| solver.get_frame_i32(constraint_key).unwrap())
+        .collect::<Vec<_>>();
+    let expected_frames = vec![
+        FrameI32 {
+            x: 0,
+            y: 0,
+            w: 10,
+            h: 3,
+        },
+        FrameI32 {
+            x: 0,
+            y: 3,
+            w: 10,
+            h: 4,
+        },
+        FrameI32 {
+            x: 0,
+            y: 7,
+            w: 10,
+            h: 3,
+        },
+    ];
+    assert_eq!(actual_frames, expected_frames);
+}
+
+#[test]
+fn test_solver_with_rounding_policies() {
+    for (rounding_policy, expected_spans) in [
+        (RoundingPolicy::Round, [(0, 3), (3, 2), (5, 3), (8, 2)]),
+        (
+            RoundingPolicy::RoundHalfEven,
+            [(0, 2), (2, 3), (5, 3), (8, 2)],
+        ),
+        (
+            RoundingPolicy::FloorOffsetsCeilExtents,
+            [(0, 3), (2, 3), (5, 3), (7, 3)],
+        ),
+        (RoundingPolicy::Truncate, [(0, 2), (2, 3), (5, 2), (7, 3)]),
+    ] {
+        let mut solver = make_solver(Some(&node! { Constraint::default(), [
+            node! { Constraint::default() },
+            node! { Constraint::default() },
+            node! { Constraint::default() },
+            node! { Constraint::default() },
+        ] }))
+        .unwrap();
+
+        solver.set_rounding_policy(rounding_policy);
+        solver.solve(10., 10.);
+
+        let root_constraint_key = solver.root_constraint_key().unwrap();
+        let actual_spans = solver
+            .get(root_constraint_key)
+            .unwrap()
+            .child_keys
+            .iter()
+            .map(|&constraint_key| {
+                let frame_i32 = solver.get_frame_i32(constraint_key).unwrap();
+                (frame_i32.y, frame_i32.h)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(actual_spans, expected_spans);
+    }
+}
+
+#[test]
+fn test_solver_with_scale_factor() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint { fill: Fill::Relative { main: FillType::Exact(2.5), cross: FillType::Scale(1) }, ..Default::default() } },
+    ] }))
+    .unwrap();
+
+    assert!(!solver.set_scale_factor(0.));
+    assert!(solver.set_scale_factor(2.));
+
+    solver.solve(10., 10.);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+
+    let actual_frames = (
+        solver.get_frame(constraint_key).unwrap(),
+        solver.get_physical_frame(constraint_key).unwrap(),
+        solver.get_frame_i32(constraint_key).unwrap(),
+    );
+    let expected_frames = (
+        Frame {
+            offset_x: 0.,
+            length_x: 10.,
+            offset_y: 0.,
+            length_y: 2.5,
+        },
+        Frame {
+            offset_x: 0.,
+            length_x: 20.,
+            offset_y: 0.,
+            length_y: 5.,
+        },
+        FrameI32 {
+            x: 0,
+            y: 0,
+            w: 20,
+            h: 5,
+        },
+    );
+    assert_eq!(actual_frames, expected_frames);
+}
+
+#[test]
+fn test_solver_reads_last_frame_while_dirty() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    assert_eq!(solver.get_last_frame(constraint_key), None);
+
+    solver.solve(10., 10.);
+
+    let frame = Frame {
+        offset_x: 0.,
+        length_x: 10.,
+        offset_y: 0.,
+        length_y: 10.,
+    };
+    let new_constraint_key = solver
+        .insert(Constraint::default(), root_constraint_key)
+        .unwrap();
+    assert!(solver.is_dirty());
+    assert_eq!(solver.get_frame(constraint_key), None);
+    assert_eq!(solver.get_last_frame(constraint_key), Some(frame));
+    assert_eq!(solver.get_last_frame(new_constraint_key), None);
+
+    solver.solve(10., 10.);
+    assert_eq!(
+        solver.get_last_frame(constraint_key),
+        solver.get_frame(constraint_key)
+    );
+
+    solver.remove(constraint_key, None);
+    assert_eq!(solver.get_last_frame(constraint_key), None);
+}
+
+#[test]
+fn test_constraint_lerp() {
+    let collapsed = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(0.),
+            cross: FillType::Scale(1),
+        },
+        content: Content {
+            direction: Direction::Horizontal,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let expanded = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(10.),
+            cross: FillType::Scale(3),
+        },
+        content: Content {
+            padding: Padding {
+                left: 4.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        layout_ignored: true,
+        ..Default::default()
+    };
+
+    let actual_constraint = collapsed.lerp(&expanded, 0.25);
+    let expected_constraint = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.5),
+            cross: FillType::Scale(2),
+        },
+        content: Content {
+            direction: Direction::Horizontal,
+            padding: Padding {
+                left: 1.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(actual_constraint, expected_constraint);
+
+    assert_eq!(collapsed.lerp(&expanded, 1.), expanded);
+    assert_eq!(collapsed.lerp(&expanded, 0.), collapsed);
+}
+
+#[test]
+fn test_solver_estimates_virtualized_extent() {
+    let root = Constraint {
+        content: Content {
+            gap_main: Some(FillType::Exact(1.)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let row = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(3.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { row },
+        node! { row },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    assert!(solver.set_estimated_child_extent(root_constraint_key, 2.));
+    assert!(!solver.set_estimated_child_extent(root_constraint_key, f64::NAN));
+    assert_eq!(solver.estimated_total_extent(root_constraint_key), None);
+
+    solver.solve(10., 10.);
+    assert_eq!(solver.estimated_total_extent(root_constraint_key), Some(7.));
+
+    assert!(solver.set_virtual_child_count(root_constraint_key, 100));
+    assert_eq!(
+        solver.estimated_total_extent(root_constraint_key),
+        Some(6. + 98. * 2. + 99.)
+    );
+}
+
+#[test]
+fn test_solver_inserts_presets() {
+    let mut solver = Solver::default();
+    let root_constraint_key = solver
+        .insert_declarative(&presets::sidebar(3.), None)
+        .unwrap();
+    let main_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[1];
+    solver
+        .insert_declarative(&presets::split_pane(0.25), Some(main_constraint_key))
+        .unwrap();
+    solver
+        .insert_declarative(&presets::centered_modal(4., 2.), Some(main_constraint_key))
+        .unwrap();
+    assert_eq!(solver.insert_declarative(&presets::toolbar(), None), None);
+
+    solver.solve(11., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 11., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 3., offset_y: 0., length_y: 10. } },
+            node! {
+                Frame { offset_x: 3., length_x: 8., offset_y: 0., length_y: 10. },
+                [
+                    node! {
+                        Frame { offset_x: 0., length_x: 8., offset_y: 0., length_y: 5. },
+                        [
+                            node! { Frame { offset_x: 0., length_x: 2., offset_y: 0., length_y: 5. } },
+                            node! { Frame { offset_x: 2., length_x: 6., offset_y: 0., length_y: 5. } },
+                        ]
+                    },
+                    node! {
+                        Frame { offset_x: 0., length_x: 8., offset_y: 5., length_y: 5. },
+                        [node! { Frame { offset_x: 2., length_x: 4., offset_y: 1.5, length_y: 2. } }]
+                    },
+                ]
+            },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_shrinks_centered_modal() {
+    let mut solver = Solver::default();
+    solver
+        .insert_declarative(&presets::centered_modal(4., 2.), None)
+        .unwrap();
+
+    solver.solve(3., 1.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 3., offset_y: 0., length_y: 1. },
+        [node! { Frame { offset_x: 0., length_x: 3., offset_y: 0., length_y: 1. } }]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_baseline_grid() {
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(3.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { exact },
+        node! { exact },
+        node! { exact },
+    ] }))
+    .unwrap();
+
+    assert!(!solver.set_baseline_grid(Some(0.)));
+    assert!(solver.set_baseline_grid(Some(4.)));
+
+    solver.solve(10., 20.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 20. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 3. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 4., length_y: 3. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 8., length_y: 3. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_reversed_baseline_grid() {
+    let root = Constraint {
+        content: Content {
+            reverse: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(3.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { exact },
+        node! { exact },
+        node! { exact },
+    ] }))
+    .unwrap();
+
+    assert!(solver.set_baseline_grid(Some(4.)));
+
+    solver.solve(10., 20.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 20. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 16., length_y: 3. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 12., length_y: 3. } },
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 8., length_y: 3. } },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_snaps_nested_containers_to_baseline_grid() {
+    let root = Constraint {
+        content: Content {
+            direction: Direction::Horizontal,
+            align_cross: Align::Middle,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let container = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Scale(1),
+            cross: FillType::Exact(8.),
+        },
+        ..Default::default()
+    };
+    let exact = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(1.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { root, [
+        node! { container, [
+            node! { exact },
+            node! { exact },
+        ] },
+    ] }))
+    .unwrap();
+
+    assert!(solver.set_baseline_grid(Some(4.)));
+
+    solver.solve(10., 10.);
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { Frame { offset_x: 0., length_x: 10., offset_y: 1., length_y: 8. }, [
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: 3., length_y: 1. } },
+                node! { Frame { offset_x: 0., length_x: 10., offset_y: 7., length_y: 1. } },
+            ] },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+}
+
+#[test]
+fn test_solver_with_compensated_scale_distribution() {
+    let bounded = Constraint {
+        bounds: Bounds {
+            min_y: 1.3,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { bounded },
+        node! { bounded },
+        node! { bounded },
+        node! { bounded },
+        node! { bounded },
+        node! { bounded },
+        node! { bounded },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[7];
+
+    solver.solve(10., 10.);
+    let length_y = solver.get_frame(constraint_key).unwrap().length_y;
+    assert_eq!(length_y, 0.9000000000000001);
+
+    solver.set_deterministic(true);
+    assert!(solver.is_dirty());
+
+    solver.solve(10., 10.);
+    let length_y = solver.get_frame(constraint_key).unwrap().length_y;
+    assert_eq!(length_y, 0.8999999999999997);
+}
+
+#[test]
+fn test_solver_composes_transforms() {
+    let padded = Constraint {
+        content: Content {
+            padding: Padding {
+                left: 1.,
+                right: 1.,
+                top: 2.,
+                bottom: 2.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { padded, [
+        node! { Constraint::default() },
+        node! { padded, [node! { Constraint::default() }] },
+    ] }))
+    .unwrap();
+
+    solver.solve(10., 10.);
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[1];
+    let leaf_constraint_key = solver.get(constraint_key).unwrap().child_keys[0];
+
+    let transform = solver.get_transform(leaf_constraint_key).unwrap();
+    assert_eq!(transform, Transform::translation(2., 7.));
+    assert_eq!(transform.apply(1., 1.), (3., 8.));
+    assert_eq!(transform.inverse().unwrap().apply(3., 8.), (1., 1.));
+
+    let sibling_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    let actual_point = solver.convert_point(leaf_constraint_key, sibling_constraint_key, 1., 1.);
+    let expected_point = Some((2., 6.));
+    assert_eq!(actual_point, expected_point);
+
+    assert_eq!(solver.to_world(leaf_constraint_key, 1., 1.), Some((3., 8.)));
+    assert_eq!(solver.to_local(leaf_constraint_key, 3., 8.), Some((1., 1.)));
+}
+
+#[test]
+fn test_solver_hit_tests_with_hit_slop() {
+    let button = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        hit_slop: Padding {
+            left: 1.,
+            right: 1.,
+            top: 1.,
+            bottom: 1.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { button },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+
+    assert_eq!(solver.hit_test(1., 1.), None);
+
+    solver.solve(10., 10.);
+
+    assert_eq!(solver.hit_test(1., 1.), Some(child_keys[0]));
+    assert_eq!(solver.hit_test(2.5, 8.5), Some(child_keys[1]));
+    assert_eq!(solver.hit_test(3.5, 8.5), Some(root_constraint_key));
+    assert_eq!(solver.hit_test(11., 1.), None);
+}
+
+#[test]
+fn test_solver_clamps_corner_radius_for_hit_testing() {
+    let rounded = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(4.),
+            cross: FillType::Exact(4.),
+        },
+        corner_radius: 10.,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { rounded },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    assert_eq!(solver.get_corner_radius(constraint_key), None);
+
+    solver.solve(10., 10.);
+
+    assert_eq!(solver.get_corner_radius(constraint_key), Some(2.));
+    assert_eq!(solver.get_corner_radius(root_constraint_key), Some(0.));
+    assert_eq!(solver.hit_test(0.2, 0.2), Some(root_constraint_key));
+    assert_eq!(solver.hit_test(2., 0.1), Some(constraint_key));
+    assert_eq!(solver.hit_test(1., 1.), Some(constraint_key));
+}
+
+#[test]
+fn test_solver_assigns_traversal_indices_in_layout_order() {
+    let badge = |align_main| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        content: Content {
+            align_main,
+            ..Default::default()
+        },
+        layout_ignored: true,
+        ..Default::default()
+    };
+    let item = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { badge(Align::End) },
+        node! { item },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    assert!(!solver.assign_traversal_indices());
+
+    solver.solve(10., 10.);
+    assert!(solver.assign_traversal_indices());
+    assert_eq!(solver.get_traversal_index(root_constraint_key), Some(0));
+    assert_eq!(solver.get_traversal_index(child_keys[0]), Some(2));
+    assert_eq!(solver.get_traversal_index(child_keys[1]), Some(1));
+
+    solver.set(child_keys[0], badge(Align::Start));
+    solver.solve(10., 10.);
+    assert_eq!(solver.get_traversal_index(child_keys[0]), Some(1));
+    assert_eq!(solver.get_traversal_index(child_keys[1]), Some(2));
+}
+
+#[test]
+fn test_solver_refreshes_traversal_indices_within_islands() {
+    let badge = |align_main| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        content: Content {
+            align_main,
+            ..Default::default()
+        },
+        layout_ignored: true,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default(), [
+            node! { badge(Align::End) },
+            node! { Constraint::default() },
+        ] },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let child_keys = solver.get(root_constraint_key).unwrap().child_keys.clone();
+    let island_constraint_key = child_keys[0];
+    let island_child_keys = solver
+        .get(island_constraint_key)
+        .unwrap()
+        .child_keys
+        .clone();
+    assert!(solver.set_island(island_constraint_key, 4., 4.));
+
+    solver.solve(10., 10.);
+    assert!(solver.assign_traversal_indices());
+    assert_eq!(solver.get_traversal_index(island_constraint_key), Some(1));
+    assert_eq!(solver.get_traversal_index(island_child_keys[0]), Some(3));
+    assert_eq!(solver.get_traversal_index(island_child_keys[1]), Some(2));
+    assert_eq!(solver.get_traversal_index(child_keys[1]), Some(4));
+
+    solver.set(island_child_keys[0], badge(Align::Start));
+    assert_eq!(solver.is_island_dirty(island_constraint_key), Some(true));
+    solver.solve(10., 10.);
+    assert_eq!(solver.get_traversal_index(island_child_keys[0]), Some(2));
+    assert_eq!(solver.get_traversal_index(island_child_keys[1]), Some(3));
+    assert_eq!(solver.get_traversal_index(child_keys[1]), Some(4));
+
+    solver.remove(island_child_keys[0], None);
+    assert_eq!(solver.is_island_dirty(island_constraint_key), Some(true));
+    solver.solve(10., 10.);
+    assert_eq!(solver.get_traversal_index(island_child_keys[0]), None);
+    assert_eq!(solver.get_traversal_index(island_child_keys[1]), Some(2));
+    assert_eq!(solver.get_traversal_index(child_keys[1]), Some(3));
+}
+
+#[test]
+fn test_solver_hit_tests_with_clipping() {
+    let make_container = |overflow| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(5.),
+            cross: FillType::Scale(1),
+        },
+        content: Content {
+            overflow,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let badge = Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(2.),
+            cross: FillType::Exact(2.),
+        },
+        content: Content {
+            align_main: Align::Fraction(AlignFraction(2.)),
+            ..Default::default()
+        },
+        layout_ignored: true,
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { make_container(Overflow::Clip), [node! { badge }] },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let container_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    let badge_constraint_key = solver.get(container_constraint_key).unwrap().child_keys[0];
+
+    solver.solve(10., 10.);
+
+    assert!(!solver.is_clipping_hits(container_constraint_key));
+    assert_eq!(solver.hit_test(1., 7.), Some(badge_constraint_key));
+
+    assert!(solver.set_clipping_hits(container_constraint_key, true));
+    assert_eq!(solver.scroll_offset(container_constraint_key), None);
+    assert_eq!(solver.hit_test(1., 7.), Some(root_constraint_key));
+    assert_eq!(
+        solver.hit_test_with(1., 7., true),
+        Some(badge_constraint_key)
+    );
+
+    solver.set(container_constraint_key, make_container(Overflow::Overflow));
+    solver.solve(10., 10.);
+
+    assert!(solver.is_clipping_hits(container_constraint_key));
+    assert_eq!(solver.hit_test(1., 7.), Some(root_constraint_key));
+
+    assert!(solver.set_clipping_hits(container_constraint_key, false));
+    assert!(!solver.is_clipping_hits(container_constraint_key));
+    assert_eq!(solver.hit_test(1., 7.), Some(badge_constraint_key));
+}
+
+#[test]
+fn test_solver_with_embedded_solver() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { Constraint::default() },
+    ] }))
+    .unwrap();
+    let embedded = Arc::new(Mutex::new(
+        make_solver(Some(&node! { Constraint::default() })).unwrap(),
+    ));
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let slot_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[1];
+    assert!(solver.set_embedded(slot_constraint_key, embedded.clone()));
+
+    solver.solve(10., 10.);
+
+    let embedded_root_frame = {
+        let embedded = embedded.lock().unwrap();
+        embedded.get_frame(embedded.root_constraint_key().unwrap())
+    };
+    let expected_frame = Frame {
+        offset_x: 0.,
+        length_x: 10.,
+        offset_y: 0.,
+        length_y: 5.,
+    };
+    assert_eq!(embedded_root_frame, Some(expected_frame));
+
+    let embedded_constraint_key = {
+        let mut embedded = embedded.lock().unwrap();
+        let embedded_root_constraint_key = embedded.root_constraint_key().unwrap();
+        embedded
+            .insert(Constraint::default(), embedded_root_constraint_key)
+            .unwrap()
+    };
+    assert!(!solver.is_dirty());
+
+    solver.solve(10., 10.);
+
+    let embedded_frame = embedded.lock().unwrap().get_frame(embedded_constraint_key);
+    assert_eq!(embedded_frame, Some(expected_frame));
+
+    let arrange_count = Arc::new(Mutex::new(0));
+    {
+        let mut embedded = embedded.lock().unwrap();
+        let embedded_root_constraint_key = embedded.root_constraint_key().unwrap();
+        let arrange_count = arrange_count.clone();
+        embedded.set_hook(embedded_root_constraint_key, Phase::Arrange, move |_, _| {
+            *arrange_count.lock().unwrap() += 1;
+        });
+    };
+
+    let first_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    solver.set(
+        first_constraint_key,
+        Constraint {
+            fill: Fill::Relative {
+                main: FillType::Exact(2.),
+                cross: FillType::Scale(1),
+            },
+            ..Default::default()
+        },
+    );
+    solver.solve(10., 10.);
+
+    let embedded_frame = embedded.lock().unwrap().get_frame(embedded_constraint_key);
+    assert_eq!(
+        embedded_frame,
+        Some(Frame {
+            length_y: 8.,
+            ..expected_frame
+        })
+    );
+    assert_eq!(*arrange_count.lock().unwrap(), 1);
+
+    assert!(solver.remove_embedded(slot_constraint_key));
+    assert!(solver.is_dirty());
+}
+
+#[test]
+fn test_solver_with_scroll_anchoring() {
+    let list = Constraint {
+        content: Content {
+            overflow: Overflow::Overflow,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let make_row = |length_y| Constraint {
+        fill: Fill::Relative {
+            main: FillType::Exact(length_y),
+            cross: FillType::Scale(1),
+        },
+        ..Default::default()
+    };
+
+    let mut solver = make_solver(Some(&node! { list, [
+        node! { make_row(4.) },
+        node! { make_row(4.) },
+        node! { make_row(4.) },
+    ] }))
+    .unwrap();
+
+    let list_constraint_key = solver.root_constraint_key().unwrap();
+    let anchor_constraint_key = solver.get(list_constraint_key).unwrap().child_keys[1];
+
+    solver.solve(10., 10.);
+
+    assert!(solver.set_scroll_offset(list_constraint_key, 0., 5.));
+    assert!(solver.set_scroll_anchoring(list_constraint_key, true));
+    assert_eq!(
+        solver.to_world(anchor_constraint_key, 0., 0.),
+        Some((0., -1.))
+    );
+
+    let new_constraint_key = solver.insert(make_row(3.), list_constraint_key).unwrap();
+    solver.reorder_children(list_constraint_key, |child_keys| {
+        let mut reordered_child_keys = child_keys.clone();
+        reordered_child_keys.move_index(child_keys.len() - 1, 0);
+        reordered_child_keys
+    });
+    solver.solve(10., 10.);
+
+    assert_eq!(solver.get_frame(new_constraint_key).unwrap().offset_y, 0.);
+    assert_eq!(solver.scroll_offset(list_constraint_key), Some((0., 8.)));
+    assert_eq!(
+        solver.to_world(anchor_constraint_key, 0., 0.),
+        Some((0., -1.))
+    );
+}
+
+#[test]
+fn test_solver_with_island() {
+    let mut solver = make_solver(Some(&node! { Constraint::default(), [
+        node! { Constraint::default() },
+        node! { Constraint::default(), [node! { Constraint::default() }] },
+    ] }))
+    .unwrap();
+
+    let root_constraint_key = solver.root_constraint_key().unwrap();
+    let chrome_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[0];
+    let island_constraint_key = solver.get(root_constraint_key).unwrap().child_keys[1];
+    assert!(!solver.set_island(root_constraint_key, 4., 4.));
+    assert!(solver.set_island(island_constraint_key, 4., 4.));
+
+    solver.solve(10., 10.);
+
+    let chrome_frame = Frame {
+        offset_x: 0.,
+        length_x: 10.,
+        offset_y: 0.,
+        length_y: 6.,
+    };
+    assert_eq!(solver.get_frame(chrome_constraint_key), Some(chrome_frame));
+
+    let new_constraint_key = solver
+        .insert(Constraint::default(), island_constraint_key)
+        .unwrap();
+    assert!(solver.is_dirty());
+    assert_eq!(solver.is_island_dirty(island_constraint_key), Some(true));
+    assert_eq!(solver.get_frame(chrome_constraint_key), Some(chrome_frame));
+    assert_eq!(solver.get_frame(new_constraint_key), None);
+
+    assert!(solver.solve_island(island_constraint_key));
+    assert!(!solver.is_dirty());
+
+    let actual_frame_tree = make_frame_tree(&solver);
+    let expected_frame_tree = Some(node! {
+        Frame { offset_x: 0., length_x: 10., offset_y: 0., length_y: 10. },
+        [
+            node! { chrome_frame },
+            node! {
+                Frame { offset_x: 0., length_x: 4., offset_y: 6., length_y: 4. },
+                [
+                    node! { Frame { offset_x: 0., length_x: 4., offset_y: 0., length_y: 2. } },
+                    node! { Frame { offset_x: 0., length_x: 4., offset_y: 2., length_y: 2. } },
+                ]
+            },
+        ]
+    });
+    assert_eq!(actual_frame_tree, expected_frame_tree);
+    assert_eq!(solver.to_world(new_constraint_key, 0., 0.), Some((0., 8.)));
+
+    solver.remove(island_constraint_key, None);
+    assert!(!solver.solve_island(island_constraint_key));
+}